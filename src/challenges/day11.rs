@@ -1,5 +1,5 @@
 use crate::display::ImageNormal;
-use crate::intcode::intcode_io::{Input, Output};
+use crate::intcode::intcode_io::{FrameHandler, FramedOutput, Input};
 use crate::intcode::IntCode;
 use crate::util::parse_intcode;
 use anyhow::{Error, Result};
@@ -24,11 +24,11 @@ pub fn part2(input: &str) -> Result<String> {
 }
 
 fn run_robot(intcode: Vec<i64>, hull: Hull) -> Result<Robot> {
-    let robot_io = RobotIO::new(hull);
+    let robot_io = FramedOutput::new(RobotIO::new(hull));
     let mut ic = IntCode::new_from_device(intcode, robot_io);
     ic.run_till_end()?;
-    let (_, output) = ic.emit();
-    Ok(output.robot)
+    let output = ic.into_device();
+    Ok(output.into_inner().robot)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -178,14 +178,12 @@ impl Robot {
 
 struct RobotIO {
     robot: Robot,
-    is_rotate: bool,
 }
 
 impl RobotIO {
     fn new(hull: Hull) -> RobotIO {
         RobotIO {
             robot: Robot::new(hull),
-            is_rotate: false,
         }
     }
 }
@@ -198,19 +196,13 @@ impl Input for RobotIO {
     }
 }
 
-impl Output for RobotIO {
-    fn output(&mut self, out: i64) -> Result<(), Error> {
+impl FrameHandler<(Color, Rotation)> for RobotIO {
+    fn handle(&mut self, (color, rotation): (Color, Rotation)) -> Result<(), Error> {
         let robot = &mut self.robot;
-        if self.is_rotate {
-            let r = Rotation::from(out);
-            robot.robot.rotate_advance(r);
-        } else {
-            let c = Color::from(out);
-            let x = robot.robot.x;
-            let y = robot.robot.y;
-            robot.hull.write(x, y, c);
-        }
-        self.is_rotate = !self.is_rotate;
+        let x = robot.robot.x;
+        let y = robot.robot.y;
+        robot.hull.write(x, y, color);
+        robot.robot.rotate_advance(rotation);
         Ok(())
     }
 }
@@ -1,45 +1,46 @@
 use crate::challenges::day13::game::{Board, Screen, Tile};
 //use crate::display::ImageNormal;
-use crate::intcode::{run_intcode, IntCode};
+use crate::intcode::intcode_io::{FramedOutput, NullIO};
+use crate::intcode::{Int, IntCode};
 use crate::util::parse_intcode;
 use anyhow::Result;
 
 pub fn part1(input: &str) -> Result<String> {
     let intcode = parse_intcode(input)?;
-    let (_, out) = run_intcode(intcode, vec![])?;
-    let mut board = Board::default();
-    for chunk in out.as_slice().chunks_exact(3) {
-        let tile = Tile::from(chunk[2]);
-        board.add(chunk[0] as i32, chunk[1] as i32, tile);
-    }
+    let board = draw_board(intcode)?;
     let blocks = board.inner.values().filter(|t| **t == Tile::Block).count();
     Ok(format!("{}", blocks))
 }
 
 pub fn part2(input: &str) -> Result<String> {
     let mut intcode = parse_intcode(input)?;
-    let (_, out) = run_intcode(intcode.clone(), vec![])?;
-    let mut board = Board::default();
-    for chunk in out.as_slice().chunks_exact(3) {
-        let tile = Tile::from(chunk[2]);
-        board.add(chunk[0] as i32, chunk[1] as i32, tile);
-    }
+    let board = draw_board(intcode.clone())?;
 
     //let img = ImageNormal::create(&board.inner);
     let screen = Screen::new(board);
 
     // insert 2 quarters
     intcode[0] = 2;
-    let mut ic = IntCode::new_from_device(intcode, screen);
+    let mut ic = IntCode::new_from_device(intcode, FramedOutput::new(screen));
     ic.run_till_end()?;
-    let (_, screen) = ic.emit();
+    let screen = ic.into_device();
+
+    Ok(format!("{}", screen.handler().score))
+}
 
-    Ok(format!("{}", screen.score))
+fn draw_board(intcode: Vec<Int>) -> Result<Board> {
+    let mut board = Board::default();
+    let draw = |(x, y, tile): (Int, Int, Tile)| {
+        board.add(x as i32, y as i32, tile);
+        Ok(())
+    };
+    IntCode::new(intcode, NullIO, FramedOutput::new(draw)).run_till_end()?;
+    Ok(board)
 }
 
 mod game {
     //use crate::display::ImageNormal;
-    use crate::intcode::intcode_io::{Input, Output};
+    use crate::intcode::intcode_io::{FrameHandler, Input};
     use anyhow::Result;
     use std::collections::HashMap;
     use std::fmt;
@@ -69,7 +70,6 @@ mod game {
         pub board: Board,
         // image: ImageNormal<Tile>,
         pub score: i64,
-        instruction: Vec<i64>,
     }
 
     impl Screen {
@@ -78,7 +78,6 @@ mod game {
                 board,
                 // image,
                 score: 0,
-                instruction: Vec::new(),
             }
         }
         // fn disp(&mut self) {
@@ -86,20 +85,6 @@ mod game {
         //     println!("Score: {}", self.score);
         //     println!("{}", self.image);
         // }
-        fn update(&mut self, instr: i64) {
-            self.instruction.push(instr);
-            if self.instruction.len() == 3 {
-                let x = self.instruction[0];
-                let y = self.instruction[1];
-                let d = self.instruction[2];
-                if x == -1 && y == 0 {
-                    self.score = d;
-                } else {
-                    self.board.add(x as i32, y as i32, Tile::from(d));
-                }
-                self.instruction.clear();
-            }
-        }
     }
 
     impl Input for Screen {
@@ -115,9 +100,13 @@ mod game {
         }
     }
 
-    impl Output for Screen {
-        fn output(&mut self, out: i64) -> Result<()> {
-            self.update(out);
+    impl FrameHandler<(i64, i64, i64)> for Screen {
+        fn handle(&mut self, (x, y, d): (i64, i64, i64)) -> Result<()> {
+            if x == -1 && y == 0 {
+                self.score = d;
+            } else {
+                self.board.add(x as i32, y as i32, Tile::from(d));
+            }
             Ok(())
         }
     }
@@ -1,49 +1,49 @@
-use crate::challenges::day15::o2repair::Robot;
+use crate::challenges::day15::o2repair::Search;
 use crate::display::ImageNormal;
-use crate::intcode::IntCode;
+use crate::intcode::{IntCode, PagedMemory};
 use crate::util::parse_intcode;
-use anyhow::Result;
+use anyhow::{anyhow as ah, Result};
 
 pub fn part1(input: &str) -> Result<String> {
-    let intcode = parse_intcode(input)?;
-    let mut ic = IntCode::new_from_device(intcode, Robot::new());
-    let _ = ic.run_till_end();
-    let (_, robot) = ic.emit();
+    let droid = IntCode::resumable(parse_intcode(input)?).with_memory::<PagedMemory>();
+    let search = Search::run(droid)?;
 
-    let img = ImageNormal::create(&robot.map.inner);
+    let img = ImageNormal::create(&search.tiles);
     //img.display_grid(true);
     log::info!("{}", img);
-    let o2 = robot.map.o2system().unwrap();
-    let path = robot.map.path((0, 0).into(), o2)?;
-    Ok(format!("{}", path.len() - 1))
+    let (o2, _) = search
+        .o2system
+        .as_ref()
+        .ok_or_else(|| ah!("no oxygen system found"))?;
+    Ok(format!("{}", search.distance[o2]))
 }
 
 pub fn part2(input: &str) -> Result<String> {
-    let intcode = parse_intcode(input)?;
-    let mut ic = IntCode::new_from_device(intcode, Robot::new());
-    let _ = ic.run_till_end();
-    let (_, robot) = ic.emit();
-
-    let o2 = robot.map.o2system().unwrap();
-    let path = robot.map.longest_path(o2)?;
-    Ok(format!("{}", path.len() - 1))
+    let droid = IntCode::resumable(parse_intcode(input)?).with_memory::<PagedMemory>();
+    let (_, droid) = Search::run(droid)?
+        .o2system
+        .ok_or_else(|| ah!("no oxygen system found"))?;
+
+    // search again, from the droid which found the oxygen system
+    let fill = Search::run(droid)?;
+    let minutes = fill.distance.values().max().cloned().unwrap_or(0);
+    Ok(format!("{}", minutes))
 }
 
 mod o2repair {
     use crate::display::Point;
-    use crate::intcode::intcode_io::{Input, Output};
+    use crate::intcode::{Event, IntCode, PagedMemory};
     use anyhow::{anyhow as ah, Result};
-    use std::collections::HashMap;
+    use std::collections::{HashMap, VecDeque};
     use std::fmt;
 
-    type Graph = petgraph::graphmap::GraphMap<Point, (), petgraph::Undirected>;
+    type Droid = IntCode<(), PagedMemory>;
 
     #[derive(Debug, Clone, Copy, PartialEq)]
     pub enum Tile {
         Empty,
         Wall,
         O2System,
-        Unknown,
     }
 
     impl fmt::Display for Tile {
@@ -55,7 +55,6 @@ mod o2repair {
                     Tile::Empty => ".",
                     Tile::Wall => "X",
                     Tile::O2System => "O",
-                    Tile::Unknown => "?",
                 }
             )
         }
@@ -79,10 +78,6 @@ mod o2repair {
         }
     }
 
-    fn adjacent_points(p: Point) -> Vec<Point> {
-        Direction::rose().iter().map(|d| d.moved(p)).collect()
-    }
-
     #[derive(Debug, Clone, Copy)]
     enum Direction {
         North,
@@ -109,15 +104,6 @@ mod o2repair {
                 Direction::West,
             ]
         }
-        fn step(src: Point, dst: Point) -> Option<Direction> {
-            match (dst.x - src.x, dst.y - src.y) {
-                (0, 1) => Some(Direction::North),
-                (0, -1) => Some(Direction::South),
-                (-1, 0) => Some(Direction::West),
-                (1, 0) => Some(Direction::East),
-                _ => None,
-            }
-        }
     }
 
     impl From<Direction> for i64 {
@@ -131,193 +117,65 @@ mod o2repair {
         }
     }
 
-    #[derive(Debug, Clone)]
-    pub struct Deck {
-        pub inner: HashMap<Point, Tile>,
-        graph: Graph,
-    }
-
-    impl Deck {
-        fn new() -> Deck {
-            let inner = HashMap::new();
-            let mut d = Deck {
-                inner,
-                graph: Graph::new(),
-            };
-            d.mark((0, 0).into(), Tile::Empty);
-            d
-        }
-        fn mark_unknown(&mut self, p: Point) -> bool {
-            let t = *self.inner.entry(p).or_insert(Tile::Unknown);
-            if t == Tile::Unknown {
-                self.mark_traverseable(p);
-                true
-            } else {
-                false
-            }
-        }
-        fn mark(&mut self, p: Point, t: Tile) {
-            self.inner.insert(p, t);
-            match t {
-                Tile::Wall => self.mark_unreachable(p),
-                Tile::Empty | Tile::O2System | Tile::Unknown => self.mark_traverseable(p),
-            }
-        }
-        fn mark_unreachable(&mut self, p: Point) {
-            self.graph.remove_node(p);
-        }
-        fn mark_traverseable(&mut self, p: Point) {
-            self.graph.add_node(p);
-            for adj_p in adjacent_points(p) {
-                if self.graph.contains_node(adj_p) {
-                    self.graph.add_edge(p, adj_p, ());
-                }
-            }
-        }
-        fn squares_with<F: Fn(Tile) -> bool>(&self, filter: F) -> Vec<Point> {
-            self.inner
-                .iter()
-                .filter_map(|(p, t)| if filter(*t) { Some(*p) } else { None })
-                .collect()
-        }
-        pub fn path(&self, src: Point, dst: Point) -> Result<Vec<Point>> {
-            petgraph::algo::astar(
-                &self.graph,
-                src,
-                |n| n == dst,
-                |_| 1,
-                |dst| src.step_dist(&dst),
-            )
-            .ok_or_else(|| ah!("could not chart course from {} to {}", src, dst))
-            .map(|(_, path)| path)
-        }
-
-        pub fn longest_path(&self, src: Point) -> Result<Vec<Point>> {
-            let mut bfs = petgraph::visit::Bfs::new(&self.graph, src);
-            let mut terminal = None;
-            while let Some(p) = bfs.next(&self.graph) {
-                terminal = Some(p);
-            }
-
-            terminal
-                .ok_or_else(|| ah!("bfs did not find any nodes"))
-                .and_then(|dst| self.path(src, dst))
-        }
-        pub fn o2system(&self) -> Option<Point> {
-            self.squares_with(|t| t == Tile::O2System)
-                .iter()
-                .cloned()
-                .next()
-        }
-    }
-
+    /// A breadth first search of the maze from wherever the droid starts.
+    ///
+    /// Rather than walking the droid back and forth, every open square keeps
+    /// its own fork of the droid standing on it, and each move is tried on a
+    /// fresh fork of that.
     #[derive(Debug)]
-    pub struct Robot {
-        loc: Point,
-        pub map: Deck,
-        cmd: Direction,
-        dst: Vec<Point>,
-    }
-
-    impl Robot {
-        pub fn new() -> Robot {
-            Robot {
-                loc: Point::new(0, 0),
-                map: Deck::new(),
-                cmd: Direction::North,
-                dst: vec![Point::new(0, 1)],
-            }
-        }
-        fn mark_neighbors(&mut self) {
-            self.dst.clear();
-            let loc = self.loc;
-            let mut target = None;
-            for p in adjacent_points(loc).into_iter().rev() {
-                if self.map.mark_unknown(p) {
-                    target = Some(p);
-                }
-            }
-            if let Some(p) = target {
-                self.dst.push(p)
-            }
-        }
-        fn new_frontier(&self) -> Option<Point> {
-            self.map
-                .squares_with(|t| t == Tile::Unknown)
-                .iter()
-                .map(|p| (*p, self.loc.step_dist(p)))
-                .fold(None, |acc: Option<(Point, i32)>, x| {
-                    if let Some(prev) = acc {
-                        Some(if prev.1 < x.1 { prev } else { x })
-                    } else {
-                        Some(x)
+    pub struct Search {
+        pub tiles: HashMap<Point, Tile>,
+        /// Moves from the start to each open square.
+        pub distance: HashMap<Point, usize>,
+        /// Where the oxygen system is, with a droid standing on it.
+        pub o2system: Option<(Point, Droid)>,
+    }
+
+    impl Search {
+        pub fn run(mut droid: Droid) -> Result<Search> {
+            droid.predecode();
+            let start = Point::new(0, 0);
+            let mut search = Search {
+                tiles: HashMap::new(),
+                distance: HashMap::new(),
+                o2system: None,
+            };
+            search.tiles.insert(start, Tile::Empty);
+            search.distance.insert(start, 0);
+
+            let mut queue = VecDeque::new();
+            queue.push_back((start, droid));
+            while let Some((loc, droid)) = queue.pop_front() {
+                let dist = search.distance[&loc] + 1;
+                for dir in Direction::rose().iter() {
+                    let pos = dir.moved(loc);
+                    if search.tiles.contains_key(&pos) {
+                        continue;
                     }
-                })
-                .map(|acc| acc.0)
-        }
-    }
-
-    impl Input for Robot {
-        fn input(&mut self) -> Result<i64> {
-            let dst = self
-                .dst
-                .pop()
-                .or_else(|| self.new_frontier())
-                .ok_or_else(|| ah!("no destination"))?;
-            if let Some(cmd) = Direction::step(self.loc, dst) {
-                self.cmd = cmd;
-                return Ok(cmd.into());
-            }
-            let mut path = self.map.path(self.loc, dst)?;
-            if path.len() < 3 {
-                return Err(ah!(
-                    "path too short? src: {} dst: {} path: {:?}",
-                    self.loc,
-                    dst,
-                    path
-                ));
-            }
-            path.reverse();
-            path.pop();
-            let dst = path.pop().unwrap();
-            let cmd = Direction::step(self.loc, dst).ok_or_else(|| {
-                ah!(
-                    "path first step was not adjacent? src: {} dst: {}",
-                    self.loc,
-                    dst
-                )
-            })?;
-            self.cmd = cmd;
-            Ok(cmd.into())
-        }
-    }
-
-    impl Output for Robot {
-        fn output(&mut self, out: i64) -> Result<()> {
-            let status = Status::from(out);
-            log::trace!(
-                "Robot {} went {:?}, found: {:?}",
-                self.loc,
-                self.cmd,
-                status
-            );
-            let pos = self.cmd.moved(self.loc);
-            let tile = match status {
-                Status::Wall => Tile::Wall,
-                Status::Move => {
-                    self.loc = pos;
-                    Tile::Empty
-                }
-                Status::O2 => {
-                    self.loc = pos;
-                    Tile::O2System
+                    let mut branch = droid.fork();
+                    branch.provide_input((*dir).into());
+                    let status = match branch.run_until_event()? {
+                        Event::Output(x) => Status::from(x),
+                        event => return Err(ah!("droid at {} stopped: {:?}", loc, event)),
+                    };
+                    log::trace!("Robot {} went {:?}, found: {:?}", loc, dir, status);
+                    let tile = match status {
+                        Status::Wall => Tile::Wall,
+                        Status::Move => Tile::Empty,
+                        Status::O2 => Tile::O2System,
+                    };
+                    search.tiles.insert(pos, tile);
+                    if tile == Tile::Wall {
+                        continue;
+                    }
+                    search.distance.insert(pos, dist);
+                    if tile == Tile::O2System {
+                        search.o2system = Some((pos, branch.fork()));
+                    }
+                    queue.push_back((pos, branch));
                 }
-            };
-            if self.dst.is_empty() {
-                self.map.mark(pos, tile);
-                self.mark_neighbors();
             }
-            Ok(())
+            Ok(search)
         }
     }
 }
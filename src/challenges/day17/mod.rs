@@ -1,4 +1,5 @@
-use crate::{display::Point, intcode::run_intcode, util::parse_intcode};
+use crate::intcode::{intcode_io::AsciiIO, IntCode};
+use crate::{display::Point, util::parse_intcode};
 use anyhow::{anyhow as ah, Result};
 use sequence_extractor::{divide3, SubSeq3};
 use std::{collections::HashMap, fmt};
@@ -86,22 +87,20 @@ impl fmt::Debug for PathInstructions {
     }
 }
 
-fn intcode_to_string(intcode: &[i64]) -> Result<String> {
-    Ok(String::from_utf8(
-        intcode.iter().map(|x| *x as u8).collect::<Vec<_>>(),
-    )?)
+/// Run the camera and return what it shows.
+fn camera(intcode: Vec<i64>) -> Result<String> {
+    let mut ic = IntCode::new_from_device(intcode, AsciiIO::new());
+    ic.run_till_end()?;
+    Ok(ic.into_device().take_output())
 }
 
 pub fn part1(input: &str) -> Result<String> {
-    let intcode = parse_intcode(input)?;
-    let (_, out) = run_intcode(intcode, vec![])?;
-    let (m, _) = Map::from_render(out.as_slice())?;
+    let render = camera(parse_intcode(input)?)?;
+    let (m, _) = Map::from_render(&render)?;
     //log::trace!( "m: {:?}, r: {:#?}", m, r);
     let intersections = m.intersections().collect::<Vec<_>>();
     log::trace!("intersections: {:#?}", intersections);
-
-    let s = intcode_to_string(out.as_slice())?;
-    log::trace!("map:\n{}", s);
+    log::trace!("map:\n{}", render);
 
     Ok(format!(
         "{}",
@@ -111,38 +110,32 @@ pub fn part1(input: &str) -> Result<String> {
 
 pub fn part2(input: &str) -> Result<String> {
     let mut intcode = parse_intcode(input)?;
-    let (_, out) = run_intcode(intcode.clone(), vec![2])?;
-    let program = program_walk(out.as_slice(), 16017)?;
+    let program = program_walk(&camera(intcode.clone())?, 16017)?;
     log::debug!("input:\n{}", program.to_ascii());
 
     // Wake up cmd
     intcode[0] = 2;
-    let mut program_ascii = program
-        .to_ascii()
-        .as_bytes()
-        .iter()
-        .map(|b| *b as i64)
-        .collect::<Vec<_>>();
+    let mut io = AsciiIO::new();
+    io.send(&program.to_ascii());
     // do not output feed
-    program_ascii.extend("n\n".as_bytes().iter().map(|b| *b as i64));
-    let (_, out_score) = run_intcode(intcode, program_ascii)?;
-    let score = out_score[out_score.len() - 1];
+    io.send_line("n");
+    let mut ic = IntCode::new_from_device(intcode, io);
+    ic.run_till_end()?;
+    let score = ic
+        .device()
+        .result()
+        .ok_or_else(|| ah!("robot did not report any dust"))?;
     Ok(format!("{}", score))
 }
 
 // Take a map directly as input
 pub fn part2_map(input: &str) -> Result<String> {
-    let out = input
-        .as_bytes()
-        .iter()
-        .map(|x| *x as i64)
-        .collect::<Vec<_>>();
-    program_walk(out.as_slice(), 0)?;
+    program_walk(input, 0)?;
 
-    Ok(format!("{}", out[0]))
+    Ok(format!("{}", input.as_bytes()[0]))
 }
 
-fn program_walk(map_data: &[i64], cheat: usize) -> Result<RobotProgram> {
+fn program_walk(map_data: &str, cheat: usize) -> Result<RobotProgram> {
     let (m, r) = Map::from_render(map_data)?;
 
     let chunk_map = m.to_chunk_graph()?;
@@ -707,7 +700,7 @@ impl Map {
             }
         })
     }
-    fn from_render(data: &[i64]) -> Result<(Map, Robot)> {
+    fn from_render(data: &str) -> Result<(Map, Robot)> {
         let mut inner = vec![];
         let mut width = None;
         let mut orientation = None;
@@ -715,8 +708,7 @@ impl Map {
 
         let mut x = 0;
         let mut y = 0;
-        for (idx, encoded) in data.iter().enumerate() {
-            let c = *encoded as u8 as char;
+        for (idx, c) in data.chars().enumerate() {
             match c {
                 '.' => inner.push(Tile::Void),
                 '#' => inner.push(Tile::Scaffold),
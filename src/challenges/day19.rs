@@ -1,28 +1,50 @@
-use crate::intcode::run_intcode;
+use crate::intcode::{Event, Int, IntCode};
 use crate::util::parse_intcode;
-use anyhow::Result;
+use anyhow::{anyhow as ah, Result};
 
 const GRID_SIZE: i64 = 100 - 1;
 
+/// Every probe runs a fresh copy of the drone program, so decode it once and
+/// fork from there.
+fn drone(input: &str) -> Result<IntCode<()>> {
+    let mut ic = IntCode::resumable(parse_intcode(input)?);
+    ic.predecode();
+    Ok(ic)
+}
+
+fn probe(drone: &IntCode<()>, x: Int, y: Int) -> Result<Int> {
+    let mut ic = drone.fork();
+    ic.provide_input(x);
+    ic.provide_input(y);
+    match ic.run_until_event()? {
+        Event::Output(out) => Ok(out),
+        e => Err(ah!(
+            "drone stopped without reporting ({}, {}): {:?}",
+            x,
+            y,
+            e
+        )),
+    }
+}
+
 pub fn part1(input: &str) -> Result<String> {
-    let intcode = parse_intcode(input)?;
+    let drone = drone(input)?;
     let grid = 50;
     let mut points = 0;
     for y in 0..grid {
         for x in 0..grid {
-            let (_, out) = run_intcode(intcode.clone(), vec![x, y])?;
-            points += out[0];
+            points += probe(&drone, x, y)?;
         }
     }
     Ok(format!("{}", points))
 }
 
 pub fn part2(input: &str) -> Result<String> {
-    let intcode = parse_intcode(input)?;
+    let drone = drone(input)?;
     let check = move |x, y| {
-        let (_, out) = run_intcode(intcode.clone(), vec![x, y]).unwrap();
-        log::debug!("({}, {}) => {}", x, y, out[0]);
-        out[0] == 1
+        let out = probe(&drone, x, y).unwrap();
+        log::debug!("({}, {}) => {}", x, y, out);
+        out == 1
     };
     let mut x = 0;
     let mut y = GRID_SIZE;
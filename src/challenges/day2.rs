@@ -1,6 +1,9 @@
 use crate::intcode::run_intcode;
+use crate::intcode::symbolic::Symbolic;
 use crate::util::parse_intcode;
-use anyhow::Result;
+use anyhow::{anyhow as ah, Result};
+
+const MAX_STEPS: u64 = 10_000;
 
 pub fn part1(input: &str) -> Result<String> {
     let intcode = parse_intcode(input)?;
@@ -10,8 +13,14 @@ pub fn part1(input: &str) -> Result<String> {
 
 pub fn part2(input: &str) -> Result<String> {
     let intcode = parse_intcode(input)?;
-    let (a1, a2) =
-        crate::challenges::day2::scan_args(&intcode, 19_690_720).expect("no valid inputs");
+    let (a1, a2) = match solve_args(&intcode, 19_690_720) {
+        Ok(args) => args,
+        Err(e) => {
+            log::warn!("unable to solve, searching instead: {:#}", e);
+            scan_args(&intcode, 19_690_720)
+        }
+    }
+    .expect("no valid inputs");
     Ok(format!("{:02}{:02}", a1, a2))
 }
 
@@ -35,6 +44,24 @@ pub fn scan_args(intcode: &[i64], expected: i64) -> Option<(i64, i64)> {
     None
 }
 
+/// Like `scan_args`, but run the program once with the noun and verb left
+/// unknown and solve the expression it leaves in cell 0.
+pub fn solve_args(intcode: &[i64], expected: i64) -> Result<Option<(i64, i64)>> {
+    let mut machine = Symbolic::new(intcode);
+    let noun = machine.symbol("noun");
+    let verb = machine.symbol("verb");
+    machine.set(1, noun.clone().into());
+    machine.set(2, verb.clone().into());
+    machine.run(MAX_STEPS)?;
+    let result = machine.peek(0);
+    log::debug!("output = {}", result);
+    let expr = result
+        .linear()
+        .ok_or_else(|| ah!("output is not linear in the noun and verb"))?;
+    let args = expr.solve(expected, &[(noun, 0..=99), (verb, 0..=99)]);
+    Ok(args.map(|v| (v[0], v[1])))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -49,4 +76,18 @@ mod test {
     fn day2part2() {
         assert_eq!(part2(DAY2_INPUT).unwrap().as_str(), "8444")
     }
+
+    #[test]
+    fn solving_matches_searching() {
+        let intcode = parse_intcode(DAY2_INPUT).unwrap();
+        for expected in &[19_690_720, 3_101_878, 493_708] {
+            assert_eq!(
+                solve_args(&intcode, *expected).unwrap(),
+                scan_args(&intcode, *expected),
+                "{}",
+                expected
+            );
+        }
+        assert_eq!(solve_args(&intcode, 1).unwrap(), None);
+    }
 }
@@ -1,7 +1,7 @@
-use crate::intcode::run_intcode;
+use crate::intcode::intcode_io::AsciiIO;
+use crate::intcode::{ErrorKind, IntCode, IntCodeError};
 use crate::util::parse_intcode;
 use anyhow::{anyhow as ah, Result};
-use std::fmt;
 
 const PROG_PT1: &str = include_str!("part1");
 const PROG_PT2: &str = include_str!("part2");
@@ -17,32 +17,23 @@ pub fn part2(input: &str) -> Result<String> {
 }
 
 pub fn run_program(intcode: &str, program: &str) -> Result<i64> {
-    let intcode = parse_intcode(intcode)?;
-    let program = compile(program);
-
-    let (_, out) = run_intcode(intcode, program)?;
-    let last = *out.last().unwrap();
-    if last > 127 {
-        Ok(last)
-    } else {
-        log::error!("{}", SpringError(out.as_slice()));
-        Err(ah!("intcode program failed"))
-    }
-}
-
-struct SpringError<'a>(&'a [i64]);
-
-impl<'a> fmt::Display for SpringError<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for x in self.0 {
-            write!(f, "{}", char::from(*x as u8))?;
-        }
-        Ok(())
-    }
-}
-
-fn compile(code: &str) -> Vec<i64> {
-    code.chars().map(|c| c as i64).collect()
+    let mut io = AsciiIO::new();
+    io.send(program);
+    let mut ic = IntCode::new_from_device(parse_intcode(intcode)?, io);
+
+    match ic.run_till_end() {
+        Ok(()) => {}
+        Err(IntCodeError {
+            kind: ErrorKind::InputExhausted,
+            ..
+        }) => return Err(ah!("springscript must end with WALK or RUN")),
+        Err(e) => return Err(e.into()),
+    };
+    let io = ic.into_device();
+    io.result().ok_or_else(|| {
+        log::error!("{}", io.text());
+        ah!("intcode program failed")
+    })
 }
 
 #[cfg(test)]
@@ -59,4 +50,10 @@ mod test {
     fn verify_part2() {
         assert_eq!(part2(DAY21_INPUT).unwrap().as_str(), "1137899149")
     }
+
+    #[test]
+    fn unterminated_program() {
+        let err = run_program(DAY21_INPUT, "NOT A J\n").unwrap_err();
+        assert_eq!(err.to_string(), "springscript must end with WALK or RUN");
+    }
 }
@@ -1,286 +1,157 @@
-use self::network::Network;
-use self::nic::{Nat, Nic};
+use self::network::{Network, Packet};
+use crate::intcode::scheduler::{Scheduler, Status};
 use crate::intcode::IntCode;
 use crate::util::parse_intcode;
-use anyhow::Result;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{mpsc, Arc};
+use anyhow::{anyhow as ah, Result};
 
 const NUM_CPU: usize = 50;
-const SLEEP_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1);
 
 pub fn part1(input: &str) -> Result<String> {
-    let (rx, mut shutdown) = run_network(input)?;
-    let first = rx.recv()?;
-    shutdown.shutdown()?;
-    Ok(format!("{:?}", first))
-}
-pub fn part2(input: &str) -> Result<String> {
-    let (rx, mut shutdown) = run_network(input)?;
-    let mut last = None;
-    let double = loop {
-        let next = rx.recv()?;
-        if let Some(prev) = last {
-            if next == prev {
-                break next;
-            }
+    let mut sched = boot(input)?;
+    loop {
+        if let Status::Halted = sched.run_round()? {
+            return Err(ah!("network halted before sending to the NAT"));
         }
-        last = Some(next);
-    };
-    shutdown.shutdown()?;
-    Ok(format!("{:?}", double))
+        if let Some(packet) = sched.router().nat {
+            return Ok(format!("{:?}", packet.y));
+        }
+    }
 }
 
-fn run_network(input: &str) -> Result<(mpsc::Receiver<i64>, Shutdown)> {
-    let intcode = parse_intcode(input)?;
-    let net = Network::new();
-    let (tx, rx) = mpsc::channel();
-    let (nat_tx, nat_rx) = mpsc::channel();
-    let shutdown = Arc::new(AtomicBool::new(false));
-    for cpu in 0..NUM_CPU {
-        let nic = Nic::new(cpu as i64, &net);
-        let mut ic = IntCode::new_from_device(intcode.clone(), nic);
-        let my_tx = tx.clone();
-        let my_shutdown = Arc::clone(&shutdown);
-        std::thread::spawn(move || {
-            let mut last_check = std::time::Instant::now();
-            let r = loop {
-                if last_check.elapsed() > SLEEP_INTERVAL {
-                    if my_shutdown.load(Ordering::Relaxed) {
-                        break Ok(());
-                    }
-                    last_check = std::time::Instant::now();
-                }
-                if ic.halt {
-                    log::warn!("CPU {} HALT", cpu);
-                    break Ok(());
-                }
-                match ic.run_one() {
-                    Ok(_) => {}
-                    Err(e) => break Err(e),
-                }
-            };
-            match r {
-                Ok(_) => {}
-                Err(e) => log::error!("cpu {} error: {}", cpu, e),
-            }
-            my_tx
-                .send(cpu)
-                .expect(&format!("cpu {} failed to send complete", cpu));
-        });
+pub fn part2(input: &str) -> Result<String> {
+    let mut sched = boot(input)?;
+    let mut last: Option<Packet> = None;
+    loop {
+        if let Status::Halted = sched.run_until_idle()? {
+            return Err(ah!("network halted"));
+        }
+        let packet = sched
+            .router_mut()
+            .nat
+            .take()
+            .ok_or_else(|| ah!("network is idle with nothing sent to the NAT"))?;
+        if last.map(|p| p.y) == Some(packet.y) {
+            return Ok(format!("{:?}", packet.y));
+        }
+        sched.router_mut().deliver(0, packet)?;
+        last = Some(packet);
     }
-    let nat_shutdown = Arc::clone(&shutdown);
-    let mut nat = Nat::new(&net);
-    let my_tx = tx.clone();
-    std::thread::spawn(move || {
-        loop {
-            if nat_shutdown.load(Ordering::Relaxed) {
-                break;
-            }
-            if let Some(p) = nat.check_update() {
-                nat_tx.send(p.y).expect("failed to send nat result");
-            }
-            std::thread::sleep(SLEEP_INTERVAL);
-        }
-        my_tx
-            .send(nat.id as usize)
-            .expect("could not send nat complete");
-    });
-    Ok((
-        nat_rx,
-        Shutdown {
-            count: NUM_CPU + 1,
-            cpu_rx: rx,
-            shutdown,
-        },
-    ))
 }
 
-struct Shutdown {
-    count: usize,
-    cpu_rx: mpsc::Receiver<usize>,
-    shutdown: Arc<AtomicBool>,
-}
-impl Shutdown {
-    fn shutdown(&mut self) -> Result<()> {
-        self.shutdown.store(true, Ordering::Relaxed);
-        for _ in 0..self.count {
-            self.cpu_rx.recv()?;
-        }
-        Ok(())
+/// Every NIC, booted with its address and polling -1 while its queue is
+/// empty.
+fn boot(input: &str) -> Result<Scheduler<Network>> {
+    let intcode = parse_intcode(input)?;
+    let mut sched = Scheduler::new(Network::new(NUM_CPU)).poll_with(-1);
+    for _ in 0..NUM_CPU {
+        let id = sched.add(IntCode::resumable(intcode.clone()));
+        sched.router_mut().boot(id);
     }
+    Ok(sched)
 }
 
-mod nic {
-    use super::network::{Network, Packet};
-    use super::SLEEP_INTERVAL;
-    use crate::intcode::intcode_io::{Input, Output};
+mod network {
+    use crate::intcode::intcode_io::{
+        FrameHandler, FrameSource, FramedInput, FramedOutput, Input, InputExhausted, Output,
+        ToWords,
+    };
+    use crate::intcode::scheduler::Router;
+    use crate::intcode::Int;
+    use anyhow::{anyhow as ah, Result};
+    use std::collections::VecDeque;
+    use std::convert::TryFrom;
 
-    pub(crate) struct Nat {
-        pub id: i64,
-        net: Network,
-        last: Option<Packet>,
-    }
+    const NAT: Int = 255;
 
-    impl Nat {
-        pub(crate) fn new(network: &Network) -> Nat {
-            Nat {
-                id: 255,
-                net: network.clone(),
-                last: None,
-            }
-        }
-        fn update(&mut self) {
-            if let Some(last) = self.net.last(self.id) {
-                self.last = Some(last)
-            }
-        }
-        pub(crate) fn check_update(&mut self) -> Option<Packet> {
-            if self.net.idle(self.id) {
-                self.update();
-                if let Some(p) = self.last.take() {
-                    self.net.send(0, p);
-                    return Some(p);
-                }
-            }
-            None
-        }
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub(crate) struct Packet {
+        pub(crate) x: i64,
+        pub(crate) y: i64,
     }
 
-    #[derive(Clone, Copy)]
-    enum SendState {
-        Ready,
-        Addr(i64),
-        Buffered(i64, i64),
+    /// What a NIC reads: its address once at boot, then packets.
+    enum Message {
+        Address(Int),
+        Packet(Packet),
     }
 
-    impl SendState {
-        fn push(&mut self, input: i64) -> Option<(i64, Packet)> {
-            match *self {
-                SendState::Ready => {
-                    *self = SendState::Addr(input);
-                    None
-                }
-                SendState::Addr(addr) => {
-                    *self = SendState::Buffered(addr, input);
-                    None
-                }
-                SendState::Buffered(addr, x) => {
-                    *self = SendState::Ready;
-                    Some((addr, Packet { x, y: input }))
-                }
+    impl ToWords for Message {
+        fn to_words(self, out: &mut VecDeque<Int>) {
+            match self {
+                Message::Address(id) => id.to_words(out),
+                Message::Packet(Packet { x, y }) => (x, y).to_words(out),
             }
         }
     }
 
-    pub(crate) struct Nic {
-        id: i64,
-        init: bool,
-        read_buffer: Option<i64>,
-        send_state: SendState,
-        network: Network,
-    }
-    impl Nic {
-        pub(crate) fn new(id: i64, network: &Network) -> Nic {
-            Nic {
-                id,
-                init: false,
-                read_buffer: None,
-                send_state: SendState::Ready,
-                network: network.clone(),
-            }
-        }
-    }
+    /// Holds the packet a NIC has just sent until the network routes it.
+    #[derive(Default)]
+    struct Outbox(Option<(Int, Packet)>);
 
-    impl Input for Nic {
-        fn input(&mut self) -> anyhow::Result<crate::intcode::Int> {
-            Ok(if !self.init {
-                self.init = true;
-                self.id
-            } else if let Some(y) = self.read_buffer.take() {
-                y
-            } else if let Some(Packet { x, y }) = self.network.recv(self.id) {
-                self.read_buffer = Some(y);
-                x
-            } else {
-                std::thread::sleep(SLEEP_INTERVAL);
-                -1
-            })
-        }
-    }
-    impl Output for Nic {
-        fn output(&mut self, out: crate::intcode::Int) -> anyhow::Result<()> {
-            if let Some((addr, packet)) = self.send_state.push(out) {
-                self.network.send(addr, packet);
-            }
+    impl FrameHandler<(Int, Int, Int)> for Outbox {
+        fn handle(&mut self, (addr, x, y): (Int, Int, Int)) -> Result<()> {
+            self.0 = Some((addr, Packet { x, y }));
             Ok(())
         }
     }
-}
-
-mod network {
-    use std::collections::{HashMap, VecDeque};
-    use std::sync::{Arc, Mutex};
 
-    #[derive(Debug, Clone, Copy)]
-    pub(crate) struct Packet {
-        pub(crate) x: i64,
-        pub(crate) y: i64,
-    }
-
-    #[derive(Default)]
-    pub(crate) struct NetStream {
-        inner: VecDeque<Packet>,
-        idle: bool,
-    }
-
-    impl NetStream {
-        pub(crate) fn insert(&mut self, data: Packet) {
-            self.inner.push_back(data);
-            self.idle = false;
-        }
-        pub(crate) fn read(&mut self) -> Option<Packet> {
-            let p = self.inner.pop_front();
-            if p.is_none() {
-                self.idle = true;
-            }
-            p
-        }
-        pub(crate) fn last(&mut self) -> Option<Packet> {
-            let last = self.inner.pop_back();
-            self.inner.clear();
-            self.idle = true;
-            last
+    /// Packets are queued on a NIC as they are routed, so there's never
+    /// anything more to fetch.
+    impl FrameSource<Message> for Outbox {
+        fn next_frame(&mut self) -> Result<Message> {
+            Err(InputExhausted.into())
         }
     }
 
-    #[derive(Default, Clone)]
+    type Nic = FramedInput<Message, FramedOutput<(Int, Int, Int), Outbox>>;
+
+    /// A framed device per NIC, plus the NAT's single slot which keeps only
+    /// the latest packet sent to it.
     pub(crate) struct Network {
-        inner: Arc<Mutex<HashMap<i64, NetStream>>>,
+        nics: Vec<Nic>,
+        pub(crate) nat: Option<Packet>,
     }
+
     impl Network {
-        pub(crate) fn new() -> Self {
+        pub(crate) fn new(size: usize) -> Network {
             Network {
-                inner: Arc::new(Mutex::new(HashMap::new())),
+                nics: (0..size)
+                    .map(|_| FramedInput::new(FramedOutput::new(Outbox::default())))
+                    .collect(),
+                nat: None,
             }
         }
-        pub(crate) fn send(&self, addr: i64, packet: Packet) {
-            let mut map = self.inner.lock().expect("bad mutex");
-            let stream = map.entry(addr).or_insert_with(|| NetStream::default());
-            stream.insert(packet)
+
+        pub(crate) fn boot(&mut self, id: usize) {
+            self.nics[id].send(Message::Address(id as Int));
         }
-        pub(crate) fn recv(&self, addr: i64) -> Option<Packet> {
-            // let addr = addr + 1;
-            let mut map = self.inner.lock().expect("bad mutex");
-            map.get_mut(&addr).and_then(|stream| stream.read())
+
+        pub(crate) fn deliver(&mut self, addr: Int, packet: Packet) -> Result<()> {
+            if addr == NAT {
+                self.nat = Some(packet);
+                return Ok(());
+            }
+            let nic = usize::try_from(addr)
+                .ok()
+                .and_then(|addr| self.nics.get_mut(addr))
+                .ok_or_else(|| ah!("packet for unknown address {}", addr))?;
+            nic.send(Message::Packet(packet));
+            Ok(())
         }
-        pub(crate) fn last(&self, addr: i64) -> Option<Packet> {
-            let mut map = self.inner.lock().expect("bad mutex");
-            map.get_mut(&addr).and_then(|stream| stream.last())
+    }
+
+    impl Router for Network {
+        fn recv(&mut self, id: usize) -> Option<Int> {
+            self.nics[id].input().ok()
         }
-        pub(crate) fn idle(&self, addr: i64) -> bool {
-            let map = self.inner.lock().expect("bad mutex");
-            map.iter().all(|(id, s)| *id == addr || s.idle)
+
+        fn send(&mut self, id: usize, value: Int) -> Result<()> {
+            let nic = &mut self.nics[id];
+            nic.output(value)?;
+            match nic.source_mut().handler_mut().0.take() {
+                Some((addr, packet)) => self.deliver(addr, packet),
+                None => Ok(()),
+            }
         }
     }
 }
@@ -31,106 +31,36 @@ pub fn part2(input: &str) -> Result<String> {
 }
 
 mod amplifier {
-    use crate::intcode::intcode_io::{
-        create_stream_io, create_stream_tap, Input, MultiIO, Output, VecIO,
-    };
+    use crate::intcode::scheduler::{Ring, Scheduler, Status};
     use crate::intcode::{Int, IntCode};
-    use anyhow::Result;
-
-    pub struct Amplifier {
-        intcode: Vec<Int>,
-        phase: i64,
-        input: Box<dyn Input + Send>,
-        output: Box<dyn Output + Send>,
-    }
-
-    impl Amplifier {
-        pub fn new<I: Input + 'static + Send, O: Output + 'static + Send>(
-            intcode: Vec<Int>,
-            phase: Int,
-            input: I,
-            output: O,
-        ) -> Amplifier {
-            let input = Box::new(input);
-            let output = Box::new(output);
-            Amplifier {
-                intcode,
-                phase,
-                input,
-                output,
-            }
-        }
-
-        pub fn run(self) -> Result<()> {
-            let fuse = MultiIO::new(VecIO::input(vec![self.phase]), self.input);
-            let mut ic = IntCode::new(self.intcode, fuse, self.output);
-            ic.run_till_end()?;
-            Ok(())
-        }
-    }
+    use anyhow::{anyhow as ah, Result};
 
     pub struct AmplifierCircut {
-        amps: Vec<Amplifier>,
-        out: std::sync::mpsc::Receiver<Int>,
+        scheduler: Scheduler<Ring>,
     }
 
     impl AmplifierCircut {
         pub fn new(intcode: &[i64], input: Int, phases: &[i64]) -> AmplifierCircut {
-            let (sys_in, sys_out, out) = create_sys_io(input);
-            let (second_in, first_out) = create_stream_io();
-            let mut input = Some(second_in);
-
-            let mut amps = Vec::with_capacity(phases.len());
-
-            amps.push(Amplifier::new(
-                intcode.to_vec(),
-                phases[0],
-                sys_in,
-                first_out,
-            ));
-
-            for phase in &phases[1..phases.len() - 1] {
-                let (next_in, this_out) = create_stream_io();
-                let this_in = input.replace(next_in).unwrap();
-                amps.push(Amplifier::new(intcode.to_vec(), *phase, this_in, this_out));
+            let mut ring = Ring::new(phases.len());
+            for (idx, phase) in phases.iter().enumerate() {
+                ring.push(idx, *phase);
             }
-
-            amps.push(Amplifier::new(
-                intcode.to_vec(),
-                phases[phases.len() - 1],
-                input.take().unwrap(),
-                sys_out,
-            ));
-
-            AmplifierCircut { amps, out }
-        }
-        pub fn run(self) -> Result<Vec<Int>> {
-            let mut threads = Vec::new();
-            for (idx, amp) in self.amps.into_iter().enumerate() {
-                let amp: Amplifier = amp;
-                let join = std::thread::spawn(move || {
-                    log::debug!("Amplifier {} start", idx);
-                    if let Err(e) = amp.run() {
-                        log::error!("Amplifier {} errored: {}", idx, e)
-                    } else {
-                        log::debug!("Amplifier {} finished", idx);
-                    }
-                });
-                threads.push(join);
+            ring.push(0, input);
+            let mut scheduler = Scheduler::new(ring);
+            for _ in phases {
+                scheduler.add(IntCode::resumable(intcode.to_vec()));
             }
-            for j in threads {
-                j.join().unwrap();
-            }
-            Ok(self.out.into_iter().collect())
+            AmplifierCircut { scheduler }
         }
-    }
 
-    fn create_sys_io(init: Int) -> (impl Input, impl Output, std::sync::mpsc::Receiver<Int>) {
-        let (feedback_input, feedback_output) = create_stream_io();
-        let (system_output, system_tap) = create_stream_tap();
-        let sys_in = MultiIO::new(VecIO::input(vec![init]), feedback_input);
-        let sys_out = MultiIO::new(feedback_output, system_output);
-        (sys_in, sys_out, system_tap)
+        /// Run until every amplifier halts, returning all the signals the
+        /// last one sent.
+        pub fn run(mut self) -> Result<Vec<Int>> {
+            match self.scheduler.run_until_idle()? {
+                Status::Halted => Ok(self.scheduler.router().last_outputs().to_vec()),
+                _ => Err(ah!("amplifiers stopped without halting")),
+            }
+        }
     }
 
     #[cfg(test)]
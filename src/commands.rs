@@ -0,0 +1,270 @@
+use crate::intcode::aot;
+use crate::intcode::asm::{assemble, assemble_with_symbols};
+use crate::intcode::cfg::Cfg;
+use crate::intcode::debugger::Debugger;
+use crate::intcode::disasm::{disassemble, Listing};
+use crate::intcode::fuzz;
+use crate::intcode::image::{load_program, Image};
+use crate::intcode::intcode_io::{Output, VecIO};
+use crate::intcode::patch::Patches;
+use crate::intcode::transcript::{Entry, RecordingIO};
+use crate::intcode::{Event, Framebuffer, Int, IntCode, Random, Snapshot};
+use crate::util::{parse_intcode, parse_str, read_to_string};
+use anyhow::{anyhow as ah, Result};
+use clap::ArgMatches;
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+
+struct EchoOutput;
+
+impl Output for EchoOutput {
+    fn output(&mut self, out: Int) -> Result<()> {
+        println!("output: {}", out);
+        Ok(())
+    }
+}
+
+pub(crate) fn disasm(args: &ArgMatches) -> Result<()> {
+    let intcode = load_program(args.value_of("file").unwrap())?;
+    let lines = disassemble(&intcode);
+    print!("{}", Listing(&lines));
+    Ok(())
+}
+
+pub(crate) fn asm(args: &ArgMatches) -> Result<()> {
+    let intcode = assemble(&read_to_string(args.value_of("file").unwrap())?)?;
+    let words = intcode.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    println!("{}", words.join(","));
+    Ok(())
+}
+
+pub(crate) fn cfg(args: &ArgMatches) -> Result<()> {
+    let intcode = load_program(args.value_of("file").unwrap())?;
+    let cfg = Cfg::build(&intcode);
+    let blocks = cfg.blocks();
+    let dynamic = blocks.iter().filter(|b| b.dynamic_jump()).count();
+    log::info!("{} blocks, {} dynamic jumps", blocks.len(), dynamic);
+    println!("{}", cfg.dot());
+    Ok(())
+}
+
+pub(crate) fn transpile(args: &ArgMatches) -> Result<()> {
+    let file = args.value_of("file").unwrap();
+    let intcode = load_program(file)?;
+    print!("{}", aot::transpile(&intcode, file));
+    Ok(())
+}
+
+pub(crate) fn debug(args: &ArgMatches) -> Result<()> {
+    let intcode = load_program(args.value_of("file").unwrap())?;
+    let input = parse_intcode(args.value_of("input").unwrap_or(""))?;
+    let mut dbg = Debugger::new(IntCode::new(intcode, VecIO::input(input), EchoOutput));
+    let stdin = std::io::stdin();
+    dbg.repl(stdin.lock(), std::io::stdout())
+}
+
+/// Convert a program between the text and binary formats. Assembly source
+/// is accepted too, and its labels kept as the image's symbol table.
+pub(crate) fn convert(args: &ArgMatches) -> Result<()> {
+    let (input, output) = (
+        args.value_of("input").unwrap(),
+        args.value_of("output").unwrap(),
+    );
+    let image = if args.is_present("asm") {
+        let (words, symbols) = assemble_with_symbols(&read_to_string(input)?)?;
+        Image {
+            words,
+            symbols,
+            patches: Vec::new(),
+        }
+    } else {
+        Image::load(input)?
+    };
+    let binary = match args.value_of("to") {
+        Some("binary") => true,
+        Some("text") => false,
+        Some(other) => return Err(ah!("unknown format '{}'", other)),
+        None => !Image::is_image(&std::fs::read(input)?),
+    };
+    if binary {
+        image.save(output)?;
+    } else {
+        if !image.symbols.is_empty() {
+            log::warn!(
+                "the text format has no symbol table, dropping {} symbols",
+                image.symbols.len()
+            );
+        }
+        std::fs::write(output, image.to_text())?;
+    }
+    log::info!(
+        "wrote {} words to {} as {}",
+        image.words.len(),
+        output,
+        if binary { "binary" } else { "text" }
+    );
+    Ok(())
+}
+
+fn clock_seed() -> Result<u64> {
+    Ok(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_nanos() as u64)
+}
+
+pub(crate) fn fuzz(args: &ArgMatches) -> Result<()> {
+    let seed = match args.value_of("seed") {
+        Some(seed) => parse_str(seed)?,
+        None => clock_seed()?,
+    };
+    let cases = parse_str(args.value_of("cases").unwrap())?;
+    let budget = parse_str(args.value_of("budget").unwrap())?;
+    log::info!("fuzzing {} cases with seed {}", cases, seed);
+    let report = fuzz::fuzz(seed, cases, budget);
+    match report.divergence {
+        Some(d) => {
+            print!("{}", d);
+            Err(ah!(
+                "machines diverged on case {} of seed {}",
+                report.cases,
+                seed
+            ))
+        }
+        None => {
+            println!(
+                "{} cases agree ({} skipped at the memory limit)",
+                report.cases, report.skipped
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Run a program interactively, reading input from stdin whenever it runs
+/// out. Lines starting with `!` control the session instead: `!save <file>`
+/// writes a snapshot which `--resume` picks up later.
+///
+/// Patches given with `--patch` are applied at load time, and noted at the
+/// top of the `--record` transcript so the run can be repeated.
+pub(crate) fn run(args: &ArgMatches) -> Result<()> {
+    let mut applied = Vec::new();
+    let mut ic: IntCode<()> = match args.value_of("resume") {
+        Some(path) => IntCode::restore(&Snapshot::load(path)?, ())?,
+        None => {
+            let file = args
+                .value_of("file")
+                .ok_or_else(|| ah!("a program file or --resume is required"))?;
+            let mut image = Image::load(file)?;
+            for path in args.values_of("patch").into_iter().flatten() {
+                let patches = Patches::load(path)?;
+                patches.apply(&mut image)?;
+                for patch in &patches.patches {
+                    log::info!("patch from {}: {}", path, patch);
+                    applied.push(format!("patch {}: {}", path, patch));
+                }
+            }
+            IntCode::resumable(image.program())
+        }
+    };
+    let framebuffer = match args.value_of("framebuffer") {
+        Some(spec) => {
+            let (base, size) = device_at(spec)?;
+            let mut dims = size.splitn(2, 'x');
+            let (width, height) = match (dims.next(), dims.next()) {
+                (Some(w), Some(h)) => (parse_str(w)?, parse_str(h)?),
+                _ => return Err(ah!("framebuffer size '{}' is not <width>x<height>", size)),
+            };
+            let fb = Framebuffer::new(width, height);
+            Some(ic.map_device(fb.range(base), fb)?)
+        }
+        None => None,
+    };
+    if let Some(spec) = args.value_of("random") {
+        let (addr, bound) = device_at(spec)?;
+        let random = Random::new(clock_seed()?, parse_str(bound)?);
+        ic.map_device(addr..addr + 1, random)?;
+    }
+    let mut recorder = match args.value_of("record") {
+        Some(path) => Some(RecordingIO::to_file((), path)?),
+        None => None,
+    };
+    if let Some(r) = &mut recorder {
+        for note in &applied {
+            r.note(note)?;
+        }
+    }
+    // fed to the machine one value at a time, so the transcript shows
+    // exactly when each was consumed
+    let mut queued = parse_intcode(args.value_of("input").unwrap_or(""))?
+        .into_iter()
+        .collect::<VecDeque<_>>();
+    let ascii = args.is_present("ascii");
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    loop {
+        let event = ic.run_until_event()?;
+        if let (Event::Output(x), Some(r)) = (event, &mut recorder) {
+            r.record(Entry::Output(x))?;
+        }
+        match event {
+            Event::Output(x) if ascii && (0..128).contains(&x) => {
+                write!(out, "{}", x as u8 as char)?
+            }
+            Event::Output(x) => writeln!(out, "{}", x)?,
+            Event::Halted => break,
+            Event::NeedsInput => {
+                if let Some(x) = queued.pop_front() {
+                    if let Some(r) = &mut recorder {
+                        r.record(Entry::Input(x))?;
+                    }
+                    ic.provide_input(x);
+                    continue;
+                }
+                out.flush()?;
+                let line = match lines.next() {
+                    Some(line) => line?,
+                    None => return Err(ah!("program needs input but stdin is closed")),
+                };
+                if let Some(cmd) = line.strip_prefix('!') {
+                    let mut words = cmd.split_whitespace();
+                    match (words.next(), words.next()) {
+                        (Some("save"), Some(path)) => {
+                            ic.snapshot().save(path)?;
+                            writeln!(out, "saved {}", path)?;
+                        }
+                        (Some("quit"), None) => return Ok(()),
+                        _ => writeln!(out, "commands: !save <file>, !quit")?,
+                    }
+                } else if ascii {
+                    queued.extend(line.bytes().map(|b| b as Int));
+                    queued.push_back(b'\n' as Int);
+                } else {
+                    queued.extend(parse_intcode(&line)?);
+                }
+            }
+        }
+    }
+    if let Some(fb) = framebuffer {
+        for row in fb.lock().unwrap().rows() {
+            let row = row
+                .iter()
+                .map(|&p| if p == 0 { ' ' } else { '#' })
+                .collect::<String>();
+            writeln!(out, "{}", row)?;
+        }
+    }
+    Ok(())
+}
+
+/// Split a device option into its address and the rest, from
+/// `<address>:<rest>`.
+fn device_at(spec: &str) -> Result<(usize, &str)> {
+    let mut parts = spec.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(addr), Some(rest)) => Ok((parse_str(addr)?, rest)),
+        _ => Err(ah!("device '{}' is not <address>:...", spec)),
+    }
+}
@@ -0,0 +1,688 @@
+//! `input/day19`, compiled by `transpile`.
+//! Regenerate this file rather than editing it.
+use crate::intcode::intcode_io::{Input, Output};
+use crate::intcode::{IntCode, Result};
+
+pub fn run<IO: Input + Output>(ic: &mut IntCode<IO>) -> Result<()> {
+    if ic.halt {
+        return Ok(());
+    }
+    if ic.watched() {
+        return ic.resume_at(ic.pc);
+    }
+    let mut pc = ic.pc;
+    loop {
+        pc = match pc {
+            0 => {
+                // 0: arb #424
+                ic.set_base(0, 424)?;
+                // 2: in rb+1
+                let v = ic.read_input(2)?;
+                let a = ic.rel_addr(2, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(4);
+                }
+                // 4: mul #11, #1, rb+0
+                let v = ic.mul(4, 11, 1)?;
+                let a = ic.rel_addr(4, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(8);
+                }
+                // 8: jt #1, #282
+                282
+            }
+            11 => {
+                // 11: add #0, #18, rb+0
+                let v = ic.add(11, 0, 18)?;
+                let a = ic.rel_addr(11, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(15);
+                }
+                // 15: jt #1, #259
+                259
+            }
+            18 => {
+                // 18: mul #1, rb+1, [221]
+                ic.inner[221] = ic.mul(18, 1, ic.inner[ic.rel_addr(18, 1)?])?;
+                // 22: in rb+1
+                let v = ic.read_input(22)?;
+                let a = ic.rel_addr(22, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(24);
+                }
+                // 24: mul #1, #31, rb+0
+                let v = ic.mul(24, 1, 31)?;
+                let a = ic.rel_addr(24, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(28);
+                }
+                // 28: jt #1, #282
+                282
+            }
+            31 => {
+                // 31: mul #1, #38, rb+0
+                let v = ic.mul(31, 1, 38)?;
+                let a = ic.rel_addr(31, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(35);
+                }
+                // 35: jt #1, #259
+                259
+            }
+            38 => {
+                // 38: mul [23], #1, rb+2
+                let v = ic.mul(38, ic.inner[23], 1)?;
+                let a = ic.rel_addr(38, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(42);
+                }
+                // 42: add rb+1, #0, rb+3
+                let v = ic.add(42, ic.inner[ic.rel_addr(42, 1)?], 0)?;
+                let a = ic.rel_addr(42, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(46);
+                }
+                // 46: add #0, #1, rb+1
+                let v = ic.add(46, 0, 1)?;
+                let a = ic.rel_addr(46, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(50);
+                }
+                // 50: mul #57, #1, rb+0
+                let v = ic.mul(50, 57, 1)?;
+                let a = ic.rel_addr(50, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(54);
+                }
+                // 54: jt #1, #303
+                303
+            }
+            57 => {
+                // 57: mul #1, rb+1, [222]
+                ic.inner[222] = ic.mul(57, 1, ic.inner[ic.rel_addr(57, 1)?])?;
+                // 61: mul [221], #1, rb+3
+                let v = ic.mul(61, ic.inner[221], 1)?;
+                let a = ic.rel_addr(61, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(65);
+                }
+                // 65: add [221], #0, rb+2
+                let v = ic.add(65, ic.inner[221], 0)?;
+                let a = ic.rel_addr(65, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(69);
+                }
+                // 69: mul #1, #259, rb+1
+                let v = ic.mul(69, 1, 259)?;
+                let a = ic.rel_addr(69, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(73);
+                }
+                // 73: mul #80, #1, rb+0
+                let v = ic.mul(73, 80, 1)?;
+                let a = ic.rel_addr(73, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(77);
+                }
+                // 77: jt #1, #225
+                225
+            }
+            80 => {
+                // 80: mul #59, #1, rb+2
+                let v = ic.mul(80, 59, 1)?;
+                let a = ic.rel_addr(80, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(84);
+                }
+                // 84: mul #1, #91, rb+0
+                let v = ic.mul(84, 1, 91)?;
+                let a = ic.rel_addr(84, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(88);
+                }
+                // 88: jt #1, #303
+                303
+            }
+            91 => {
+                // 91: mul rb+1, #1, [223]
+                ic.inner[223] = ic.mul(91, ic.inner[ic.rel_addr(91, 1)?], 1)?;
+                // 95: add [222], #0, rb+4
+                let v = ic.add(95, ic.inner[222], 0)?;
+                let a = ic.rel_addr(95, 4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(99);
+                }
+                // 99: mul #259, #1, rb+3
+                let v = ic.mul(99, 259, 1)?;
+                let a = ic.rel_addr(99, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(103);
+                }
+                // 103: mul #1, #225, rb+2
+                let v = ic.mul(103, 1, 225)?;
+                let a = ic.rel_addr(103, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(107);
+                }
+                // 107: add #225, #0, rb+1
+                let v = ic.add(107, 225, 0)?;
+                let a = ic.rel_addr(107, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(111);
+                }
+                // 111: add #118, #0, rb+0
+                let v = ic.add(111, 118, 0)?;
+                let a = ic.rel_addr(111, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(115);
+                }
+                // 115: jt #1, #225
+                225
+            }
+            118 => {
+                // 118: mul [222], #1, rb+3
+                let v = ic.mul(118, ic.inner[222], 1)?;
+                let a = ic.rel_addr(118, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(122);
+                }
+                // 122: mul #1, #112, rb+2
+                let v = ic.mul(122, 1, 112)?;
+                let a = ic.rel_addr(122, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(126);
+                }
+                // 126: add #0, #133, rb+0
+                let v = ic.add(126, 0, 133)?;
+                let a = ic.rel_addr(126, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(130);
+                }
+                // 130: jt #1, #303
+                ic.checked_addr(130, ic.inner[132])?
+            }
+            133 => {
+                // 133: mul rb+1, #-1, rb+1
+                let v = ic.mul(133, ic.inner[ic.rel_addr(133, 1)?], -1)?;
+                let a = ic.rel_addr(133, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(137);
+                }
+                // 137: add [223], rb+1, rb+1
+                let v = ic.add(137, ic.inner[223], ic.inner[ic.rel_addr(137, 1)?])?;
+                let a = ic.rel_addr(137, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(141);
+                }
+                // 141: add #148, #0, rb+0
+                let v = ic.add(141, 148, 0)?;
+                let a = ic.rel_addr(141, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(145);
+                }
+                // 145: jt #1, #259
+                259
+            }
+            148 => {
+                // 148: add rb+1, #0, [223]
+                ic.inner[223] = ic.add(148, ic.inner[ic.rel_addr(148, 1)?], 0)?;
+                // 152: mul #1, [221], rb+4
+                let v = ic.mul(152, 1, ic.inner[221])?;
+                let a = ic.rel_addr(152, 4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(156);
+                }
+                // 156: mul [222], #1, rb+3
+                let v = ic.mul(156, ic.inner[222], 1)?;
+                let a = ic.rel_addr(156, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(160);
+                }
+                // 160: mul #1, #18, rb+2
+                let v = ic.mul(160, 1, 18)?;
+                let a = ic.rel_addr(160, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(164);
+                }
+                // 164: add [132], #-2, [224]
+                ic.inner[224] = ic.add(164, ic.inner[132], -2)?;
+                // 168: mul [224], #2, [224]
+                ic.inner[224] = ic.mul(168, ic.inner[224], 2)?;
+                // 172: add [224], #3, [224]
+                ic.inner[224] = ic.add(172, ic.inner[224], 3)?;
+                // 176: mul [132], #-1, [132]
+                ic.inner[132] = ic.mul(176, ic.inner[132], -1)?;
+                // 180: add [224], [132], [224]
+                ic.inner[224] = ic.add(180, ic.inner[224], ic.inner[132])?;
+                // 184: add [224], #1, rb+1
+                let v = ic.add(184, ic.inner[224], 1)?;
+                let a = ic.rel_addr(184, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(188);
+                }
+                // 188: add #0, #195, rb+0
+                let v = ic.add(188, 0, 195)?;
+                let a = ic.rel_addr(188, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(192);
+                }
+                // 192: jf #0, [108]
+                ic.checked_addr(192, ic.inner[108])?
+            }
+            195 => {
+                // 195: lt rb+1, [223], rb+2
+                let v = if ic.inner[ic.rel_addr(195, 1)?] < ic.inner[223] { 1 } else { 0 };
+                let a = ic.rel_addr(195, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(199);
+                }
+                // 199: add [23], #0, rb+1
+                let v = ic.add(199, ic.inner[23], 0)?;
+                let a = ic.rel_addr(199, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(203);
+                }
+                // 203: mul #1, #-1, rb+3
+                let v = ic.mul(203, 1, -1)?;
+                let a = ic.rel_addr(203, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(207);
+                }
+                // 207: mul #1, #214, rb+0
+                let v = ic.mul(207, 1, 214)?;
+                let a = ic.rel_addr(207, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(211);
+                }
+                // 211: jt #1, #303
+                303
+            }
+            214 => {
+                // 214: add #1, rb+1, rb+1
+                let v = ic.add(214, 1, ic.inner[ic.rel_addr(214, 1)?])?;
+                let a = ic.rel_addr(214, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(218);
+                }
+                // 218: out rb+1
+                ic.write_output(218, ic.inner[ic.rel_addr(218, 1)?])?;
+                // 220: hlt
+                return ic.halt_at(220);
+            }
+            225 => {
+                // 225: arb #5
+                ic.set_base(225, 5)?;
+                // 227: add #0, rb-4, [249]
+                ic.inner[249] = ic.add(227, 0, ic.inner[ic.rel_addr(227, -4)?])?;
+                // 231: add #0, rb-3, rb+1
+                let v = ic.add(231, 0, ic.inner[ic.rel_addr(231, -3)?])?;
+                let a = ic.rel_addr(231, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(235);
+                }
+                // 235: mul rb-2, #1, rb+2
+                let v = ic.mul(235, ic.inner[ic.rel_addr(235, -2)?], 1)?;
+                let a = ic.rel_addr(235, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(239);
+                }
+                // 239: add rb-1, #0, rb+3
+                let v = ic.add(239, ic.inner[ic.rel_addr(239, -1)?], 0)?;
+                let a = ic.rel_addr(239, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(243);
+                }
+                // 243: add #250, #0, rb+0
+                let v = ic.add(243, 250, 0)?;
+                let a = ic.rel_addr(243, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(247);
+                }
+                // 247: jt #1, #225
+                ic.checked_addr(247, ic.inner[249])?
+            }
+            250 => {
+                // 250: add #0, rb+1, rb-4
+                let v = ic.add(250, 0, ic.inner[ic.rel_addr(250, 1)?])?;
+                let a = ic.rel_addr(250, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(254);
+                }
+                // 254: arb #-5
+                ic.set_base(254, -5)?;
+                // 256: jt #1, rb+0
+                ic.checked_addr(256, ic.inner[ic.rel_addr(256, 0)?])?
+            }
+            259 => {
+                // 259: arb #3
+                ic.set_base(259, 3)?;
+                // 261: lt #0, rb-2, rb-1
+                let v = if 0 < ic.inner[ic.rel_addr(261, -2)?] { 1 } else { 0 };
+                let a = ic.rel_addr(261, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(265);
+                }
+                // 265: mul rb-1, #2, rb-1
+                let v = ic.mul(265, ic.inner[ic.rel_addr(265, -1)?], 2)?;
+                let a = ic.rel_addr(265, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(269);
+                }
+                // 269: add rb-1, #-1, rb-1
+                let v = ic.add(269, ic.inner[ic.rel_addr(269, -1)?], -1)?;
+                let a = ic.rel_addr(269, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(273);
+                }
+                // 273: mul rb-1, rb-2, rb-2
+                let v = ic.mul(273, ic.inner[ic.rel_addr(273, -1)?], ic.inner[ic.rel_addr(273, -2)?])?;
+                let a = ic.rel_addr(273, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(277);
+                }
+                // 277: arb #-3
+                ic.set_base(277, -3)?;
+                // 279: jf #0, rb+0
+                ic.checked_addr(279, ic.inner[ic.rel_addr(279, 0)?])?
+            }
+            282 => {
+                // 282: arb #3
+                ic.set_base(282, 3)?;
+                // 284: lt rb-2, #0, rb-1
+                let v = if ic.inner[ic.rel_addr(284, -2)?] < 0 { 1 } else { 0 };
+                let a = ic.rel_addr(284, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(288);
+                }
+                // 288: jf rb-1, #294
+                if ic.inner[ic.rel_addr(288, -1)?] == 0 {
+                    294
+                } else {
+                    291
+                }
+            }
+            291 => {
+                // 291: out #0
+                ic.write_output(291, 0)?;
+                // 293: hlt
+                return ic.halt_at(293);
+            }
+            294 => {
+                // 294: mul rb-2, #1, rb-2
+                let v = ic.mul(294, ic.inner[ic.rel_addr(294, -2)?], 1)?;
+                let a = ic.rel_addr(294, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(298);
+                }
+                // 298: arb #-3
+                ic.set_base(298, -3)?;
+                // 300: jt #1, rb+0
+                ic.checked_addr(300, ic.inner[ic.rel_addr(300, 0)?])?
+            }
+            303 => {
+                // 303: arb #5
+                ic.set_base(303, 5)?;
+                // 305: lt rb-3, rb-4, rb-1
+                let v = if ic.inner[ic.rel_addr(305, -3)?] < ic.inner[ic.rel_addr(305, -4)?] { 1 } else { 0 };
+                let a = ic.rel_addr(305, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(309);
+                }
+                // 309: jf rb-1, #346
+                if ic.inner[ic.rel_addr(309, -1)?] == 0 {
+                    346
+                } else {
+                    312
+                }
+            }
+            312 => {
+                // 312: add rb-4, rb-3, rb-4
+                let v = ic.add(312, ic.inner[ic.rel_addr(312, -4)?], ic.inner[ic.rel_addr(312, -3)?])?;
+                let a = ic.rel_addr(312, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(316);
+                }
+                // 316: mul rb-3, #-1, rb-1
+                let v = ic.mul(316, ic.inner[ic.rel_addr(316, -3)?], -1)?;
+                let a = ic.rel_addr(316, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(320);
+                }
+                // 320: add rb-4, rb-1, rb+2
+                let v = ic.add(320, ic.inner[ic.rel_addr(320, -4)?], ic.inner[ic.rel_addr(320, -1)?])?;
+                let a = ic.rel_addr(320, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(324);
+                }
+                // 324: mul rb+2, #-1, rb-1
+                let v = ic.mul(324, ic.inner[ic.rel_addr(324, 2)?], -1)?;
+                let a = ic.rel_addr(324, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(328);
+                }
+                // 328: add rb-4, rb-1, rb+1
+                let v = ic.add(328, ic.inner[ic.rel_addr(328, -4)?], ic.inner[ic.rel_addr(328, -1)?])?;
+                let a = ic.rel_addr(328, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(332);
+                }
+                // 332: mul #1, rb-2, rb+3
+                let v = ic.mul(332, 1, ic.inner[ic.rel_addr(332, -2)?])?;
+                let a = ic.rel_addr(332, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(336);
+                }
+                // 336: add #343, #0, rb+0
+                let v = ic.add(336, 343, 0)?;
+                let a = ic.rel_addr(336, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(340);
+                }
+                // 340: jf #0, #303
+                303
+            }
+            343 => {
+                // 343: jt #1, #415
+                415
+            }
+            346 => {
+                // 346: lt rb-2, rb-3, rb-1
+                let v = if ic.inner[ic.rel_addr(346, -2)?] < ic.inner[ic.rel_addr(346, -3)?] { 1 } else { 0 };
+                let a = ic.rel_addr(346, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(350);
+                }
+                // 350: jf rb-1, #387
+                if ic.inner[ic.rel_addr(350, -1)?] == 0 {
+                    387
+                } else {
+                    353
+                }
+            }
+            353 => {
+                // 353: add rb-3, rb-2, rb-3
+                let v = ic.add(353, ic.inner[ic.rel_addr(353, -3)?], ic.inner[ic.rel_addr(353, -2)?])?;
+                let a = ic.rel_addr(353, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(357);
+                }
+                // 357: mul rb-2, #-1, rb-1
+                let v = ic.mul(357, ic.inner[ic.rel_addr(357, -2)?], -1)?;
+                let a = ic.rel_addr(357, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(361);
+                }
+                // 361: add rb-3, rb-1, rb+3
+                let v = ic.add(361, ic.inner[ic.rel_addr(361, -3)?], ic.inner[ic.rel_addr(361, -1)?])?;
+                let a = ic.rel_addr(361, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(365);
+                }
+                // 365: mul rb+3, #-1, rb-1
+                let v = ic.mul(365, ic.inner[ic.rel_addr(365, 3)?], -1)?;
+                let a = ic.rel_addr(365, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(369);
+                }
+                // 369: add rb-3, rb-1, rb+2
+                let v = ic.add(369, ic.inner[ic.rel_addr(369, -3)?], ic.inner[ic.rel_addr(369, -1)?])?;
+                let a = ic.rel_addr(369, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(373);
+                }
+                // 373: mul #1, rb-4, rb+1
+                let v = ic.mul(373, 1, ic.inner[ic.rel_addr(373, -4)?])?;
+                let a = ic.rel_addr(373, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(377);
+                }
+                // 377: add #384, #0, rb+0
+                let v = ic.add(377, 384, 0)?;
+                let a = ic.rel_addr(377, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(381);
+                }
+                // 381: jt #1, #303
+                303
+            }
+            384 => {
+                // 384: jt #1, #415
+                415
+            }
+            387 => {
+                // 387: mul rb-4, #-1, rb-4
+                let v = ic.mul(387, ic.inner[ic.rel_addr(387, -4)?], -1)?;
+                let a = ic.rel_addr(387, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(391);
+                }
+                // 391: add rb-4, rb-3, rb-4
+                let v = ic.add(391, ic.inner[ic.rel_addr(391, -4)?], ic.inner[ic.rel_addr(391, -3)?])?;
+                let a = ic.rel_addr(391, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(395);
+                }
+                // 395: mul rb-3, rb-2, rb-2
+                let v = ic.mul(395, ic.inner[ic.rel_addr(395, -3)?], ic.inner[ic.rel_addr(395, -2)?])?;
+                let a = ic.rel_addr(395, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(399);
+                }
+                // 399: mul rb-2, rb-4, rb-4
+                let v = ic.mul(399, ic.inner[ic.rel_addr(399, -2)?], ic.inner[ic.rel_addr(399, -4)?])?;
+                let a = ic.rel_addr(399, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(403);
+                }
+                // 403: mul rb-3, rb-2, rb-3
+                let v = ic.mul(403, ic.inner[ic.rel_addr(403, -3)?], ic.inner[ic.rel_addr(403, -2)?])?;
+                let a = ic.rel_addr(403, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(407);
+                }
+                // 407: mul rb-4, #-1, rb-2
+                let v = ic.mul(407, ic.inner[ic.rel_addr(407, -4)?], -1)?;
+                let a = ic.rel_addr(407, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(411);
+                }
+                // 411: add rb-3, rb-2, rb+1
+                let v = ic.add(411, ic.inner[ic.rel_addr(411, -3)?], ic.inner[ic.rel_addr(411, -2)?])?;
+                let a = ic.rel_addr(411, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(415);
+                }
+                415
+            }
+            415 => {
+                // 415: mul #1, rb+1, rb-4
+                let v = ic.mul(415, 1, ic.inner[ic.rel_addr(415, 1)?])?;
+                let a = ic.rel_addr(415, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(419);
+                }
+                // 419: arb #-5
+                ic.set_base(419, -5)?;
+                // 421: jf #0, rb+0
+                ic.checked_addr(421, ic.inner[ic.rel_addr(421, 0)?])?
+            }
+            _ => return ic.resume_at(pc),
+        };
+    }
+}
+
+fn is_code(addr: usize) -> bool {
+    matches!(addr, 0..=131 | 133..=220 | 225..=248 | 250..=423)
+}
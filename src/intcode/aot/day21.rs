@@ -0,0 +1,2475 @@
+//! `input/day21`, compiled by `transpile`.
+//! Regenerate this file rather than editing it.
+use crate::intcode::intcode_io::{Input, Output};
+use crate::intcode::{IntCode, Result};
+
+pub fn run<IO: Input + Output>(ic: &mut IntCode<IO>) -> Result<()> {
+    if ic.halt {
+        return Ok(());
+    }
+    if ic.watched() {
+        return ic.resume_at(ic.pc);
+    }
+    let mut pc = ic.pc;
+    loop {
+        pc = match pc {
+            0 => {
+                // 0: arb #2050
+                match ic.inner[0] {
+                    101 => {
+                        // add #2050, [21101], [966]
+                        ic.inner[966] = ic.add(0, 2050, ic.inner[21101])?;
+                        4
+                    }
+                    102 => {
+                        // mul #2050, [21101], [966]
+                        ic.inner[966] = ic.mul(0, 2050, ic.inner[21101])?;
+                        4
+                    }
+                    104 => {
+                        // out #2050
+                        ic.write_output(0, 2050)?;
+                        2
+                    }
+                    105 => {
+                        // jt #2050, [21101]
+                        ic.checked_addr(0, ic.inner[21101])?
+                    }
+                    106 => {
+                        // jf #2050, [21101]
+                        3
+                    }
+                    107 => {
+                        // lt #2050, [21101], [966]
+                        ic.inner[966] = if 2050 < ic.inner[21101] { 1 } else { 0 };
+                        4
+                    }
+                    108 => {
+                        // eq #2050, [21101], [966]
+                        ic.inner[966] = if 2050 == ic.inner[21101] { 1 } else { 0 };
+                        4
+                    }
+                    109 => {
+                        // arb #2050
+                        ic.set_base(0, 2050)?;
+                        2
+                    }
+                    _ => return ic.resume_at(0),
+                }
+            }
+            2 => {
+                // 2: add #966, #0, rb+1
+                let v = ic.add(2, 966, 0)?;
+                let a = ic.rel_addr(2, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(6);
+                }
+                // 6: add #0, #13, rb+0
+                let v = ic.add(6, 0, 13)?;
+                let a = ic.rel_addr(6, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(10);
+                }
+                // 10: jf #0, #1378
+                1378
+            }
+            13 => {
+                // 13: mul #1, #20, rb+0
+                let v = ic.mul(13, 1, 20)?;
+                let a = ic.rel_addr(13, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(17);
+                }
+                // 17: jf #0, #1337
+                1337
+            }
+            20 => {
+                // 20: mul #1, #27, rb+0
+                let v = ic.mul(20, 1, 27)?;
+                let a = ic.rel_addr(20, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(24);
+                }
+                // 24: jf #0, #1279
+                1279
+            }
+            27 => {
+                // 27: eq rb+1, #65, [748]
+                ic.inner[748] = if ic.inner[ic.rel_addr(27, 1)?] == 65 { 1 } else { 0 };
+                // 31: jt [748], #73
+                if ic.inner[748] != 0 {
+                    73
+                } else {
+                    34
+                }
+            }
+            34 => {
+                // 34: eq rb+1, #79, [748]
+                ic.inner[748] = if ic.inner[ic.rel_addr(34, 1)?] == 79 { 1 } else { 0 };
+                // 38: jt [748], #110
+                if ic.inner[748] != 0 {
+                    110
+                } else {
+                    41
+                }
+            }
+            41 => {
+                // 41: eq rb+1, #78, [748]
+                ic.inner[748] = if ic.inner[ic.rel_addr(41, 1)?] == 78 { 1 } else { 0 };
+                // 45: jt [748], #132
+                if ic.inner[748] != 0 {
+                    132
+                } else {
+                    48
+                }
+            }
+            48 => {
+                // 48: eq rb+1, #87, [748]
+                ic.inner[748] = if ic.inner[ic.rel_addr(48, 1)?] == 87 { 1 } else { 0 };
+                // 52: jt [748], #169
+                if ic.inner[748] != 0 {
+                    169
+                } else {
+                    55
+                }
+            }
+            55 => {
+                // 55: eq rb+1, #82, [748]
+                ic.inner[748] = if ic.inner[ic.rel_addr(55, 1)?] == 82 { 1 } else { 0 };
+                // 59: jt [748], #239
+                if ic.inner[748] != 0 {
+                    239
+                } else {
+                    62
+                }
+            }
+            62 => {
+                // 62: mul #1041, #1, rb+1
+                let v = ic.mul(62, 1041, 1)?;
+                let a = ic.rel_addr(62, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(66);
+                }
+                // 66: add #0, #73, rb+0
+                let v = ic.add(66, 0, 73)?;
+                let a = ic.rel_addr(66, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(70);
+                }
+                // 70: jf #0, #1421
+                1421
+            }
+            73 => {
+                // 73: add #0, #78, rb+1
+                let v = ic.add(73, 0, 78)?;
+                let a = ic.rel_addr(73, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(77);
+                }
+                // 77: mul #1, #1041, rb+2
+                let v = ic.mul(77, 1, 1041)?;
+                let a = ic.rel_addr(77, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(81);
+                }
+                // 81: mul #88, #1, rb+0
+                let v = ic.mul(81, 88, 1)?;
+                let a = ic.rel_addr(81, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(85);
+                }
+                // 85: jf #0, #1301
+                1301
+            }
+            88 => {
+                // 88: mul #68, #1, rb+1
+                let v = ic.mul(88, 68, 1)?;
+                let a = ic.rel_addr(88, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(92);
+                }
+                // 92: add #0, #1041, rb+2
+                let v = ic.add(92, 0, 1041)?;
+                let a = ic.rel_addr(92, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(96);
+                }
+                // 96: mul #1, #103, rb+0
+                let v = ic.mul(96, 1, 103)?;
+                let a = ic.rel_addr(96, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(100);
+                }
+                // 100: jt #1, #1301
+                1301
+            }
+            103 => {
+                // 103: add #0, #1, [750]
+                ic.inner[750] = ic.add(103, 0, 1)?;
+                // 107: jf #0, #298
+                298
+            }
+            110 => {
+                // 110: add #82, #0, rb+1
+                let v = ic.add(110, 82, 0)?;
+                let a = ic.rel_addr(110, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(114);
+                }
+                // 114: mul #1, #1041, rb+2
+                let v = ic.mul(114, 1, 1041)?;
+                let a = ic.rel_addr(114, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(118);
+                }
+                // 118: add #125, #0, rb+0
+                let v = ic.add(118, 125, 0)?;
+                let a = ic.rel_addr(118, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(122);
+                }
+                // 122: jt #1, #1301
+                1301
+            }
+            125 => {
+                // 125: add #0, #2, [750]
+                ic.inner[750] = ic.add(125, 0, 2)?;
+                // 129: jf #0, #298
+                298
+            }
+            132 => {
+                // 132: add #79, #0, rb+1
+                let v = ic.add(132, 79, 0)?;
+                let a = ic.rel_addr(132, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(136);
+                }
+                // 136: mul #1041, #1, rb+2
+                let v = ic.mul(136, 1041, 1)?;
+                let a = ic.rel_addr(136, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(140);
+                }
+                // 140: mul #147, #1, rb+0
+                let v = ic.mul(140, 147, 1)?;
+                let a = ic.rel_addr(140, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(144);
+                }
+                // 144: jt #1, #1301
+                1301
+            }
+            147 => {
+                // 147: mul #84, #1, rb+1
+                let v = ic.mul(147, 84, 1)?;
+                let a = ic.rel_addr(147, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(151);
+                }
+                // 151: mul #1, #1041, rb+2
+                let v = ic.mul(151, 1, 1041)?;
+                let a = ic.rel_addr(151, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(155);
+                }
+                // 155: add #162, #0, rb+0
+                let v = ic.add(155, 162, 0)?;
+                let a = ic.rel_addr(155, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(159);
+                }
+                // 159: jt #1, #1301
+                1301
+            }
+            162 => {
+                // 162: add #0, #3, [750]
+                ic.inner[750] = ic.add(162, 0, 3)?;
+                // 166: jt #1, #298
+                298
+            }
+            169 => {
+                // 169: mul #1, #65, rb+1
+                let v = ic.mul(169, 1, 65)?;
+                let a = ic.rel_addr(169, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(173);
+                }
+                // 173: add #1041, #0, rb+2
+                let v = ic.add(173, 1041, 0)?;
+                let a = ic.rel_addr(173, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(177);
+                }
+                // 177: add #184, #0, rb+0
+                let v = ic.add(177, 184, 0)?;
+                let a = ic.rel_addr(177, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(181);
+                }
+                // 181: jf #0, #1301
+                1301
+            }
+            184 => {
+                // 184: mul #1, #76, rb+1
+                let v = ic.mul(184, 1, 76)?;
+                let a = ic.rel_addr(184, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(188);
+                }
+                // 188: mul #1, #1041, rb+2
+                let v = ic.mul(188, 1, 1041)?;
+                let a = ic.rel_addr(188, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(192);
+                }
+                // 192: mul #1, #199, rb+0
+                let v = ic.mul(192, 1, 199)?;
+                let a = ic.rel_addr(192, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(196);
+                }
+                // 196: jf #0, #1301
+                1301
+            }
+            199 => {
+                // 199: add #0, #75, rb+1
+                let v = ic.add(199, 0, 75)?;
+                let a = ic.rel_addr(199, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(203);
+                }
+                // 203: add #0, #1041, rb+2
+                let v = ic.add(203, 0, 1041)?;
+                let a = ic.rel_addr(203, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(207);
+                }
+                // 207: mul #214, #1, rb+0
+                let v = ic.mul(207, 214, 1)?;
+                let a = ic.rel_addr(207, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(211);
+                }
+                // 211: jt #1, #1301
+                1301
+            }
+            214 => {
+                // 214: add #0, #221, rb+0
+                let v = ic.add(214, 0, 221)?;
+                let a = ic.rel_addr(214, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(218);
+                }
+                // 218: jf #0, #1337
+                1337
+            }
+            221 => {
+                // 221: mul #1, #10, rb+1
+                let v = ic.mul(221, 1, 10)?;
+                let a = ic.rel_addr(221, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(225);
+                }
+                // 225: mul #1041, #1, rb+2
+                let v = ic.mul(225, 1041, 1)?;
+                let a = ic.rel_addr(225, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(229);
+                }
+                // 229: mul #1, #236, rb+0
+                let v = ic.mul(229, 1, 236)?;
+                let a = ic.rel_addr(229, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(233);
+                }
+                // 233: jf #0, #1301
+                1301
+            }
+            236 => {
+                // 236: jt #1, #553
+                553
+            }
+            239 => {
+                // 239: add #0, #85, rb+1
+                let v = ic.add(239, 0, 85)?;
+                let a = ic.rel_addr(239, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(243);
+                }
+                // 243: add #0, #1041, rb+2
+                let v = ic.add(243, 0, 1041)?;
+                let a = ic.rel_addr(243, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(247);
+                }
+                // 247: add #254, #0, rb+0
+                let v = ic.add(247, 254, 0)?;
+                let a = ic.rel_addr(247, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(251);
+                }
+                // 251: jt #1, #1301
+                1301
+            }
+            254 => {
+                // 254: add #0, #78, rb+1
+                let v = ic.add(254, 0, 78)?;
+                let a = ic.rel_addr(254, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(258);
+                }
+                // 258: mul #1, #1041, rb+2
+                let v = ic.mul(258, 1, 1041)?;
+                let a = ic.rel_addr(258, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(262);
+                }
+                // 262: add #0, #269, rb+0
+                let v = ic.add(262, 0, 269)?;
+                let a = ic.rel_addr(262, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(266);
+                }
+                // 266: jf #0, #1301
+                1301
+            }
+            269 => {
+                // 269: add #0, #276, rb+0
+                let v = ic.add(269, 0, 276)?;
+                let a = ic.rel_addr(269, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(273);
+                }
+                // 273: jf #0, #1337
+                1337
+            }
+            276 => {
+                // 276: mul #10, #1, rb+1
+                let v = ic.mul(276, 10, 1)?;
+                let a = ic.rel_addr(276, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(280);
+                }
+                // 280: add #0, #1041, rb+2
+                let v = ic.add(280, 0, 1041)?;
+                let a = ic.rel_addr(280, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(284);
+                }
+                // 284: mul #291, #1, rb+0
+                let v = ic.mul(284, 291, 1)?;
+                let a = ic.rel_addr(284, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(288);
+                }
+                // 288: jt #1, #1301
+                1301
+            }
+            291 => {
+                // 291: add #0, #1, [755]
+                ic.inner[755] = ic.add(291, 0, 1)?;
+                // 295: jf #0, #553
+                553
+            }
+            298 => {
+                // 298: add #0, #32, rb+1
+                let v = ic.add(298, 0, 32)?;
+                let a = ic.rel_addr(298, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(302);
+                }
+                // 302: mul #1, #1041, rb+2
+                let v = ic.mul(302, 1, 1041)?;
+                let a = ic.rel_addr(302, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(306);
+                }
+                // 306: mul #313, #1, rb+0
+                let v = ic.mul(306, 313, 1)?;
+                let a = ic.rel_addr(306, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(310);
+                }
+                // 310: jf #0, #1301
+                1301
+            }
+            313 => {
+                // 313: add #320, #0, rb+0
+                let v = ic.add(313, 320, 0)?;
+                let a = ic.rel_addr(313, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(317);
+                }
+                // 317: jf #0, #1337
+                1337
+            }
+            320 => {
+                // 320: mul #1, #327, rb+0
+                let v = ic.mul(320, 1, 327)?;
+                let a = ic.rel_addr(320, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(324);
+                }
+                // 324: jf #0, #1279
+                1279
+            }
+            327 => {
+                // 327: mul rb+1, #1, [749]
+                ic.inner[749] = ic.mul(327, ic.inner[ic.rel_addr(327, 1)?], 1)?;
+                // 331: mul #65, #1, rb+2
+                let v = ic.mul(331, 65, 1)?;
+                let a = ic.rel_addr(331, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(335);
+                }
+                // 335: add #73, #0, rb+3
+                let v = ic.add(335, 73, 0)?;
+                let a = ic.rel_addr(335, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(339);
+                }
+                // 339: mul #1, #346, rb+0
+                let v = ic.mul(339, 1, 346)?;
+                let a = ic.rel_addr(339, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(343);
+                }
+                // 343: jt #1, #1889
+                1889
+            }
+            346 => {
+                // 346: jf rb+1, #367
+                if ic.inner[ic.rel_addr(346, 1)?] == 0 {
+                    367
+                } else {
+                    349
+                }
+            }
+            349 => {
+                // 349: lt [749], #69, [748]
+                ic.inner[748] = if ic.inner[749] < 69 { 1 } else { 0 };
+                // 353: jt [748], #360
+                if ic.inner[748] != 0 {
+                    360
+                } else {
+                    356
+                }
+            }
+            356 => {
+                // 356: add #1, #0, [756]
+                ic.inner[756] = ic.add(356, 1, 0)?;
+                360
+            }
+            360 => {
+                // 360: add [749], #-64, [751]
+                ic.inner[751] = ic.add(360, ic.inner[749], -64)?;
+                // 364: jf #0, #406
+                406
+            }
+            367 => {
+                // 367: eq [749], #74, [748]
+                ic.inner[748] = if ic.inner[749] == 74 { 1 } else { 0 };
+                // 371: jf [748], #381
+                if ic.inner[748] == 0 {
+                    381
+                } else {
+                    374
+                }
+            }
+            374 => {
+                // 374: mul #-1, #1, [751]
+                ic.inner[751] = ic.mul(374, -1, 1)?;
+                // 378: jf #0, #406
+                406
+            }
+            381 => {
+                // 381: eq [749], #84, [748]
+                ic.inner[748] = if ic.inner[749] == 84 { 1 } else { 0 };
+                // 385: jf [748], #395
+                if ic.inner[748] == 0 {
+                    395
+                } else {
+                    388
+                }
+            }
+            388 => {
+                // 388: add #-2, #0, [751]
+                ic.inner[751] = ic.add(388, -2, 0)?;
+                // 392: jf #0, #406
+                406
+            }
+            395 => {
+                // 395: mul #1, #1100, rb+1
+                let v = ic.mul(395, 1, 1100)?;
+                let a = ic.rel_addr(395, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(399);
+                }
+                // 399: add #0, #406, rb+0
+                let v = ic.add(399, 0, 406)?;
+                let a = ic.rel_addr(399, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(403);
+                }
+                // 403: jt #1, #1421
+                1421
+            }
+            406 => {
+                // 406: add #32, #0, rb+1
+                let v = ic.add(406, 32, 0)?;
+                let a = ic.rel_addr(406, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(410);
+                }
+                // 410: mul #1, #1100, rb+2
+                let v = ic.mul(410, 1, 1100)?;
+                let a = ic.rel_addr(410, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(414);
+                }
+                // 414: add #0, #421, rb+0
+                let v = ic.add(414, 0, 421)?;
+                let a = ic.rel_addr(414, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(418);
+                }
+                // 418: jt #1, #1301
+                1301
+            }
+            421 => {
+                // 421: add #0, #428, rb+0
+                let v = ic.add(421, 0, 428)?;
+                let a = ic.rel_addr(421, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(425);
+                }
+                // 425: jf #0, #1337
+                1337
+            }
+            428 => {
+                // 428: mul #435, #1, rb+0
+                let v = ic.mul(428, 435, 1)?;
+                let a = ic.rel_addr(428, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(432);
+                }
+                // 432: jf #0, #1279
+                1279
+            }
+            435 => {
+                // 435: add #0, rb+1, [749]
+                ic.inner[749] = ic.add(435, 0, ic.inner[ic.rel_addr(435, 1)?])?;
+                // 439: eq [749], #74, [748]
+                ic.inner[748] = if ic.inner[749] == 74 { 1 } else { 0 };
+                // 443: jf [748], #453
+                if ic.inner[748] == 0 {
+                    453
+                } else {
+                    446
+                }
+            }
+            446 => {
+                // 446: add #0, #-1, [752]
+                ic.inner[752] = ic.add(446, 0, -1)?;
+                // 450: jf #0, #478
+                478
+            }
+            453 => {
+                // 453: eq [749], #84, [748]
+                ic.inner[748] = if ic.inner[749] == 84 { 1 } else { 0 };
+                // 457: jf [748], #467
+                if ic.inner[748] == 0 {
+                    467
+                } else {
+                    460
+                }
+            }
+            460 => {
+                // 460: add #0, #-2, [752]
+                ic.inner[752] = ic.add(460, 0, -2)?;
+                // 464: jt #1, #478
+                478
+            }
+            467 => {
+                // 467: mul #1168, #1, rb+1
+                let v = ic.mul(467, 1168, 1)?;
+                let a = ic.rel_addr(467, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(471);
+                }
+                // 471: mul #478, #1, rb+0
+                let v = ic.mul(471, 478, 1)?;
+                let a = ic.rel_addr(471, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(475);
+                }
+                // 475: jt #1, #1421
+                1421
+            }
+            478 => {
+                // 478: mul #485, #1, rb+0
+                let v = ic.mul(478, 485, 1)?;
+                let a = ic.rel_addr(478, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(482);
+                }
+                // 482: jt #1, #1337
+                1337
+            }
+            485 => {
+                // 485: mul #10, #1, rb+1
+                let v = ic.mul(485, 10, 1)?;
+                let a = ic.rel_addr(485, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(489);
+                }
+                // 489: mul #1, #1168, rb+2
+                let v = ic.mul(489, 1, 1168)?;
+                let a = ic.rel_addr(489, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(493);
+                }
+                // 493: mul #1, #500, rb+0
+                let v = ic.mul(493, 1, 500)?;
+                let a = ic.rel_addr(493, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(497);
+                }
+                // 497: jf #0, #1301
+                1301
+            }
+            500 => {
+                // 500: lt [920], #15, [748]
+                ic.inner[748] = if ic.inner[920] < 15 { 1 } else { 0 };
+                // 504: jt [748], #518
+                if ic.inner[748] != 0 {
+                    518
+                } else {
+                    507
+                }
+            }
+            507 => {
+                // 507: mul #1209, #1, rb+1
+                let v = ic.mul(507, 1209, 1)?;
+                let a = ic.rel_addr(507, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(511);
+                }
+                // 511: mul #518, #1, rb+0
+                let v = ic.mul(511, 518, 1)?;
+                let a = ic.rel_addr(511, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(515);
+                }
+                // 515: jt #1, #1421
+                1421
+            }
+            518 => {
+                // 518: mul [920], #3, [529]
+                ic.inner[529] = ic.mul(518, ic.inner[920], 3)?;
+                // 522: add [529], #921, [529]
+                ic.inner[529] = ic.add(522, ic.inner[529], 921)?;
+                // 526: mul #1, [750], [0]
+                let v = ic.mul(526, 1, ic.inner[750])?;
+                let a = ic.checked_addr(526, ic.inner[529])?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(530);
+                }
+                // 530: add [529], #1, [537]
+                ic.inner[537] = ic.add(530, ic.inner[529], 1)?;
+                // 534: add #0, [751], [0]
+                let v = ic.add(534, 0, ic.inner[751])?;
+                let a = ic.checked_addr(534, ic.inner[537])?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(538);
+                }
+                // 538: add [537], #1, [545]
+                ic.inner[545] = ic.add(538, ic.inner[537], 1)?;
+                // 542: mul [752], #1, [0]
+                let v = ic.mul(542, ic.inner[752], 1)?;
+                let a = ic.checked_addr(542, ic.inner[545])?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(546);
+                }
+                // 546: add [920], #1, [920]
+                ic.inner[920] = ic.add(546, ic.inner[920], 1)?;
+                // 550: jt #1, #13
+                13
+            }
+            553 => {
+                // 553: jt [755], #577
+                if ic.inner[755] != 0 {
+                    577
+                } else {
+                    556
+                }
+            }
+            556 => {
+                // 556: jf [756], #570
+                if ic.inner[756] == 0 {
+                    570
+                } else {
+                    559
+                }
+            }
+            559 => {
+                // 559: mul #1, #1100, rb+1
+                let v = ic.mul(559, 1, 1100)?;
+                let a = ic.rel_addr(559, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(563);
+                }
+                // 563: add #570, #0, rb+0
+                let v = ic.add(563, 570, 0)?;
+                let a = ic.rel_addr(563, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(567);
+                }
+                // 567: jf #0, #1421
+                1421
+            }
+            570 => {
+                // 570: add #987, #0, rb+1
+                let v = ic.add(570, 987, 0)?;
+                let a = ic.rel_addr(570, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(574);
+                }
+                // 574: jf #0, #581
+                581
+            }
+            577 => {
+                // 577: mul #1, #1001, rb+1
+                let v = ic.mul(577, 1, 1001)?;
+                let a = ic.rel_addr(577, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(581);
+                }
+                581
+            }
+            581 => {
+                // 581: add #588, #0, rb+0
+                let v = ic.add(581, 588, 0)?;
+                let a = ic.rel_addr(581, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(585);
+                }
+                // 585: jt #1, #1378
+                1378
+            }
+            588 => {
+                // 588: add #0, #758, [593]
+                ic.inner[593] = ic.add(588, 0, 758)?;
+                592
+            }
+            592 => {
+                // 592: mul [0], #1, [753]
+                ic.inner[753] = ic.mul(592, ic.inner[ic.checked_addr(592, ic.inner[593])?], 1)?;
+                // 596: jf [753], #654
+                if ic.inner[753] == 0 {
+                    654
+                } else {
+                    599
+                }
+            }
+            599 => {
+                // 599: mul #1, [753], rb+1
+                let v = ic.mul(599, 1, ic.inner[753])?;
+                let a = ic.rel_addr(599, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(603);
+                }
+                // 603: add #610, #0, rb+0
+                let v = ic.add(603, 610, 0)?;
+                let a = ic.rel_addr(603, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(607);
+                }
+                // 607: jt #1, #667
+                667
+            }
+            610 => {
+                // 610: mul #0, #1, rb+1
+                let v = ic.mul(610, 0, 1)?;
+                let a = ic.rel_addr(610, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(614);
+                }
+                // 614: add #621, #0, rb+0
+                let v = ic.add(614, 621, 0)?;
+                let a = ic.rel_addr(614, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(618);
+                }
+                // 618: jf #0, #1463
+                1463
+            }
+            621 => {
+                // 621: jt rb+1, #647
+                if ic.inner[ic.rel_addr(621, 1)?] != 0 {
+                    647
+                } else {
+                    624
+                }
+            }
+            624 => {
+                // 624: mul #1, #1015, rb+1
+                let v = ic.mul(624, 1, 1015)?;
+                let a = ic.rel_addr(624, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(628);
+                }
+                // 628: add #635, #0, rb+0
+                let v = ic.add(628, 635, 0)?;
+                let a = ic.rel_addr(628, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(632);
+                }
+                // 632: jf #0, #1378
+                1378
+            }
+            635 => {
+                // 635: mul #1, #1, rb+1
+                let v = ic.mul(635, 1, 1)?;
+                let a = ic.rel_addr(635, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(639);
+                }
+                // 639: add #0, #646, rb+0
+                let v = ic.add(639, 0, 646)?;
+                let a = ic.rel_addr(639, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(643);
+                }
+                // 643: jt #1, #1463
+                1463
+            }
+            646 => {
+                // 646: hlt
+                return ic.halt_at(646);
+            }
+            647 => {
+                // 647: add [593], #1, [593]
+                ic.inner[593] = ic.add(647, ic.inner[593], 1)?;
+                // 651: jf #0, #592
+                592
+            }
+            654 => {
+                // 654: jf [755], #664
+                if ic.inner[755] == 0 {
+                    664
+                } else {
+                    657
+                }
+            }
+            657 => {
+                // 657: mul #0, #1, [755]
+                ic.inner[755] = ic.mul(657, 0, 1)?;
+                // 661: jt #1, #647
+                647
+            }
+            664 => {
+                // 664: out [754]
+                ic.write_output(664, ic.inner[754])?;
+                // 666: hlt
+                return ic.halt_at(666);
+            }
+            667 => {
+                // 667: arb #2
+                ic.set_base(667, 2)?;
+                // 669: mul #1, #726, [757]
+                ic.inner[757] = ic.mul(669, 1, 726)?;
+                // 673: mul rb-1, #1, rb+1
+                let v = ic.mul(673, ic.inner[ic.rel_addr(673, -1)?], 1)?;
+                let a = ic.rel_addr(673, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(677);
+                }
+                // 677: add #0, #9, rb+2
+                let v = ic.add(677, 0, 9)?;
+                let a = ic.rel_addr(677, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(681);
+                }
+                // 681: add #0, #697, rb+3
+                let v = ic.add(681, 0, 697)?;
+                let a = ic.rel_addr(681, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(685);
+                }
+                // 685: add #0, #692, rb+0
+                let v = ic.add(685, 0, 692)?;
+                let a = ic.rel_addr(685, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(689);
+                }
+                // 689: jf #0, #1913
+                1913
+            }
+            692 => {
+                // 692: arb #-2
+                ic.set_base(692, -2)?;
+                // 694: jf #0, rb+0
+                ic.checked_addr(694, ic.inner[ic.rel_addr(694, 0)?])?
+            }
+            697 => {
+                // 697: arb #2
+                ic.set_base(697, 2)?;
+                // 699: add #0, [757], [706]
+                ic.inner[706] = ic.add(699, 0, ic.inner[757])?;
+                // 703: add #0, rb-1, [0]
+                let v = ic.add(703, 0, ic.inner[ic.rel_addr(703, -1)?])?;
+                let a = ic.checked_addr(703, ic.inner[706])?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(707);
+                }
+                // 707: add [757], #1, [757]
+                ic.inner[757] = ic.add(707, ic.inner[757], 1)?;
+                // 711: arb #-2
+                ic.set_base(711, -2)?;
+                // 713: jt #1, rb+0
+                ic.checked_addr(713, ic.inner[ic.rel_addr(713, 0)?])?
+            }
+            1263 => {
+                // 1263: arb #1
+                ic.set_base(1263, 1)?;
+                // 1265: jt [1262], #1270
+                if ic.inner[1262] != 0 {
+                    1270
+                } else {
+                    1268
+                }
+            }
+            1268 => {
+                // 1268: in [1262]
+                ic.inner[1262] = ic.read_input(1268)?;
+                1270
+            }
+            1270 => {
+                // 1270: add [1262], #0, rb+0
+                let v = ic.add(1270, ic.inner[1262], 0)?;
+                let a = ic.rel_addr(1270, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1274);
+                }
+                // 1274: arb #-1
+                ic.set_base(1274, -1)?;
+                // 1276: jf #0, rb+0
+                ic.checked_addr(1276, ic.inner[ic.rel_addr(1276, 0)?])?
+            }
+            1279 => {
+                // 1279: arb #1
+                ic.set_base(1279, 1)?;
+                // 1281: mul #1288, #1, rb+0
+                let v = ic.mul(1281, 1288, 1)?;
+                let a = ic.rel_addr(1281, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1285);
+                }
+                // 1285: jt #1, #1263
+                1263
+            }
+            1288 => {
+                // 1288: add #0, [1262], rb+0
+                let v = ic.add(1288, 0, ic.inner[1262])?;
+                let a = ic.rel_addr(1288, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1292);
+                }
+                // 1292: mul #1, #0, [1262]
+                ic.inner[1262] = ic.mul(1292, 1, 0)?;
+                // 1296: arb #-1
+                ic.set_base(1296, -1)?;
+                // 1298: jt #1, rb+0
+                ic.checked_addr(1298, ic.inner[ic.rel_addr(1298, 0)?])?
+            }
+            1301 => {
+                // 1301: arb #5
+                ic.set_base(1301, 5)?;
+                // 1303: mul #1310, #1, rb+0
+                let v = ic.mul(1303, 1310, 1)?;
+                let a = ic.rel_addr(1303, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1307);
+                }
+                // 1307: jf #0, #1279
+                1279
+            }
+            1310 => {
+                // 1310: mul #1, rb+1, rb-2
+                let v = ic.mul(1310, 1, ic.inner[ic.rel_addr(1310, 1)?])?;
+                let a = ic.rel_addr(1310, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1314);
+                }
+                // 1314: eq rb-2, rb-4, rb-1
+                let v = if ic.inner[ic.rel_addr(1314, -2)?] == ic.inner[ic.rel_addr(1314, -4)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1314, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1318);
+                }
+                // 1318: jt rb-1, #1332
+                if ic.inner[ic.rel_addr(1318, -1)?] != 0 {
+                    1332
+                } else {
+                    1321
+                }
+            }
+            1321 => {
+                // 1321: mul #1, rb-3, rb+1
+                let v = ic.mul(1321, 1, ic.inner[ic.rel_addr(1321, -3)?])?;
+                let a = ic.rel_addr(1321, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1325);
+                }
+                // 1325: mul #1332, #1, rb+0
+                let v = ic.mul(1325, 1332, 1)?;
+                let a = ic.rel_addr(1325, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1329);
+                }
+                // 1329: jt #1, #1421
+                1421
+            }
+            1332 => {
+                // 1332: arb #-5
+                ic.set_base(1332, -5)?;
+                // 1334: jf #0, rb+0
+                ic.checked_addr(1334, ic.inner[ic.rel_addr(1334, 0)?])?
+            }
+            1337 => {
+                // 1337: arb #2
+                ic.set_base(1337, 2)?;
+                1339
+            }
+            1339 => {
+                // 1339: add #1346, #0, rb+0
+                let v = ic.add(1339, 1346, 0)?;
+                let a = ic.rel_addr(1339, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1343);
+                }
+                // 1343: jf #0, #1263
+                1263
+            }
+            1346 => {
+                // 1346: eq rb+1, #32, rb-1
+                let v = if ic.inner[ic.rel_addr(1346, 1)?] == 32 { 1 } else { 0 };
+                let a = ic.rel_addr(1346, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1350);
+                }
+                // 1350: jt rb-1, #1363
+                if ic.inner[ic.rel_addr(1350, -1)?] != 0 {
+                    1363
+                } else {
+                    1353
+                }
+            }
+            1353 => {
+                // 1353: eq rb+1, #9, rb-1
+                let v = if ic.inner[ic.rel_addr(1353, 1)?] == 9 { 1 } else { 0 };
+                let a = ic.rel_addr(1353, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1357);
+                }
+                // 1357: jt rb-1, #1363
+                if ic.inner[ic.rel_addr(1357, -1)?] != 0 {
+                    1363
+                } else {
+                    1360
+                }
+            }
+            1360 => {
+                // 1360: jf #0, #1373
+                1373
+            }
+            1363 => {
+                // 1363: add #1370, #0, rb+0
+                let v = ic.add(1363, 1370, 0)?;
+                let a = ic.rel_addr(1363, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1367);
+                }
+                // 1367: jt #1, #1279
+                1279
+            }
+            1370 => {
+                // 1370: jt #1, #1339
+                1339
+            }
+            1373 => {
+                // 1373: arb #-2
+                ic.set_base(1373, -2)?;
+                // 1375: jt #1, rb+0
+                ic.checked_addr(1375, ic.inner[ic.rel_addr(1375, 0)?])?
+            }
+            1378 => {
+                // 1378: arb #5
+                ic.set_base(1378, 5)?;
+                // 1380: add rb-4, #0, [1385]
+                ic.inner[1385] = ic.add(1380, ic.inner[ic.rel_addr(1380, -4)?], 0)?;
+                // 1384: add [0], #0, rb-2
+                let v = ic.add(1384, ic.inner[ic.checked_addr(1384, ic.inner[1385])?], 0)?;
+                let a = ic.rel_addr(1384, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1388);
+                }
+                // 1388: add #1, rb-4, rb-4
+                let v = ic.add(1388, 1, ic.inner[ic.rel_addr(1388, -4)?])?;
+                let a = ic.rel_addr(1388, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1392);
+                }
+                // 1392: mul #0, #1, rb-3
+                let v = ic.mul(1392, 0, 1)?;
+                let a = ic.rel_addr(1392, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1396);
+                }
+                1396
+            }
+            1396 => {
+                // 1396: eq rb-3, rb-2, rb-1
+                let v = if ic.inner[ic.rel_addr(1396, -3)?] == ic.inner[ic.rel_addr(1396, -2)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1396, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1400);
+                }
+                // 1400: jt rb-1, #1416
+                if ic.inner[ic.rel_addr(1400, -1)?] != 0 {
+                    1416
+                } else {
+                    1403
+                }
+            }
+            1403 => {
+                // 1403: add rb-4, rb-3, [1408]
+                ic.inner[1408] = ic.add(1403, ic.inner[ic.rel_addr(1403, -4)?], ic.inner[ic.rel_addr(1403, -3)?])?;
+                // 1407: out [0]
+                ic.write_output(1407, ic.inner[ic.checked_addr(1407, ic.inner[1408])?])?;
+                // 1409: add rb-3, #1, rb-3
+                let v = ic.add(1409, ic.inner[ic.rel_addr(1409, -3)?], 1)?;
+                let a = ic.rel_addr(1409, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1413);
+                }
+                // 1413: jt #1, #1396
+                1396
+            }
+            1416 => {
+                // 1416: arb #-5
+                ic.set_base(1416, -5)?;
+                // 1418: jf #0, rb+0
+                ic.checked_addr(1418, ic.inner[ic.rel_addr(1418, 0)?])?
+            }
+            1421 => {
+                // 1421: arb #2
+                ic.set_base(1421, 2)?;
+                // 1423: out #10
+                ic.write_output(1423, 10)?;
+                // 1425: add #0, rb-1, rb+1
+                let v = ic.add(1425, 0, ic.inner[ic.rel_addr(1425, -1)?])?;
+                let a = ic.rel_addr(1425, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1429);
+                }
+                // 1429: mul #1436, #1, rb+0
+                let v = ic.mul(1429, 1436, 1)?;
+                let a = ic.rel_addr(1429, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1433);
+                }
+                // 1433: jt #1, #1378
+                1378
+            }
+            1436 => {
+                // 1436: out #10
+                ic.write_output(1436, 10)?;
+                // 1438: hlt
+                return ic.halt_at(1438);
+            }
+            1444 => {
+                // 1444: arb #3
+                ic.set_base(1444, 3)?;
+                // 1446: mul [593], [753], rb-1
+                let v = ic.mul(1446, ic.inner[593], ic.inner[753])?;
+                let a = ic.rel_addr(1446, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1450);
+                }
+                // 1450: mul rb-1, rb-2, rb-1
+                let v = ic.mul(1450, ic.inner[ic.rel_addr(1450, -1)?], ic.inner[ic.rel_addr(1450, -2)?])?;
+                let a = ic.rel_addr(1450, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1454);
+                }
+                // 1454: add rb-1, [754], [754]
+                ic.inner[754] = ic.add(1454, ic.inner[ic.rel_addr(1454, -1)?], ic.inner[754])?;
+                // 1458: arb #-3
+                ic.set_base(1458, -3)?;
+                // 1460: jf #0, rb+0
+                ic.checked_addr(1460, ic.inner[ic.rel_addr(1460, 0)?])?
+            }
+            1463 => {
+                // 1463: arb #10
+                ic.set_base(1463, 10)?;
+                // 1465: add #0, #5, rb-5
+                let v = ic.add(1465, 0, 5)?;
+                let a = ic.rel_addr(1465, -5)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1469);
+                }
+                // 1469: add #1, #0, rb-4
+                let v = ic.add(1469, 1, 0)?;
+                let a = ic.rel_addr(1469, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1473);
+                }
+                // 1473: mul #1, #0, rb-3
+                let v = ic.mul(1473, 1, 0)?;
+                let a = ic.rel_addr(1473, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1477);
+                }
+                1477
+            }
+            1477 => {
+                // 1477: jf rb-9, #1555
+                if ic.inner[ic.rel_addr(1477, -9)?] == 0 {
+                    1555
+                } else {
+                    1480
+                }
+            }
+            1480 => {
+                // 1480: add #3, #0, rb-6
+                let v = ic.add(1480, 3, 0)?;
+                let a = ic.rel_addr(1480, -6)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1484);
+                }
+                1484
+            }
+            1484 => {
+                // 1484: add #0, #5, rb-7
+                let v = ic.add(1484, 0, 5)?;
+                let a = ic.rel_addr(1484, -7)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1488);
+                }
+                1488
+            }
+            1488 => {
+                // 1488: eq rb-7, rb-5, rb-8
+                let v = if ic.inner[ic.rel_addr(1488, -7)?] == ic.inner[ic.rel_addr(1488, -5)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1488, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1492);
+                }
+                // 1492: jf rb-8, #1507
+                if ic.inner[ic.rel_addr(1492, -8)?] == 0 {
+                    1507
+                } else {
+                    1495
+                }
+            }
+            1495 => {
+                // 1495: eq rb-6, rb-4, rb-8
+                let v = if ic.inner[ic.rel_addr(1495, -6)?] == ic.inner[ic.rel_addr(1495, -4)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1495, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1499);
+                }
+                // 1499: jf rb-8, #1507
+                if ic.inner[ic.rel_addr(1499, -8)?] == 0 {
+                    1507
+                } else {
+                    1502
+                }
+            }
+            1502 => {
+                // 1502: out #64
+                ic.write_output(1502, 64)?;
+                // 1504: jf #0, #1529
+                1529
+            }
+            1507 => {
+                // 1507: jt rb-6, #1527
+                if ic.inner[ic.rel_addr(1507, -6)?] != 0 {
+                    1527
+                } else {
+                    1510
+                }
+            }
+            1510 => {
+                // 1510: add rb-7, #716, [1515]
+                ic.inner[1515] = ic.add(1510, ic.inner[ic.rel_addr(1510, -7)?], 716)?;
+                // 1514: mul [0], #-11, rb-8
+                let v = ic.mul(1514, ic.inner[ic.checked_addr(1514, ic.inner[1515])?], -11)?;
+                let a = ic.rel_addr(1514, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1518);
+                }
+                // 1518: add rb-8, #46, rb-8
+                let v = ic.add(1518, ic.inner[ic.rel_addr(1518, -8)?], 46)?;
+                let a = ic.rel_addr(1518, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1522);
+                }
+                // 1522: out rb-8
+                ic.write_output(1522, ic.inner[ic.rel_addr(1522, -8)?])?;
+                // 1524: jt #1, #1529
+                1529
+            }
+            1527 => {
+                // 1527: out #46
+                ic.write_output(1527, 46)?;
+                1529
+            }
+            1529 => {
+                // 1529: add rb-7, #1, rb-7
+                let v = ic.add(1529, ic.inner[ic.rel_addr(1529, -7)?], 1)?;
+                let a = ic.rel_addr(1529, -7)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1533);
+                }
+                // 1533: lt rb-7, #22, rb-8
+                let v = if ic.inner[ic.rel_addr(1533, -7)?] < 22 { 1 } else { 0 };
+                let a = ic.rel_addr(1533, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1537);
+                }
+                // 1537: jt rb-8, #1488
+                if ic.inner[ic.rel_addr(1537, -8)?] != 0 {
+                    1488
+                } else {
+                    1540
+                }
+            }
+            1540 => {
+                // 1540: out #10
+                ic.write_output(1540, 10)?;
+                // 1542: add rb-6, #-1, rb-6
+                let v = ic.add(1542, ic.inner[ic.rel_addr(1542, -6)?], -1)?;
+                let a = ic.rel_addr(1542, -6)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1546);
+                }
+                // 1546: lt rb-6, #0, rb-8
+                let v = if ic.inner[ic.rel_addr(1546, -6)?] < 0 { 1 } else { 0 };
+                let a = ic.rel_addr(1546, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1550);
+                }
+                // 1550: jf rb-8, #1484
+                if ic.inner[ic.rel_addr(1550, -8)?] == 0 {
+                    1484
+                } else {
+                    1553
+                }
+            }
+            1553 => {
+                // 1553: out #10
+                ic.write_output(1553, 10)?;
+                1555
+            }
+            1555 => {
+                // 1555: lt rb-4, #1, rb-8
+                let v = if ic.inner[ic.rel_addr(1555, -4)?] < 1 { 1 } else { 0 };
+                let a = ic.rel_addr(1555, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1559);
+                }
+                // 1559: jf rb-8, #1569
+                if ic.inner[ic.rel_addr(1559, -8)?] == 0 {
+                    1569
+                } else {
+                    1562
+                }
+            }
+            1562 => {
+                // 1562: mul #0, #1, rb-9
+                let v = ic.mul(1562, 0, 1)?;
+                let a = ic.rel_addr(1562, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1566);
+                }
+                // 1566: jf #0, #1689
+                1689
+            }
+            1569 => {
+                // 1569: eq rb-5, #21, rb-8
+                let v = if ic.inner[ic.rel_addr(1569, -5)?] == 21 { 1 } else { 0 };
+                let a = ic.rel_addr(1569, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1573);
+                }
+                // 1573: jf rb-8, #1583
+                if ic.inner[ic.rel_addr(1573, -8)?] == 0 {
+                    1583
+                } else {
+                    1576
+                }
+            }
+            1576 => {
+                // 1576: mul #1, #1, rb-9
+                let v = ic.mul(1576, 1, 1)?;
+                let a = ic.rel_addr(1576, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1580);
+                }
+                // 1580: jt #1, #1689
+                1689
+            }
+            1583 => {
+                // 1583: add rb-5, #716, [1589]
+                ic.inner[1589] = ic.add(1583, ic.inner[ic.rel_addr(1583, -5)?], 716)?;
+                // 1587: mul #1, [0], rb-2
+                let v = ic.mul(1587, 1, ic.inner[ic.checked_addr(1587, ic.inner[1589])?])?;
+                let a = ic.rel_addr(1587, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1591);
+                }
+                // 1591: eq rb-4, #1, rb-1
+                let v = if ic.inner[ic.rel_addr(1591, -4)?] == 1 { 1 } else { 0 };
+                let a = ic.rel_addr(1591, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1595);
+                }
+                // 1595: mul rb-2, rb-1, rb-1
+                let v = ic.mul(1595, ic.inner[ic.rel_addr(1595, -2)?], ic.inner[ic.rel_addr(1595, -1)?])?;
+                let a = ic.rel_addr(1595, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1599);
+                }
+                // 1599: jt rb-2, #1613
+                if ic.inner[ic.rel_addr(1599, -2)?] != 0 {
+                    1613
+                } else {
+                    1602
+                }
+            }
+            1602 => {
+                // 1602: mul #1, rb-5, rb+1
+                let v = ic.mul(1602, 1, ic.inner[ic.rel_addr(1602, -5)?])?;
+                let a = ic.rel_addr(1602, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1606);
+                }
+                // 1606: add #0, #1613, rb+0
+                let v = ic.add(1606, 0, 1613)?;
+                let a = ic.rel_addr(1606, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1610);
+                }
+                // 1610: jf #0, #1444
+                1444
+            }
+            1613 => {
+                // 1613: jf rb-1, #1634
+                if ic.inner[ic.rel_addr(1613, -1)?] == 0 {
+                    1634
+                } else {
+                    1616
+                }
+            }
+            1616 => {
+                // 1616: add #0, rb-5, rb+1
+                let v = ic.add(1616, 0, ic.inner[ic.rel_addr(1616, -5)?])?;
+                let a = ic.rel_addr(1616, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1620);
+                }
+                // 1620: add #1627, #0, rb+0
+                let v = ic.add(1620, 1627, 0)?;
+                let a = ic.rel_addr(1620, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1624);
+                }
+                // 1624: jt #1, #1694
+                1694
+            }
+            1627 => {
+                // 1627: jf rb+1, #1634
+                if ic.inner[ic.rel_addr(1627, 1)?] == 0 {
+                    1634
+                } else {
+                    1630
+                }
+            }
+            1630 => {
+                // 1630: mul #2, #1, rb-3
+                let v = ic.mul(1630, 2, 1)?;
+                let a = ic.rel_addr(1630, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1634);
+                }
+                1634
+            }
+            1634 => {
+                // 1634: lt #1, rb-4, rb-8
+                let v = if 1 < ic.inner[ic.rel_addr(1634, -4)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1634, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1638);
+                }
+                // 1638: add rb-1, rb-8, rb-8
+                let v = ic.add(1638, ic.inner[ic.rel_addr(1638, -1)?], ic.inner[ic.rel_addr(1638, -8)?])?;
+                let a = ic.rel_addr(1638, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1642);
+                }
+                // 1642: jf rb-8, #1649
+                if ic.inner[ic.rel_addr(1642, -8)?] == 0 {
+                    1649
+                } else {
+                    1645
+                }
+            }
+            1645 => {
+                // 1645: add rb-5, #1, rb-5
+                let v = ic.add(1645, ic.inner[ic.rel_addr(1645, -5)?], 1)?;
+                let a = ic.rel_addr(1645, -5)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1649);
+                }
+                1649
+            }
+            1649 => {
+                // 1649: jf rb-3, #1663
+                if ic.inner[ic.rel_addr(1649, -3)?] == 0 {
+                    1663
+                } else {
+                    1652
+                }
+            }
+            1652 => {
+                // 1652: add rb-3, #-1, rb-3
+                let v = ic.add(1652, ic.inner[ic.rel_addr(1652, -3)?], -1)?;
+                let a = ic.rel_addr(1652, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1656);
+                }
+                // 1656: add rb-4, #1, rb-4
+                let v = ic.add(1656, ic.inner[ic.rel_addr(1656, -4)?], 1)?;
+                let a = ic.rel_addr(1656, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1660);
+                }
+                // 1660: jf #0, #1667
+                1667
+            }
+            1663 => {
+                // 1663: add rb-4, #-1, rb-4
+                let v = ic.add(1663, ic.inner[ic.rel_addr(1663, -4)?], -1)?;
+                let a = ic.rel_addr(1663, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1667);
+                }
+                1667
+            }
+            1667 => {
+                // 1667: eq rb-4, #0, rb-1
+                let v = if ic.inner[ic.rel_addr(1667, -4)?] == 0 { 1 } else { 0 };
+                let a = ic.rel_addr(1667, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1671);
+                }
+                // 1671: add rb-5, #716, [1676]
+                ic.inner[1676] = ic.add(1671, ic.inner[ic.rel_addr(1671, -5)?], 716)?;
+                // 1675: mul [0], rb-1, rb-1
+                let v = ic.mul(1675, ic.inner[ic.checked_addr(1675, ic.inner[1676])?], ic.inner[ic.rel_addr(1675, -1)?])?;
+                let a = ic.rel_addr(1675, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1679);
+                }
+                // 1679: jf rb-1, #1686
+                if ic.inner[ic.rel_addr(1679, -1)?] == 0 {
+                    1686
+                } else {
+                    1682
+                }
+            }
+            1682 => {
+                // 1682: mul #1, #1, rb-4
+                let v = ic.mul(1682, 1, 1)?;
+                let a = ic.rel_addr(1682, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1686);
+                }
+                1686
+            }
+            1686 => {
+                // 1686: jf #0, #1477
+                1477
+            }
+            1689 => {
+                // 1689: arb #-10
+                ic.set_base(1689, -10)?;
+                // 1691: jf #0, rb+0
+                ic.checked_addr(1691, ic.inner[ic.rel_addr(1691, 0)?])?
+            }
+            1694 => {
+                // 1694: arb #11
+                ic.set_base(1694, 11)?;
+                // 1696: mul #0, #1, rb-6
+                let v = ic.mul(1696, 0, 1)?;
+                let a = ic.rel_addr(1696, -6)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1700);
+                }
+                // 1700: add #0, #0, rb-8
+                let v = ic.add(1700, 0, 0)?;
+                let a = ic.rel_addr(1700, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1704);
+                }
+                // 1704: add #0, #0, rb-7
+                let v = ic.add(1704, 0, 0)?;
+                let a = ic.rel_addr(1704, -7)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1708);
+                }
+                1708
+            }
+            1708 => {
+                // 1708: eq rb-6, [920], rb-9
+                let v = if ic.inner[ic.rel_addr(1708, -6)?] == ic.inner[920] { 1 } else { 0 };
+                let a = ic.rel_addr(1708, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1712);
+                }
+                // 1712: jt rb-9, #1880
+                if ic.inner[ic.rel_addr(1712, -9)?] != 0 {
+                    1880
+                } else {
+                    1715
+                }
+            }
+            1715 => {
+                // 1715: mul rb-6, #3, rb-9
+                let v = ic.mul(1715, ic.inner[ic.rel_addr(1715, -6)?], 3)?;
+                let a = ic.rel_addr(1715, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1719);
+                }
+                // 1719: add rb-9, #921, [1724]
+                ic.inner[1724] = ic.add(1719, ic.inner[ic.rel_addr(1719, -9)?], 921)?;
+                // 1723: mul [0], #1, rb-5
+                let v = ic.mul(1723, ic.inner[ic.checked_addr(1723, ic.inner[1724])?], 1)?;
+                let a = ic.rel_addr(1723, -5)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1727);
+                }
+                // 1727: add [1724], #1, [1733]
+                ic.inner[1733] = ic.add(1727, ic.inner[1724], 1)?;
+                // 1731: mul #1, [0], rb-4
+                let v = ic.mul(1731, 1, ic.inner[ic.checked_addr(1731, ic.inner[1733])?])?;
+                let a = ic.rel_addr(1731, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1735);
+                }
+                // 1735: add rb-4, #0, rb+1
+                let v = ic.add(1735, ic.inner[ic.rel_addr(1735, -4)?], 0)?;
+                let a = ic.rel_addr(1735, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1739);
+                }
+                // 1739: mul #1, #1, rb+2
+                let v = ic.mul(1739, 1, 1)?;
+                let a = ic.rel_addr(1739, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1743);
+                }
+                // 1743: add #0, #9, rb+3
+                let v = ic.add(1743, 0, 9)?;
+                let a = ic.rel_addr(1743, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1747);
+                }
+                // 1747: mul #1, #1754, rb+0
+                let v = ic.mul(1747, 1, 1754)?;
+                let a = ic.rel_addr(1747, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1751);
+                }
+                // 1751: jt #1, #1889
+                1889
+            }
+            1754 => {
+                // 1754: jf rb+1, #1772
+                if ic.inner[ic.rel_addr(1754, 1)?] == 0 {
+                    1772
+                } else {
+                    1757
+                }
+            }
+            1757 => {
+                // 1757: add rb-10, rb-4, [1766]
+                ic.inner[1766] = ic.add(1757, ic.inner[ic.rel_addr(1757, -10)?], ic.inner[ic.rel_addr(1757, -4)?])?;
+                // 1761: add [1766], #716, [1766]
+                ic.inner[1766] = ic.add(1761, ic.inner[1766], 716)?;
+                // 1765: add [0], #0, rb-3
+                let v = ic.add(1765, ic.inner[ic.checked_addr(1765, ic.inner[1766])?], 0)?;
+                let a = ic.rel_addr(1765, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1769);
+                }
+                // 1769: jf #0, #1790
+                1790
+            }
+            1772 => {
+                // 1772: eq rb-4, #-1, rb-9
+                let v = if ic.inner[ic.rel_addr(1772, -4)?] == -1 { 1 } else { 0 };
+                let a = ic.rel_addr(1772, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1776);
+                }
+                // 1776: jf rb-9, #1786
+                if ic.inner[ic.rel_addr(1776, -9)?] == 0 {
+                    1786
+                } else {
+                    1779
+                }
+            }
+            1779 => {
+                // 1779: add #0, rb-8, rb-3
+                let v = ic.add(1779, 0, ic.inner[ic.rel_addr(1779, -8)?])?;
+                let a = ic.rel_addr(1779, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1783);
+                }
+                // 1783: jt #1, #1790
+                1790
+            }
+            1786 => {
+                // 1786: mul #1, rb-7, rb-3
+                let v = ic.mul(1786, 1, ic.inner[ic.rel_addr(1786, -7)?])?;
+                let a = ic.rel_addr(1786, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1790);
+                }
+                1790
+            }
+            1790 => {
+                // 1790: add [1733], #1, [1795]
+                ic.inner[1795] = ic.add(1790, ic.inner[1733], 1)?;
+                // 1794: add [0], #0, rb-2
+                let v = ic.add(1794, ic.inner[ic.checked_addr(1794, ic.inner[1795])?], 0)?;
+                let a = ic.rel_addr(1794, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1798);
+                }
+                // 1798: eq rb-2, #-1, rb-9
+                let v = if ic.inner[ic.rel_addr(1798, -2)?] == -1 { 1 } else { 0 };
+                let a = ic.rel_addr(1798, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1802);
+                }
+                // 1802: jf rb-9, #1812
+                if ic.inner[ic.rel_addr(1802, -9)?] == 0 {
+                    1812
+                } else {
+                    1805
+                }
+            }
+            1805 => {
+                // 1805: add #0, rb-8, rb-1
+                let v = ic.add(1805, 0, ic.inner[ic.rel_addr(1805, -8)?])?;
+                let a = ic.rel_addr(1805, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1809);
+                }
+                // 1809: jf #0, #1816
+                1816
+            }
+            1812 => {
+                // 1812: mul rb-7, #1, rb-1
+                let v = ic.mul(1812, ic.inner[ic.rel_addr(1812, -7)?], 1)?;
+                let a = ic.rel_addr(1812, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1816);
+                }
+                1816
+            }
+            1816 => {
+                // 1816: eq rb-5, #1, rb-9
+                let v = if ic.inner[ic.rel_addr(1816, -5)?] == 1 { 1 } else { 0 };
+                let a = ic.rel_addr(1816, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1820);
+                }
+                // 1820: jt rb-9, #1837
+                if ic.inner[ic.rel_addr(1820, -9)?] != 0 {
+                    1837
+                } else {
+                    1823
+                }
+            }
+            1823 => {
+                // 1823: eq rb-5, #2, rb-9
+                let v = if ic.inner[ic.rel_addr(1823, -5)?] == 2 { 1 } else { 0 };
+                let a = ic.rel_addr(1823, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1827);
+                }
+                // 1827: jt rb-9, #1844
+                if ic.inner[ic.rel_addr(1827, -9)?] != 0 {
+                    1844
+                } else {
+                    1830
+                }
+            }
+            1830 => {
+                // 1830: eq rb-3, #0, rb-1
+                let v = if ic.inner[ic.rel_addr(1830, -3)?] == 0 { 1 } else { 0 };
+                let a = ic.rel_addr(1830, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1834);
+                }
+                // 1834: jf #0, #1855
+                1855
+            }
+            1837 => {
+                // 1837: mul rb-3, rb-1, rb-1
+                let v = ic.mul(1837, ic.inner[ic.rel_addr(1837, -3)?], ic.inner[ic.rel_addr(1837, -1)?])?;
+                let a = ic.rel_addr(1837, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1841);
+                }
+                // 1841: jt #1, #1855
+                1855
+            }
+            1844 => {
+                // 1844: add rb-3, rb-1, rb-1
+                let v = ic.add(1844, ic.inner[ic.rel_addr(1844, -3)?], ic.inner[ic.rel_addr(1844, -1)?])?;
+                let a = ic.rel_addr(1844, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1848);
+                }
+                // 1848: lt #0, rb-1, rb-1
+                let v = if 0 < ic.inner[ic.rel_addr(1848, -1)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1848, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1852);
+                }
+                // 1852: jt #1, #1855
+                1855
+            }
+            1855 => {
+                // 1855: eq rb-2, #-1, rb-9
+                let v = if ic.inner[ic.rel_addr(1855, -2)?] == -1 { 1 } else { 0 };
+                let a = ic.rel_addr(1855, -9)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1859);
+                }
+                // 1859: jf rb-9, #1869
+                if ic.inner[ic.rel_addr(1859, -9)?] == 0 {
+                    1869
+                } else {
+                    1862
+                }
+            }
+            1862 => {
+                // 1862: mul #1, rb-1, rb-8
+                let v = ic.mul(1862, 1, ic.inner[ic.rel_addr(1862, -1)?])?;
+                let a = ic.rel_addr(1862, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1866);
+                }
+                // 1866: jf #0, #1873
+                1873
+            }
+            1869 => {
+                // 1869: mul rb-1, #1, rb-7
+                let v = ic.mul(1869, ic.inner[ic.rel_addr(1869, -1)?], 1)?;
+                let a = ic.rel_addr(1869, -7)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1873);
+                }
+                1873
+            }
+            1873 => {
+                // 1873: add rb-6, #1, rb-6
+                let v = ic.add(1873, ic.inner[ic.rel_addr(1873, -6)?], 1)?;
+                let a = ic.rel_addr(1873, -6)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1877);
+                }
+                // 1877: jt #1, #1708
+                1708
+            }
+            1880 => {
+                // 1880: mul #1, rb-8, rb-10
+                let v = ic.mul(1880, 1, ic.inner[ic.rel_addr(1880, -8)?])?;
+                let a = ic.rel_addr(1880, -10)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1884);
+                }
+                // 1884: arb #-11
+                ic.set_base(1884, -11)?;
+                // 1886: jt #1, rb+0
+                ic.checked_addr(1886, ic.inner[ic.rel_addr(1886, 0)?])?
+            }
+            1889 => {
+                // 1889: arb #7
+                ic.set_base(1889, 7)?;
+                // 1891: lt rb-6, rb-5, rb-3
+                let v = if ic.inner[ic.rel_addr(1891, -6)?] < ic.inner[ic.rel_addr(1891, -5)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1891, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1895);
+                }
+                // 1895: lt rb-4, rb-6, rb-2
+                let v = if ic.inner[ic.rel_addr(1895, -4)?] < ic.inner[ic.rel_addr(1895, -6)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1895, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1899);
+                }
+                // 1899: add rb-3, rb-2, rb-1
+                let v = ic.add(1899, ic.inner[ic.rel_addr(1899, -3)?], ic.inner[ic.rel_addr(1899, -2)?])?;
+                let a = ic.rel_addr(1899, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1903);
+                }
+                // 1903: eq rb-1, #0, rb-6
+                let v = if ic.inner[ic.rel_addr(1903, -1)?] == 0 { 1 } else { 0 };
+                let a = ic.rel_addr(1903, -6)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1907);
+                }
+                // 1907: arb #-7
+                ic.set_base(1907, -7)?;
+                // 1909: jf #0, rb+0
+                ic.checked_addr(1909, ic.inner[ic.rel_addr(1909, 0)?])?
+            }
+            1913 => {
+                // 1913: arb #5
+                ic.set_base(1913, 5)?;
+                // 1915: add rb-2, #0, [1912]
+                ic.inner[1912] = ic.add(1915, ic.inner[ic.rel_addr(1915, -2)?], 0)?;
+                // 1919: lt rb-4, #0, rb-1
+                let v = if ic.inner[ic.rel_addr(1919, -4)?] < 0 { 1 } else { 0 };
+                let a = ic.rel_addr(1919, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1923);
+                }
+                // 1923: jf rb-1, #1930
+                if ic.inner[ic.rel_addr(1923, -1)?] == 0 {
+                    1930
+                } else {
+                    1926
+                }
+            }
+            1926 => {
+                // 1926: add #0, #0, rb-4
+                let v = ic.add(1926, 0, 0)?;
+                let a = ic.rel_addr(1926, -4)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1930);
+                }
+                1930
+            }
+            1930 => {
+                // 1930: add #0, rb-4, rb+1
+                let v = ic.add(1930, 0, ic.inner[ic.rel_addr(1930, -4)?])?;
+                let a = ic.rel_addr(1930, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1934);
+                }
+                // 1934: add rb-3, #0, rb+2
+                let v = ic.add(1934, ic.inner[ic.rel_addr(1934, -3)?], 0)?;
+                let a = ic.rel_addr(1934, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1938);
+                }
+                // 1938: mul #1, #1, rb+3
+                let v = ic.mul(1938, 1, 1)?;
+                let a = ic.rel_addr(1938, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1942);
+                }
+                // 1942: mul #1949, #1, rb+0
+                let v = ic.mul(1942, 1949, 1)?;
+                let a = ic.rel_addr(1942, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1946);
+                }
+                // 1946: jt #1, #1954
+                1954
+            }
+            1949 => {
+                // 1949: arb #-5
+                ic.set_base(1949, -5)?;
+                // 1951: jf #0, rb+0
+                ic.checked_addr(1951, ic.inner[ic.rel_addr(1951, 0)?])?
+            }
+            1954 => {
+                // 1954: arb #6
+                ic.set_base(1954, 6)?;
+                // 1956: lt rb-4, #1, rb-1
+                let v = if ic.inner[ic.rel_addr(1956, -4)?] < 1 { 1 } else { 0 };
+                let a = ic.rel_addr(1956, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1960);
+                }
+                // 1960: jf rb-1, #1977
+                if ic.inner[ic.rel_addr(1960, -1)?] == 0 {
+                    1977
+                } else {
+                    1963
+                }
+            }
+            1963 => {
+                // 1963: lt rb-5, rb-3, rb-1
+                let v = if ic.inner[ic.rel_addr(1963, -5)?] < ic.inner[ic.rel_addr(1963, -3)?] { 1 } else { 0 };
+                let a = ic.rel_addr(1963, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1967);
+                }
+                // 1967: jf rb-1, #1977
+                if ic.inner[ic.rel_addr(1967, -1)?] == 0 {
+                    1977
+                } else {
+                    1970
+                }
+            }
+            1970 => {
+                // 1970: mul #1, rb-5, rb-5
+                let v = ic.mul(1970, 1, ic.inner[ic.rel_addr(1970, -5)?])?;
+                let a = ic.rel_addr(1970, -5)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1974);
+                }
+                // 1974: jt #1, #2045
+                2045
+            }
+            1977 => {
+                // 1977: mul #1, rb-5, rb+1
+                let v = ic.mul(1977, 1, ic.inner[ic.rel_addr(1977, -5)?])?;
+                let a = ic.rel_addr(1977, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1981);
+                }
+                // 1981: add rb-4, #-1, rb+2
+                let v = ic.add(1981, ic.inner[ic.rel_addr(1981, -4)?], -1)?;
+                let a = ic.rel_addr(1981, 2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1985);
+                }
+                // 1985: mul rb-3, #2, rb+3
+                let v = ic.mul(1985, ic.inner[ic.rel_addr(1985, -3)?], 2)?;
+                let a = ic.rel_addr(1985, 3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1989);
+                }
+                // 1989: mul #1, #1996, rb+0
+                let v = ic.mul(1989, 1, 1996)?;
+                let a = ic.rel_addr(1989, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(1993);
+                }
+                // 1993: jf #0, #1954
+                1954
+            }
+            1996 => {
+                // 1996: add #0, rb+1, rb-5
+                let v = ic.add(1996, 0, ic.inner[ic.rel_addr(1996, 1)?])?;
+                let a = ic.rel_addr(1996, -5)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2000);
+                }
+                // 2000: mul #1, #1, rb-2
+                let v = ic.mul(2000, 1, 1)?;
+                let a = ic.rel_addr(2000, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2004);
+                }
+                // 2004: lt rb-5, rb-3, rb-1
+                let v = if ic.inner[ic.rel_addr(2004, -5)?] < ic.inner[ic.rel_addr(2004, -3)?] { 1 } else { 0 };
+                let a = ic.rel_addr(2004, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2008);
+                }
+                // 2008: jf rb-1, #2015
+                if ic.inner[ic.rel_addr(2008, -1)?] == 0 {
+                    2015
+                } else {
+                    2011
+                }
+            }
+            2011 => {
+                // 2011: mul #0, #1, rb-2
+                let v = ic.mul(2011, 0, 1)?;
+                let a = ic.rel_addr(2011, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2015);
+                }
+                2015
+            }
+            2015 => {
+                // 2015: mul rb-3, rb-2, rb-3
+                let v = ic.mul(2015, ic.inner[ic.rel_addr(2015, -3)?], ic.inner[ic.rel_addr(2015, -2)?])?;
+                let a = ic.rel_addr(2015, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2019);
+                }
+                // 2019: lt #0, rb-4, rb-1
+                let v = if 0 < ic.inner[ic.rel_addr(2019, -4)?] { 1 } else { 0 };
+                let a = ic.rel_addr(2019, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2023);
+                }
+                // 2023: jf rb-1, #2037
+                if ic.inner[ic.rel_addr(2023, -1)?] == 0 {
+                    2037
+                } else {
+                    2026
+                }
+            }
+            2026 => {
+                // 2026: mul #1, rb-2, rb+1
+                let v = ic.mul(2026, 1, ic.inner[ic.rel_addr(2026, -2)?])?;
+                let a = ic.rel_addr(2026, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2030);
+                }
+                // 2030: add #0, #2037, rb+0
+                let v = ic.add(2030, 0, 2037)?;
+                let a = ic.rel_addr(2030, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2034);
+                }
+                // 2034: jf #0, [1912]
+                ic.checked_addr(2034, ic.inner[1912])?
+            }
+            2037 => {
+                // 2037: mul rb-3, #-1, rb-3
+                let v = ic.mul(2037, ic.inner[ic.rel_addr(2037, -3)?], -1)?;
+                let a = ic.rel_addr(2037, -3)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2041);
+                }
+                // 2041: add rb-5, rb-3, rb-5
+                let v = ic.add(2041, ic.inner[ic.rel_addr(2041, -5)?], ic.inner[ic.rel_addr(2041, -3)?])?;
+                let a = ic.rel_addr(2041, -5)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(2045);
+                }
+                2045
+            }
+            2045 => {
+                // 2045: arb #-6
+                ic.set_base(2045, -6)?;
+                // 2047: jf #0, rb+0
+                ic.checked_addr(2047, ic.inner[ic.rel_addr(2047, 0)?])?
+            }
+            _ => return ic.resume_at(pc),
+        };
+    }
+}
+
+fn is_code(addr: usize) -> bool {
+    matches!(addr, 1..=528 | 530..=536 | 538..=544 | 546..=592 | 594..=705 | 707..=715 | 1263..=1384 | 1386..=1407 | 1409..=1438 | 1444..=1514 | 1516..=1588 | 1590..=1675 | 1677..=1723 | 1725..=1732 | 1734..=1765 | 1767..=1794 | 1796..=1911 | 1913..=2049)
+}
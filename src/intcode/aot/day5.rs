@@ -0,0 +1,730 @@
+//! `input/day5`, compiled by `transpile`.
+//! Regenerate this file rather than editing it.
+use crate::intcode::intcode_io::{Input, Output};
+use crate::intcode::{IntCode, Result};
+
+pub fn run<IO: Input + Output>(ic: &mut IntCode<IO>) -> Result<()> {
+    if ic.halt {
+        return Ok(());
+    }
+    if ic.watched() {
+        return ic.resume_at(ic.pc);
+    }
+    let mut pc = ic.pc;
+    loop {
+        pc = match pc {
+            0 => {
+                // 0: in [225]
+                match ic.inner[0] {
+                    1 => {
+                        // add [225], [1], [225]
+                        ic.inner[225] = ic.add(0, ic.inner[225], ic.inner[1])?;
+                        4
+                    }
+                    2 => {
+                        // mul [225], [1], [225]
+                        ic.inner[225] = ic.mul(0, ic.inner[225], ic.inner[1])?;
+                        4
+                    }
+                    3 => {
+                        // in [225]
+                        ic.inner[225] = ic.read_input(0)?;
+                        2
+                    }
+                    4 => {
+                        // out [225]
+                        ic.write_output(0, ic.inner[225])?;
+                        2
+                    }
+                    5 => {
+                        // jt [225], [1]
+                        if ic.inner[225] != 0 {
+                            ic.checked_addr(0, ic.inner[1])?
+                        } else {
+                            3
+                        }
+                    }
+                    6 => {
+                        // jf [225], [1]
+                        if ic.inner[225] == 0 {
+                            ic.checked_addr(0, ic.inner[1])?
+                        } else {
+                            3
+                        }
+                    }
+                    7 => {
+                        // lt [225], [1], [225]
+                        ic.inner[225] = if ic.inner[225] < ic.inner[1] { 1 } else { 0 };
+                        4
+                    }
+                    8 => {
+                        // eq [225], [1], [225]
+                        ic.inner[225] = if ic.inner[225] == ic.inner[1] { 1 } else { 0 };
+                        4
+                    }
+                    9 => {
+                        // arb [225]
+                        ic.set_base(0, ic.inner[225])?;
+                        2
+                    }
+                    99 => {
+                        // hlt
+                        return ic.halt_at(0);
+                    }
+                    _ => return ic.resume_at(0),
+                }
+            }
+            2 => {
+                // 2: add [225], [6], [6]
+                ic.inner[6] = ic.add(2, ic.inner[225], ic.inner[6])?;
+                // 6: .data 1100
+                match ic.inner[6] {
+                    1101 => {
+                        // add #1, #238, [225]
+                        ic.inner[225] = ic.add(6, 1, 238)?;
+                        10
+                    }
+                    1102 => {
+                        // mul #1, #238, [225]
+                        ic.inner[225] = ic.mul(6, 1, 238)?;
+                        10
+                    }
+                    1105 => {
+                        // jt #1, #238
+                        238
+                    }
+                    1106 => {
+                        // jf #1, #238
+                        9
+                    }
+                    1107 => {
+                        // lt #1, #238, [225]
+                        ic.inner[225] = if 1 < 238 { 1 } else { 0 };
+                        10
+                    }
+                    1108 => {
+                        // eq #1, #238, [225]
+                        ic.inner[225] = if 1 == 238 { 1 } else { 0 };
+                        10
+                    }
+                    _ => return ic.resume_at(6),
+                }
+            }
+            10 => {
+                // 10: out #0
+                ic.write_output(10, 0)?;
+                // 12: add #81, #30, [225]
+                ic.inner[225] = ic.add(12, 81, 30)?;
+                // 16: mul #9, #63, [225]
+                ic.inner[225] = ic.mul(16, 9, 63)?;
+                // 20: add [92], #45, [224]
+                ic.inner[224] = ic.add(20, ic.inner[92], 45)?;
+                // 24: add #-83, [224], [224]
+                ic.inner[224] = ic.add(24, -83, ic.inner[224])?;
+                // 28: out [224]
+                ic.write_output(28, ic.inner[224])?;
+                // 30: mul #8, [223], [223]
+                ic.inner[223] = ic.mul(30, 8, ic.inner[223])?;
+                // 34: add #2, [224], [224]
+                ic.inner[224] = ic.add(34, 2, ic.inner[224])?;
+                // 38: add [224], [223], [223]
+                ic.inner[223] = ic.add(38, ic.inner[224], ic.inner[223])?;
+                // 42: mul #41, #38, [225]
+                ic.inner[225] = ic.mul(42, 41, 38)?;
+                // 46: mul [165], #73, [224]
+                ic.inner[224] = ic.mul(46, ic.inner[165], 73)?;
+                // 50: add #-2920, [224], [224]
+                ic.inner[224] = ic.add(50, -2920, ic.inner[224])?;
+                // 54: out [224]
+                ic.write_output(54, ic.inner[224])?;
+                // 56: mul #8, [223], [223]
+                ic.inner[223] = ic.mul(56, 8, ic.inner[223])?;
+                // 60: add #4, [224], [224]
+                ic.inner[224] = ic.add(60, 4, ic.inner[224])?;
+                // 64: add [223], [224], [223]
+                ic.inner[223] = ic.add(64, ic.inner[223], ic.inner[224])?;
+                // 68: add #18, #14, [224]
+                ic.inner[224] = ic.add(68, 18, 14)?;
+                // 72: add [224], #-32, [224]
+                ic.inner[224] = ic.add(72, ic.inner[224], -32)?;
+                // 76: out [224]
+                ic.write_output(76, ic.inner[224])?;
+                // 78: mul [223], #8, [223]
+                ic.inner[223] = ic.mul(78, ic.inner[223], 8)?;
+                // 82: add #3, [224], [224]
+                ic.inner[224] = ic.add(82, 3, ic.inner[224])?;
+                // 86: add [224], [223], [223]
+                ic.inner[223] = ic.add(86, ic.inner[224], ic.inner[223])?;
+                // 90: add #67, #38, [225]
+                ic.inner[225] = ic.add(90, 67, 38)?;
+                // 94: mul #54, #62, [224]
+                ic.inner[224] = ic.mul(94, 54, 62)?;
+                // 98: add [224], #-3348, [224]
+                ic.inner[224] = ic.add(98, ic.inner[224], -3348)?;
+                // 102: out [224]
+                ic.write_output(102, ic.inner[224])?;
+                // 104: mul [223], #8, [223]
+                ic.inner[223] = ic.mul(104, ic.inner[223], 8)?;
+                // 108: add [224], #1, [224]
+                ic.inner[224] = ic.add(108, ic.inner[224], 1)?;
+                // 112: add [224], [223], [223]
+                ic.inner[223] = ic.add(112, ic.inner[224], ic.inner[223])?;
+                // 116: add [161], [169], [224]
+                ic.inner[224] = ic.add(116, ic.inner[161], ic.inner[169])?;
+                // 120: add #-62, [224], [224]
+                ic.inner[224] = ic.add(120, -62, ic.inner[224])?;
+                // 124: out [224]
+                ic.write_output(124, ic.inner[224])?;
+                // 126: mul [223], #8, [223]
+                ic.inner[223] = ic.mul(126, ic.inner[223], 8)?;
+                // 130: add #1, [224], [224]
+                ic.inner[224] = ic.add(130, 1, ic.inner[224])?;
+                // 134: add [223], [224], [223]
+                ic.inner[223] = ic.add(134, ic.inner[223], ic.inner[224])?;
+                // 138: mul [14], [18], [224]
+                ic.inner[224] = ic.mul(138, ic.inner[14], ic.inner[18])?;
+                // 142: add [224], #-1890, [224]
+                ic.inner[224] = ic.add(142, ic.inner[224], -1890)?;
+                // 146: out [224]
+                ic.write_output(146, ic.inner[224])?;
+                // 148: mul [223], #8, [223]
+                ic.inner[223] = ic.mul(148, ic.inner[223], 8)?;
+                // 152: add #3, [224], [224]
+                ic.inner[224] = ic.add(152, 3, ic.inner[224])?;
+                // 156: add [223], [224], [223]
+                ic.inner[223] = ic.add(156, ic.inner[223], ic.inner[224])?;
+                // 160: add #20, #25, [225]
+                ic.inner[225] = ic.add(160, 20, 25)?;
+                // 164: mul #40, #11, [225]
+                ic.inner[225] = ic.mul(164, 40, 11)?;
+                // 168: mul #42, #58, [225]
+                ic.inner[225] = ic.mul(168, 42, 58)?;
+                // 172: add #76, [217], [224]
+                ic.inner[224] = ic.add(172, 76, ic.inner[217])?;
+                // 176: add #-153, [224], [224]
+                ic.inner[224] = ic.add(176, -153, ic.inner[224])?;
+                // 180: out [224]
+                ic.write_output(180, ic.inner[224])?;
+                // 182: mul #8, [223], [223]
+                ic.inner[223] = ic.mul(182, 8, ic.inner[223])?;
+                // 186: add [224], #5, [224]
+                ic.inner[224] = ic.add(186, ic.inner[224], 5)?;
+                // 190: add [224], [223], [223]
+                ic.inner[223] = ic.add(190, ic.inner[224], ic.inner[223])?;
+                // 194: mul #11, [43], [224]
+                ic.inner[224] = ic.mul(194, 11, ic.inner[43])?;
+                // 198: add [224], #-451, [224]
+                ic.inner[224] = ic.add(198, ic.inner[224], -451)?;
+                // 202: out [224]
+                ic.write_output(202, ic.inner[224])?;
+                // 204: mul [223], #8, [223]
+                ic.inner[223] = ic.mul(204, ic.inner[223], 8)?;
+                // 208: add #6, [224], [224]
+                ic.inner[224] = ic.add(208, 6, ic.inner[224])?;
+                // 212: add [223], [224], [223]
+                ic.inner[223] = ic.add(212, ic.inner[223], ic.inner[224])?;
+                // 216: mul #77, #23, [225]
+                ic.inner[225] = ic.mul(216, 77, 23)?;
+                // 220: out [223]
+                ic.write_output(220, ic.inner[223])?;
+                // 222: hlt
+                return ic.halt_at(222);
+            }
+            238 => {
+                // 238: jt #0, #99999
+                241
+            }
+            241 => {
+                // 241: jt #227, #247
+                247
+            }
+            247 => {
+                // 247: jt [227], #99999
+                if ic.inner[227] != 0 {
+                    99999
+                } else {
+                    250
+                }
+            }
+            250 => {
+                // 250: jt [0], #256
+                if ic.inner[0] != 0 {
+                    256
+                } else {
+                    253
+                }
+            }
+            253 => {
+                // 253: jt #1, #99999
+                99999
+            }
+            256 => {
+                // 256: jf #227, #99999
+                259
+            }
+            259 => {
+                // 259: jf #0, #265
+                265
+            }
+            265 => {
+                // 265: jf [0], #99999
+                if ic.inner[0] == 0 {
+                    99999
+                } else {
+                    268
+                }
+            }
+            268 => {
+                // 268: jf [227], #274
+                if ic.inner[227] == 0 {
+                    274
+                } else {
+                    271
+                }
+            }
+            271 => {
+                // 271: jt #1, #99999
+                99999
+            }
+            274 => {
+                // 274: jt #1, #280
+                280
+            }
+            280 => {
+                // 280: add [225], [225], [225]
+                ic.inner[225] = ic.add(280, ic.inner[225], ic.inner[225])?;
+                // 284: add #294, #0, [0]
+                ic.inner[0] = ic.add(284, 294, 0)?;
+                // 288: jt #1, [0]
+                ic.checked_addr(288, ic.inner[0])?
+            }
+            294 => {
+                // 294: jf #0, #300
+                300
+            }
+            300 => {
+                // 300: add [225], [225], [225]
+                ic.inner[225] = ic.add(300, ic.inner[225], ic.inner[225])?;
+                // 304: add #314, #0, [0]
+                ic.inner[0] = ic.add(304, 314, 0)?;
+                // 308: jf #0, [0]
+                ic.checked_addr(308, ic.inner[0])?
+            }
+            314 => {
+                // 314: eq [226], [677], [224]
+                ic.inner[224] = if ic.inner[226] == ic.inner[677] { 1 } else { 0 };
+                // 318: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(318, ic.inner[223], 2)?;
+                // 322: jf [224], #329
+                if ic.inner[224] == 0 {
+                    329
+                } else {
+                    325
+                }
+            }
+            325 => {
+                // 325: add [223], #1, [223]
+                ic.inner[223] = ic.add(325, ic.inner[223], 1)?;
+                329
+            }
+            329 => {
+                // 329: lt [226], [226], [224]
+                ic.inner[224] = if ic.inner[226] < ic.inner[226] { 1 } else { 0 };
+                // 333: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(333, 2, ic.inner[223])?;
+                // 337: jf [224], #344
+                if ic.inner[224] == 0 {
+                    344
+                } else {
+                    340
+                }
+            }
+            340 => {
+                // 340: add #1, [223], [223]
+                ic.inner[223] = ic.add(340, 1, ic.inner[223])?;
+                344
+            }
+            344 => {
+                // 344: eq #677, [677], [224]
+                ic.inner[224] = if 677 == ic.inner[677] { 1 } else { 0 };
+                // 348: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(348, ic.inner[223], 2)?;
+                // 352: jf [224], #359
+                if ic.inner[224] == 0 {
+                    359
+                } else {
+                    355
+                }
+            }
+            355 => {
+                // 355: add #1, [223], [223]
+                ic.inner[223] = ic.add(355, 1, ic.inner[223])?;
+                359
+            }
+            359 => {
+                // 359: lt #226, #677, [224]
+                ic.inner[224] = if 226 < 677 { 1 } else { 0 };
+                // 363: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(363, ic.inner[223], 2)?;
+                // 367: jt [224], #374
+                if ic.inner[224] != 0 {
+                    374
+                } else {
+                    370
+                }
+            }
+            370 => {
+                // 370: add #1, [223], [223]
+                ic.inner[223] = ic.add(370, 1, ic.inner[223])?;
+                374
+            }
+            374 => {
+                // 374: eq [677], #226, [224]
+                ic.inner[224] = if ic.inner[677] == 226 { 1 } else { 0 };
+                // 378: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(378, ic.inner[223], 2)?;
+                // 382: jt [224], #389
+                if ic.inner[224] != 0 {
+                    389
+                } else {
+                    385
+                }
+            }
+            385 => {
+                // 385: add #1, [223], [223]
+                ic.inner[223] = ic.add(385, 1, ic.inner[223])?;
+                389
+            }
+            389 => {
+                // 389: lt [677], #226, [224]
+                ic.inner[224] = if ic.inner[677] < 226 { 1 } else { 0 };
+                // 393: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(393, ic.inner[223], 2)?;
+                // 397: jt [224], #404
+                if ic.inner[224] != 0 {
+                    404
+                } else {
+                    400
+                }
+            }
+            400 => {
+                // 400: add [223], #1, [223]
+                ic.inner[223] = ic.add(400, ic.inner[223], 1)?;
+                404
+            }
+            404 => {
+                // 404: lt #677, #226, [224]
+                ic.inner[224] = if 677 < 226 { 1 } else { 0 };
+                // 408: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(408, ic.inner[223], 2)?;
+                // 412: jt [224], #419
+                if ic.inner[224] != 0 {
+                    419
+                } else {
+                    415
+                }
+            }
+            415 => {
+                // 415: add [223], #1, [223]
+                ic.inner[223] = ic.add(415, ic.inner[223], 1)?;
+                419
+            }
+            419 => {
+                // 419: eq #677, [226], [224]
+                ic.inner[224] = if 677 == ic.inner[226] { 1 } else { 0 };
+                // 423: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(423, 2, ic.inner[223])?;
+                // 427: jf [224], #434
+                if ic.inner[224] == 0 {
+                    434
+                } else {
+                    430
+                }
+            }
+            430 => {
+                // 430: add [223], #1, [223]
+                ic.inner[223] = ic.add(430, ic.inner[223], 1)?;
+                434
+            }
+            434 => {
+                // 434: lt [226], [677], [224]
+                ic.inner[224] = if ic.inner[226] < ic.inner[677] { 1 } else { 0 };
+                // 438: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(438, 2, ic.inner[223])?;
+                // 442: jt [224], #449
+                if ic.inner[224] != 0 {
+                    449
+                } else {
+                    445
+                }
+            }
+            445 => {
+                // 445: add [223], #1, [223]
+                ic.inner[223] = ic.add(445, ic.inner[223], 1)?;
+                449
+            }
+            449 => {
+                // 449: lt #226, [226], [224]
+                ic.inner[224] = if 226 < ic.inner[226] { 1 } else { 0 };
+                // 453: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(453, 2, ic.inner[223])?;
+                // 457: jf [224], #464
+                if ic.inner[224] == 0 {
+                    464
+                } else {
+                    460
+                }
+            }
+            460 => {
+                // 460: add #1, [223], [223]
+                ic.inner[223] = ic.add(460, 1, ic.inner[223])?;
+                464
+            }
+            464 => {
+                // 464: lt #677, [226], [224]
+                ic.inner[224] = if 677 < ic.inner[226] { 1 } else { 0 };
+                // 468: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(468, 2, ic.inner[223])?;
+                // 472: jf [224], #479
+                if ic.inner[224] == 0 {
+                    479
+                } else {
+                    475
+                }
+            }
+            475 => {
+                // 475: add #1, [223], [223]
+                ic.inner[223] = ic.add(475, 1, ic.inner[223])?;
+                479
+            }
+            479 => {
+                // 479: lt [677], #677, [224]
+                ic.inner[224] = if ic.inner[677] < 677 { 1 } else { 0 };
+                // 483: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(483, ic.inner[223], 2)?;
+                // 487: jf [224], #494
+                if ic.inner[224] == 0 {
+                    494
+                } else {
+                    490
+                }
+            }
+            490 => {
+                // 490: add [223], #1, [223]
+                ic.inner[223] = ic.add(490, ic.inner[223], 1)?;
+                494
+            }
+            494 => {
+                // 494: eq [226], #226, [224]
+                ic.inner[224] = if ic.inner[226] == 226 { 1 } else { 0 };
+                // 498: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(498, ic.inner[223], 2)?;
+                // 502: jf [224], #509
+                if ic.inner[224] == 0 {
+                    509
+                } else {
+                    505
+                }
+            }
+            505 => {
+                // 505: add #1, [223], [223]
+                ic.inner[223] = ic.add(505, 1, ic.inner[223])?;
+                509
+            }
+            509 => {
+                // 509: lt [677], [226], [224]
+                ic.inner[224] = if ic.inner[677] < ic.inner[226] { 1 } else { 0 };
+                // 513: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(513, ic.inner[223], 2)?;
+                // 517: jf [224], #524
+                if ic.inner[224] == 0 {
+                    524
+                } else {
+                    520
+                }
+            }
+            520 => {
+                // 520: add [223], #1, [223]
+                ic.inner[223] = ic.add(520, ic.inner[223], 1)?;
+                524
+            }
+            524 => {
+                // 524: lt [226], #226, [224]
+                ic.inner[224] = if ic.inner[226] < 226 { 1 } else { 0 };
+                // 528: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(528, 2, ic.inner[223])?;
+                // 532: jf [224], #539
+                if ic.inner[224] == 0 {
+                    539
+                } else {
+                    535
+                }
+            }
+            535 => {
+                // 535: add #1, [223], [223]
+                ic.inner[223] = ic.add(535, 1, ic.inner[223])?;
+                539
+            }
+            539 => {
+                // 539: eq [677], [226], [224]
+                ic.inner[224] = if ic.inner[677] == ic.inner[226] { 1 } else { 0 };
+                // 543: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(543, ic.inner[223], 2)?;
+                // 547: jf [224], #554
+                if ic.inner[224] == 0 {
+                    554
+                } else {
+                    550
+                }
+            }
+            550 => {
+                // 550: add #1, [223], [223]
+                ic.inner[223] = ic.add(550, 1, ic.inner[223])?;
+                554
+            }
+            554 => {
+                // 554: eq [677], #677, [224]
+                ic.inner[224] = if ic.inner[677] == 677 { 1 } else { 0 };
+                // 558: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(558, 2, ic.inner[223])?;
+                // 562: jf [224], #569
+                if ic.inner[224] == 0 {
+                    569
+                } else {
+                    565
+                }
+            }
+            565 => {
+                // 565: add #1, [223], [223]
+                ic.inner[223] = ic.add(565, 1, ic.inner[223])?;
+                569
+            }
+            569 => {
+                // 569: eq #677, #226, [224]
+                ic.inner[224] = if 677 == 226 { 1 } else { 0 };
+                // 573: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(573, 2, ic.inner[223])?;
+                // 577: jt [224], #584
+                if ic.inner[224] != 0 {
+                    584
+                } else {
+                    580
+                }
+            }
+            580 => {
+                // 580: add #1, [223], [223]
+                ic.inner[223] = ic.add(580, 1, ic.inner[223])?;
+                584
+            }
+            584 => {
+                // 584: lt #677, [677], [224]
+                ic.inner[224] = if 677 < ic.inner[677] { 1 } else { 0 };
+                // 588: mul #2, [223], [223]
+                ic.inner[223] = ic.mul(588, 2, ic.inner[223])?;
+                // 592: jf [224], #599
+                if ic.inner[224] == 0 {
+                    599
+                } else {
+                    595
+                }
+            }
+            595 => {
+                // 595: add [223], #1, [223]
+                ic.inner[223] = ic.add(595, ic.inner[223], 1)?;
+                599
+            }
+            599 => {
+                // 599: eq #677, #677, [224]
+                ic.inner[224] = if 677 == 677 { 1 } else { 0 };
+                // 603: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(603, ic.inner[223], 2)?;
+                // 607: jf [224], #614
+                if ic.inner[224] == 0 {
+                    614
+                } else {
+                    610
+                }
+            }
+            610 => {
+                // 610: add [223], #1, [223]
+                ic.inner[223] = ic.add(610, ic.inner[223], 1)?;
+                614
+            }
+            614 => {
+                // 614: lt #677, #677, [224]
+                ic.inner[224] = if 677 < 677 { 1 } else { 0 };
+                // 618: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(618, ic.inner[223], 2)?;
+                // 622: jt [224], #629
+                if ic.inner[224] != 0 {
+                    629
+                } else {
+                    625
+                }
+            }
+            625 => {
+                // 625: add [223], #1, [223]
+                ic.inner[223] = ic.add(625, ic.inner[223], 1)?;
+                629
+            }
+            629 => {
+                // 629: eq #226, [226], [224]
+                ic.inner[224] = if 226 == ic.inner[226] { 1 } else { 0 };
+                // 633: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(633, ic.inner[223], 2)?;
+                // 637: jt [224], #644
+                if ic.inner[224] != 0 {
+                    644
+                } else {
+                    640
+                }
+            }
+            640 => {
+                // 640: add #1, [223], [223]
+                ic.inner[223] = ic.add(640, 1, ic.inner[223])?;
+                644
+            }
+            644 => {
+                // 644: eq [226], [226], [224]
+                ic.inner[224] = if ic.inner[226] == ic.inner[226] { 1 } else { 0 };
+                // 648: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(648, ic.inner[223], 2)?;
+                // 652: jt [224], #659
+                if ic.inner[224] != 0 {
+                    659
+                } else {
+                    655
+                }
+            }
+            655 => {
+                // 655: add #1, [223], [223]
+                ic.inner[223] = ic.add(655, 1, ic.inner[223])?;
+                659
+            }
+            659 => {
+                // 659: eq #226, #677, [224]
+                ic.inner[224] = if 226 == 677 { 1 } else { 0 };
+                // 663: mul [223], #2, [223]
+                ic.inner[223] = ic.mul(663, ic.inner[223], 2)?;
+                // 667: jf [224], #674
+                if ic.inner[224] == 0 {
+                    674
+                } else {
+                    670
+                }
+            }
+            670 => {
+                // 670: add #1, [223], [223]
+                ic.inner[223] = ic.add(670, 1, ic.inner[223])?;
+                674
+            }
+            674 => {
+                // 674: out [223]
+                ic.write_output(674, ic.inner[223])?;
+                // 676: hlt
+                return ic.halt_at(676);
+            }
+            _ => return ic.resume_at(pc),
+        };
+    }
+}
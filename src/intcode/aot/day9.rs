@@ -0,0 +1,1096 @@
+//! `input/day9`, compiled by `transpile`.
+//! Regenerate this file rather than editing it.
+use crate::intcode::intcode_io::{Input, Output};
+use crate::intcode::{IntCode, Result};
+
+pub fn run<IO: Input + Output>(ic: &mut IntCode<IO>) -> Result<()> {
+    if ic.halt {
+        return Ok(());
+    }
+    if ic.watched() {
+        return ic.resume_at(ic.pc);
+    }
+    let mut pc = ic.pc;
+    loop {
+        pc = match pc {
+            0 => {
+                // 0: mul #34463338, #34463338, [63]
+                ic.inner[63] = ic.mul(0, 34463338, 34463338)?;
+                // 4: lt [63], #34463338, [63]
+                ic.inner[63] = if ic.inner[63] < 34463338 { 1 } else { 0 };
+                // 8: jt [63], #53
+                if ic.inner[63] != 0 {
+                    53
+                } else {
+                    11
+                }
+            }
+            11 => {
+                // 11: mul #3, #1, [1000]
+                ic.inner[1000] = ic.mul(11, 3, 1)?;
+                // 15: arb #988
+                ic.set_base(15, 988)?;
+                // 17: arb rb+12
+                ic.set_base(17, ic.inner[ic.rel_addr(17, 12)?])?;
+                // 19: arb [1000]
+                ic.set_base(19, ic.inner[1000])?;
+                // 21: arb rb+6
+                ic.set_base(21, ic.inner[ic.rel_addr(21, 6)?])?;
+                // 23: arb rb+3
+                ic.set_base(23, ic.inner[ic.rel_addr(23, 3)?])?;
+                // 25: in rb+0
+                let v = ic.read_input(25)?;
+                let a = ic.rel_addr(25, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(27);
+                }
+                // 27: eq [1000], #1, [63]
+                ic.inner[63] = if ic.inner[1000] == 1 { 1 } else { 0 };
+                // 31: jt [63], #65
+                if ic.inner[63] != 0 {
+                    65
+                } else {
+                    34
+                }
+            }
+            34 => {
+                // 34: eq [1000], #2, [63]
+                ic.inner[63] = if ic.inner[1000] == 2 { 1 } else { 0 };
+                // 38: jt [63], #904
+                if ic.inner[63] != 0 {
+                    904
+                } else {
+                    41
+                }
+            }
+            41 => {
+                // 41: eq [1000], #0, [63]
+                ic.inner[63] = if ic.inner[1000] == 0 { 1 } else { 0 };
+                // 45: jt [63], #58
+                if ic.inner[63] != 0 {
+                    58
+                } else {
+                    48
+                }
+            }
+            48 => {
+                // 48: out [25]
+                ic.write_output(48, ic.inner[25])?;
+                // 50: out #0
+                ic.write_output(50, 0)?;
+                // 52: hlt
+                return ic.halt_at(52);
+            }
+            53 => {
+                // 53: out [0]
+                ic.write_output(53, ic.inner[0])?;
+                // 55: out #0
+                ic.write_output(55, 0)?;
+                // 57: hlt
+                return ic.halt_at(57);
+            }
+            58 => {
+                // 58: out [17]
+                ic.write_output(58, ic.inner[17])?;
+                // 60: out #0
+                ic.write_output(60, 0)?;
+                // 62: hlt
+                return ic.halt_at(62);
+            }
+            65 => {
+                // 65: add #0, #33, [1017]
+                ic.inner[1017] = ic.add(65, 0, 33)?;
+                // 69: add #24, #0, [1014]
+                ic.inner[1014] = ic.add(69, 24, 0)?;
+                // 73: add #519, #0, [1028]
+                ic.inner[1028] = ic.add(73, 519, 0)?;
+                // 77: mul #34, #1, [1004]
+                ic.inner[1004] = ic.mul(77, 34, 1)?;
+                // 81: add #0, #31, [1007]
+                ic.inner[1007] = ic.add(81, 0, 31)?;
+                // 85: add #0, #844, [1025]
+                ic.inner[1025] = ic.add(85, 0, 844)?;
+                // 89: mul #0, #1, [1020]
+                ic.inner[1020] = ic.mul(89, 0, 1)?;
+                // 93: mul #38, #1, [1003]
+                ic.inner[1003] = ic.mul(93, 38, 1)?;
+                // 97: mul #39, #1, [1008]
+                ic.inner[1008] = ic.mul(97, 39, 1)?;
+                // 101: mul #849, #1, [1024]
+                ic.inner[1024] = ic.mul(101, 849, 1)?;
+                // 105: add #0, #22, [1001]
+                ic.inner[1001] = ic.add(105, 0, 22)?;
+                // 109: mul #25, #1, [1009]
+                ic.inner[1009] = ic.mul(109, 25, 1)?;
+                // 113: add #1, #0, [1021]
+                ic.inner[1021] = ic.add(113, 1, 0)?;
+                // 117: add #0, #407, [1022]
+                ic.inner[1022] = ic.add(117, 0, 407)?;
+                // 121: add #404, #0, [1023]
+                ic.inner[1023] = ic.add(121, 404, 0)?;
+                // 125: add #0, #35, [1013]
+                ic.inner[1013] = ic.add(125, 0, 35)?;
+                // 129: add #27, #0, [1011]
+                ic.inner[1011] = ic.add(129, 27, 0)?;
+                // 133: add #0, #37, [1016]
+                ic.inner[1016] = ic.add(133, 0, 37)?;
+                // 137: mul #1, #26, [1019]
+                ic.inner[1019] = ic.mul(137, 1, 26)?;
+                // 141: mul #28, #1, [1015]
+                ic.inner[1015] = ic.mul(141, 28, 1)?;
+                // 145: add #0, #30, [1000]
+                ic.inner[1000] = ic.add(145, 0, 30)?;
+                // 149: mul #1, #36, [1005]
+                ic.inner[1005] = ic.mul(149, 1, 36)?;
+                // 153: add #0, #29, [1002]
+                ic.inner[1002] = ic.add(153, 0, 29)?;
+                // 157: add #23, #0, [1012]
+                ic.inner[1012] = ic.add(157, 23, 0)?;
+                // 161: mul #1, #32, [1010]
+                ic.inner[1010] = ic.mul(161, 1, 32)?;
+                // 165: mul #21, #1, [1006]
+                ic.inner[1006] = ic.mul(165, 21, 1)?;
+                // 169: add #808, #0, [1027]
+                ic.inner[1027] = ic.add(169, 808, 0)?;
+                // 173: mul #20, #1, [1018]
+                ic.inner[1018] = ic.mul(173, 20, 1)?;
+                // 177: add #0, #514, [1029]
+                ic.inner[1029] = ic.add(177, 0, 514)?;
+                // 181: mul #1, #815, [1026]
+                ic.inner[1026] = ic.mul(181, 1, 815)?;
+                // 185: arb #14
+                ic.set_base(185, 14)?;
+                // 187: lt #24, rb-5, [63]
+                ic.inner[63] = if 24 < ic.inner[ic.rel_addr(187, -5)?] { 1 } else { 0 };
+                // 191: jt [63], #199
+                if ic.inner[63] != 0 {
+                    199
+                } else {
+                    194
+                }
+            }
+            194 => {
+                // 194: out [187]
+                ic.write_output(194, ic.inner[187])?;
+                // 196: jt #1, #203
+                203
+            }
+            199 => {
+                // 199: add [64], #1, [64]
+                ic.inner[64] = ic.add(199, ic.inner[64], 1)?;
+                203
+            }
+            203 => {
+                // 203: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(203, ic.inner[64], 2)?;
+                // 207: arb #-1
+                ic.set_base(207, -1)?;
+                // 209: eq #21, rb-7, [63]
+                ic.inner[63] = if 21 == ic.inner[ic.rel_addr(209, -7)?] { 1 } else { 0 };
+                // 213: jt [63], #225
+                if ic.inner[63] != 0 {
+                    225
+                } else {
+                    216
+                }
+            }
+            216 => {
+                // 216: out [209]
+                ic.write_output(216, ic.inner[209])?;
+                // 218: add [64], #1, [64]
+                ic.inner[64] = ic.add(218, ic.inner[64], 1)?;
+                // 222: jf #0, #225
+                225
+            }
+            225 => {
+                // 225: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(225, ic.inner[64], 2)?;
+                // 229: arb #-16
+                ic.set_base(229, -16)?;
+                // 231: add rb+6, #0, [63]
+                ic.inner[63] = ic.add(231, ic.inner[ic.rel_addr(231, 6)?], 0)?;
+                // 235: eq [63], #35, [63]
+                ic.inner[63] = if ic.inner[63] == 35 { 1 } else { 0 };
+                // 239: jt [63], #249
+                if ic.inner[63] != 0 {
+                    249
+                } else {
+                    242
+                }
+            }
+            242 => {
+                // 242: add [64], #1, [64]
+                ic.inner[64] = ic.add(242, ic.inner[64], 1)?;
+                // 246: jf #0, #251
+                251
+            }
+            249 => {
+                // 249: out [231]
+                ic.write_output(249, ic.inner[231])?;
+                251
+            }
+            251 => {
+                // 251: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(251, ic.inner[64], 2)?;
+                // 255: arb #9
+                ic.set_base(255, 9)?;
+                // 257: mul #1, rb+2, [63]
+                ic.inner[63] = ic.mul(257, 1, ic.inner[ic.rel_addr(257, 2)?])?;
+                // 261: eq [63], #37, [63]
+                ic.inner[63] = if ic.inner[63] == 37 { 1 } else { 0 };
+                // 265: jt [63], #271
+                if ic.inner[63] != 0 {
+                    271
+                } else {
+                    268
+                }
+            }
+            268 => {
+                // 268: jt #1, #277
+                277
+            }
+            271 => {
+                // 271: out [257]
+                ic.write_output(271, ic.inner[257])?;
+                // 273: add [64], #1, [64]
+                ic.inner[64] = ic.add(273, ic.inner[64], 1)?;
+                277
+            }
+            277 => {
+                // 277: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(277, ic.inner[64], 2)?;
+                // 281: arb #11
+                ic.set_base(281, 11)?;
+                // 283: eq rb-8, #23, [63]
+                ic.inner[63] = if ic.inner[ic.rel_addr(283, -8)?] == 23 { 1 } else { 0 };
+                // 287: jt [63], #293
+                if ic.inner[63] != 0 {
+                    293
+                } else {
+                    290
+                }
+            }
+            290 => {
+                // 290: jt #1, #299
+                299
+            }
+            293 => {
+                // 293: out [283]
+                ic.write_output(293, ic.inner[283])?;
+                // 295: add [64], #1, [64]
+                ic.inner[64] = ic.add(295, ic.inner[64], 1)?;
+                299
+            }
+            299 => {
+                // 299: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(299, ic.inner[64], 2)?;
+                // 303: arb #8
+                ic.set_base(303, 8)?;
+                // 305: lt #40, #39, rb-8
+                let v = if 40 < 39 { 1 } else { 0 };
+                let a = ic.rel_addr(305, -8)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(309);
+                }
+                // 309: jt [1017], #319
+                if ic.inner[1017] != 0 {
+                    319
+                } else {
+                    312
+                }
+            }
+            312 => {
+                // 312: add [64], #1, [64]
+                ic.inner[64] = ic.add(312, ic.inner[64], 1)?;
+                // 316: jf #0, #321
+                321
+            }
+            319 => {
+                // 319: out [305]
+                ic.write_output(319, ic.inner[305])?;
+                321
+            }
+            321 => {
+                // 321: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(321, ic.inner[64], 2)?;
+                // 325: arb #-28
+                ic.set_base(325, -28)?;
+                // 327: add #0, rb+6, [63]
+                ic.inner[63] = ic.add(327, 0, ic.inner[ic.rel_addr(327, 6)?])?;
+                // 331: eq [63], #39, [63]
+                ic.inner[63] = if ic.inner[63] == 39 { 1 } else { 0 };
+                // 335: jt [63], #341
+                if ic.inner[63] != 0 {
+                    341
+                } else {
+                    338
+                }
+            }
+            338 => {
+                // 338: jf #0, #347
+                347
+            }
+            341 => {
+                // 341: out [327]
+                ic.write_output(341, ic.inner[327])?;
+                // 343: add [64], #1, [64]
+                ic.inner[64] = ic.add(343, ic.inner[64], 1)?;
+                347
+            }
+            347 => {
+                // 347: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(347, ic.inner[64], 2)?;
+                // 351: arb #19
+                ic.set_base(351, 19)?;
+                // 353: lt #26, rb-7, [63]
+                ic.inner[63] = if 26 < ic.inner[ic.rel_addr(353, -7)?] { 1 } else { 0 };
+                // 357: jt [63], #363
+                if ic.inner[63] != 0 {
+                    363
+                } else {
+                    360
+                }
+            }
+            360 => {
+                // 360: jf #0, #369
+                369
+            }
+            363 => {
+                // 363: out [353]
+                ic.write_output(363, ic.inner[353])?;
+                // 365: add [64], #1, [64]
+                ic.inner[64] = ic.add(365, ic.inner[64], 1)?;
+                369
+            }
+            369 => {
+                // 369: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(369, ic.inner[64], 2)?;
+                // 373: arb #1
+                ic.set_base(373, 1)?;
+                // 375: mul rb-9, #1, [63]
+                ic.inner[63] = ic.mul(375, ic.inner[ic.rel_addr(375, -9)?], 1)?;
+                // 379: eq [63], #39, [63]
+                ic.inner[63] = if ic.inner[63] == 39 { 1 } else { 0 };
+                // 383: jt [63], #395
+                if ic.inner[63] != 0 {
+                    395
+                } else {
+                    386
+                }
+            }
+            386 => {
+                // 386: out [375]
+                ic.write_output(386, ic.inner[375])?;
+                // 388: add [64], #1, [64]
+                ic.inner[64] = ic.add(388, ic.inner[64], 1)?;
+                // 392: jt #1, #395
+                395
+            }
+            395 => {
+                // 395: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(395, ic.inner[64], 2)?;
+                // 399: arb #9
+                ic.set_base(399, 9)?;
+                // 401: jt #1, rb-3
+                ic.checked_addr(401, ic.inner[ic.rel_addr(401, -3)?])?
+            }
+            404 => {
+                // 404: jf #0, #413
+                413
+            }
+            407 => {
+                // 407: out [401]
+                ic.write_output(407, ic.inner[401])?;
+                // 409: add [64], #1, [64]
+                ic.inner[64] = ic.add(409, ic.inner[64], 1)?;
+                413
+            }
+            413 => {
+                // 413: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(413, ic.inner[64], 2)?;
+                // 417: arb #-13
+                ic.set_base(417, -13)?;
+                // 419: lt rb-4, #26, [63]
+                ic.inner[63] = if ic.inner[ic.rel_addr(419, -4)?] < 26 { 1 } else { 0 };
+                // 423: jt [63], #435
+                if ic.inner[63] != 0 {
+                    435
+                } else {
+                    426
+                }
+            }
+            426 => {
+                // 426: out [419]
+                ic.write_output(426, ic.inner[419])?;
+                // 428: add [64], #1, [64]
+                ic.inner[64] = ic.add(428, ic.inner[64], 1)?;
+                // 432: jt #1, #435
+                435
+            }
+            435 => {
+                // 435: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(435, ic.inner[64], 2)?;
+                // 439: arb #-1
+                ic.set_base(439, -1)?;
+                // 441: add #41, #0, rb+7
+                let v = ic.add(441, 41, 0)?;
+                let a = ic.rel_addr(441, 7)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(445);
+                }
+                // 445: eq [1019], #41, [63]
+                ic.inner[63] = if ic.inner[1019] == 41 { 1 } else { 0 };
+                // 449: jt [63], #461
+                if ic.inner[63] != 0 {
+                    461
+                } else {
+                    452
+                }
+            }
+            452 => {
+                // 452: out [441]
+                ic.write_output(452, ic.inner[441])?;
+                // 454: add [64], #1, [64]
+                ic.inner[64] = ic.add(454, ic.inner[64], 1)?;
+                // 458: jt #1, #461
+                461
+            }
+            461 => {
+                // 461: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(461, ic.inner[64], 2)?;
+                // 465: arb #7
+                ic.set_base(465, 7)?;
+                // 467: lt #42, #43, rb-2
+                let v = if 42 < 43 { 1 } else { 0 };
+                let a = ic.rel_addr(467, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(471);
+                }
+                // 471: jt [1017], #479
+                if ic.inner[1017] != 0 {
+                    479
+                } else {
+                    474
+                }
+            }
+            474 => {
+                // 474: out [467]
+                ic.write_output(474, ic.inner[467])?;
+                // 476: jt #1, #483
+                483
+            }
+            479 => {
+                // 479: add [64], #1, [64]
+                ic.inner[64] = ic.add(479, ic.inner[64], 1)?;
+                483
+            }
+            483 => {
+                // 483: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(483, ic.inner[64], 2)?;
+                // 487: arb #-6
+                ic.set_base(487, -6)?;
+                // 489: eq #43, #46, rb+0
+                let v = if 43 == 46 { 1 } else { 0 };
+                let a = ic.rel_addr(489, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(493);
+                }
+                // 493: jt [1013], #499
+                if ic.inner[1013] != 0 {
+                    499
+                } else {
+                    496
+                }
+            }
+            496 => {
+                // 496: jf #0, #505
+                505
+            }
+            499 => {
+                // 499: out [489]
+                ic.write_output(499, ic.inner[489])?;
+                // 501: add [64], #1, [64]
+                ic.inner[64] = ic.add(501, ic.inner[64], 1)?;
+                505
+            }
+            505 => {
+                // 505: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(505, ic.inner[64], 2)?;
+                // 509: arb #17
+                ic.set_base(509, 17)?;
+                // 511: jf #0, rb-2
+                ic.checked_addr(511, ic.inner[ic.rel_addr(511, -2)?])?
+            }
+            514 => {
+                // 514: out [511]
+                ic.write_output(514, ic.inner[511])?;
+                // 516: jt #1, #523
+                523
+            }
+            519 => {
+                // 519: add [64], #1, [64]
+                ic.inner[64] = ic.add(519, ic.inner[64], 1)?;
+                523
+            }
+            523 => {
+                // 523: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(523, ic.inner[64], 2)?;
+                // 527: arb #-27
+                ic.set_base(527, -27)?;
+                // 529: mul rb-1, #1, [63]
+                ic.inner[63] = ic.mul(529, ic.inner[ic.rel_addr(529, -1)?], 1)?;
+                // 533: eq [63], #28, [63]
+                ic.inner[63] = if ic.inner[63] == 28 { 1 } else { 0 };
+                // 537: jt [63], #547
+                if ic.inner[63] != 0 {
+                    547
+                } else {
+                    540
+                }
+            }
+            540 => {
+                // 540: add [64], #1, [64]
+                ic.inner[64] = ic.add(540, ic.inner[64], 1)?;
+                // 544: jf #0, #549
+                549
+            }
+            547 => {
+                // 547: out [529]
+                ic.write_output(547, ic.inner[529])?;
+                549
+            }
+            549 => {
+                // 549: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(549, ic.inner[64], 2)?;
+                // 553: arb #18
+                ic.set_base(553, 18)?;
+                // 555: jf rb-1, #567
+                if ic.inner[ic.rel_addr(555, -1)?] == 0 {
+                    567
+                } else {
+                    558
+                }
+            }
+            558 => {
+                // 558: out [555]
+                ic.write_output(558, ic.inner[555])?;
+                // 560: add [64], #1, [64]
+                ic.inner[64] = ic.add(560, ic.inner[64], 1)?;
+                // 564: jf #0, #567
+                567
+            }
+            567 => {
+                // 567: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(567, ic.inner[64], 2)?;
+                // 571: arb #-16
+                ic.set_base(571, -16)?;
+                // 573: mul #44, #1, rb+6
+                let v = ic.mul(573, 44, 1)?;
+                let a = ic.rel_addr(573, 6)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(577);
+                }
+                // 577: eq [1011], #43, [63]
+                ic.inner[63] = if ic.inner[1011] == 43 { 1 } else { 0 };
+                // 581: jt [63], #587
+                if ic.inner[63] != 0 {
+                    587
+                } else {
+                    584
+                }
+            }
+            584 => {
+                // 584: jf #0, #593
+                593
+            }
+            587 => {
+                // 587: out [573]
+                ic.write_output(587, ic.inner[573])?;
+                // 589: add [64], #1, [64]
+                ic.inner[64] = ic.add(589, ic.inner[64], 1)?;
+                593
+            }
+            593 => {
+                // 593: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(593, ic.inner[64], 2)?;
+                // 597: arb #8
+                ic.set_base(597, 8)?;
+                // 599: mul #45, #1, rb-1
+                let v = ic.mul(599, 45, 1)?;
+                let a = ic.rel_addr(599, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(603);
+                }
+                // 603: eq [1012], #45, [63]
+                ic.inner[63] = if ic.inner[1012] == 45 { 1 } else { 0 };
+                // 607: jt [63], #619
+                if ic.inner[63] != 0 {
+                    619
+                } else {
+                    610
+                }
+            }
+            610 => {
+                // 610: out [599]
+                ic.write_output(610, ic.inner[599])?;
+                // 612: add [64], #1, [64]
+                ic.inner[64] = ic.add(612, ic.inner[64], 1)?;
+                // 616: jt #1, #619
+                619
+            }
+            619 => {
+                // 619: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(619, ic.inner[64], 2)?;
+                // 623: arb #7
+                ic.set_base(623, 7)?;
+                // 625: jt rb+1, #633
+                if ic.inner[ic.rel_addr(625, 1)?] != 0 {
+                    633
+                } else {
+                    628
+                }
+            }
+            628 => {
+                // 628: out [625]
+                ic.write_output(628, ic.inner[625])?;
+                // 630: jf #0, #637
+                637
+            }
+            633 => {
+                // 633: add [64], #1, [64]
+                ic.inner[64] = ic.add(633, ic.inner[64], 1)?;
+                637
+            }
+            637 => {
+                // 637: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(637, ic.inner[64], 2)?;
+                // 641: arb #-8
+                ic.set_base(641, -8)?;
+                // 643: mul #1, rb-3, [63]
+                ic.inner[63] = ic.mul(643, 1, ic.inner[ic.rel_addr(643, -3)?])?;
+                // 647: eq [63], #25, [63]
+                ic.inner[63] = if ic.inner[63] == 25 { 1 } else { 0 };
+                // 651: jt [63], #659
+                if ic.inner[63] != 0 {
+                    659
+                } else {
+                    654
+                }
+            }
+            654 => {
+                // 654: out [643]
+                ic.write_output(654, ic.inner[643])?;
+                // 656: jt #1, #663
+                663
+            }
+            659 => {
+                // 659: add [64], #1, [64]
+                ic.inner[64] = ic.add(659, ic.inner[64], 1)?;
+                663
+            }
+            663 => {
+                // 663: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(663, ic.inner[64], 2)?;
+                // 667: arb #14
+                ic.set_base(667, 14)?;
+                // 669: jf rb-5, #679
+                if ic.inner[ic.rel_addr(669, -5)?] == 0 {
+                    679
+                } else {
+                    672
+                }
+            }
+            672 => {
+                // 672: add [64], #1, [64]
+                ic.inner[64] = ic.add(672, ic.inner[64], 1)?;
+                // 676: jt #1, #681
+                681
+            }
+            679 => {
+                // 679: out [669]
+                ic.write_output(679, ic.inner[669])?;
+                681
+            }
+            681 => {
+                // 681: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(681, ic.inner[64], 2)?;
+                // 685: arb #-28
+                ic.set_base(685, -28)?;
+                // 687: add #0, rb+2, [63]
+                ic.inner[63] = ic.add(687, 0, ic.inner[ic.rel_addr(687, 2)?])?;
+                // 691: eq [63], #30, [63]
+                ic.inner[63] = if ic.inner[63] == 30 { 1 } else { 0 };
+                // 695: jt [63], #707
+                if ic.inner[63] != 0 {
+                    707
+                } else {
+                    698
+                }
+            }
+            698 => {
+                // 698: out [687]
+                ic.write_output(698, ic.inner[687])?;
+                // 700: add [64], #1, [64]
+                ic.inner[64] = ic.add(700, ic.inner[64], 1)?;
+                // 704: jf #0, #707
+                707
+            }
+            707 => {
+                // 707: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(707, ic.inner[64], 2)?;
+                // 711: arb #21
+                ic.set_base(711, 21)?;
+                // 713: add #46, #0, rb+0
+                let v = ic.add(713, 46, 0)?;
+                let a = ic.rel_addr(713, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(717);
+                }
+                // 717: eq [1019], #48, [63]
+                ic.inner[63] = if ic.inner[1019] == 48 { 1 } else { 0 };
+                // 721: jt [63], #727
+                if ic.inner[63] != 0 {
+                    727
+                } else {
+                    724
+                }
+            }
+            724 => {
+                // 724: jf #0, #733
+                733
+            }
+            727 => {
+                // 727: out [713]
+                ic.write_output(727, ic.inner[713])?;
+                // 729: add [64], #1, [64]
+                ic.inner[64] = ic.add(729, ic.inner[64], 1)?;
+                733
+            }
+            733 => {
+                // 733: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(733, ic.inner[64], 2)?;
+                // 737: arb #-3
+                ic.set_base(737, -3)?;
+                // 739: eq #47, #47, rb+1
+                let v = if 47 == 47 { 1 } else { 0 };
+                let a = ic.rel_addr(739, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(743);
+                }
+                // 743: jt [1017], #751
+                if ic.inner[1017] != 0 {
+                    751
+                } else {
+                    746
+                }
+            }
+            746 => {
+                // 746: out [739]
+                ic.write_output(746, ic.inner[739])?;
+                // 748: jf #0, #755
+                755
+            }
+            751 => {
+                // 751: add [64], #1, [64]
+                ic.inner[64] = ic.add(751, ic.inner[64], 1)?;
+                755
+            }
+            755 => {
+                // 755: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(755, ic.inner[64], 2)?;
+                // 759: arb #-13
+                ic.set_base(759, -13)?;
+                // 761: lt rb+0, #37, [63]
+                ic.inner[63] = if ic.inner[ic.rel_addr(761, 0)?] < 37 { 1 } else { 0 };
+                // 765: jt [63], #771
+                if ic.inner[63] != 0 {
+                    771
+                } else {
+                    768
+                }
+            }
+            768 => {
+                // 768: jt #1, #777
+                777
+            }
+            771 => {
+                // 771: out [761]
+                ic.write_output(771, ic.inner[761])?;
+                // 773: add [64], #1, [64]
+                ic.inner[64] = ic.add(773, ic.inner[64], 1)?;
+                777
+            }
+            777 => {
+                // 777: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(777, ic.inner[64], 2)?;
+                // 781: arb #7
+                ic.set_base(781, 7)?;
+                // 783: eq #21, rb-9, [63]
+                ic.inner[63] = if 21 == ic.inner[ic.rel_addr(783, -9)?] { 1 } else { 0 };
+                // 787: jt [63], #797
+                if ic.inner[63] != 0 {
+                    797
+                } else {
+                    790
+                }
+            }
+            790 => {
+                // 790: add [64], #1, [64]
+                ic.inner[64] = ic.add(790, ic.inner[64], 1)?;
+                // 794: jt #1, #799
+                799
+            }
+            797 => {
+                // 797: out [783]
+                ic.write_output(797, ic.inner[783])?;
+                799
+            }
+            799 => {
+                // 799: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(799, ic.inner[64], 2)?;
+                // 803: arb #22
+                ic.set_base(803, 22)?;
+                // 805: jf #0, rb-5
+                ic.checked_addr(805, ic.inner[ic.rel_addr(805, -5)?])?
+            }
+            808 => {
+                // 808: add [64], #1, [64]
+                ic.inner[64] = ic.add(808, ic.inner[64], 1)?;
+                // 812: jf #0, #817
+                817
+            }
+            815 => {
+                // 815: out [805]
+                ic.write_output(815, ic.inner[805])?;
+                817
+            }
+            817 => {
+                // 817: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(817, ic.inner[64], 2)?;
+                // 821: arb #-4
+                ic.set_base(821, -4)?;
+                // 823: jt rb-8, #829
+                if ic.inner[ic.rel_addr(823, -8)?] != 0 {
+                    829
+                } else {
+                    826
+                }
+            }
+            826 => {
+                // 826: jf #0, #835
+                835
+            }
+            829 => {
+                // 829: out [823]
+                ic.write_output(829, ic.inner[823])?;
+                // 831: add [64], #1, [64]
+                ic.inner[64] = ic.add(831, ic.inner[64], 1)?;
+                835
+            }
+            835 => {
+                // 835: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(835, ic.inner[64], 2)?;
+                // 839: arb #-4
+                ic.set_base(839, -4)?;
+                // 841: jt #1, rb+0
+                ic.checked_addr(841, ic.inner[ic.rel_addr(841, 0)?])?
+            }
+            844 => {
+                // 844: out [841]
+                ic.write_output(844, ic.inner[841])?;
+                // 846: jt #1, #853
+                853
+            }
+            849 => {
+                // 849: add [64], #1, [64]
+                ic.inner[64] = ic.add(849, ic.inner[64], 1)?;
+                853
+            }
+            853 => {
+                // 853: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(853, ic.inner[64], 2)?;
+                // 857: arb #-30
+                ic.set_base(857, -30)?;
+                // 859: eq rb+6, #30, [63]
+                ic.inner[63] = if ic.inner[ic.rel_addr(859, 6)?] == 30 { 1 } else { 0 };
+                // 863: jt [63], #871
+                if ic.inner[63] != 0 {
+                    871
+                } else {
+                    866
+                }
+            }
+            866 => {
+                // 866: out [859]
+                ic.write_output(866, ic.inner[859])?;
+                // 868: jt #1, #875
+                875
+            }
+            871 => {
+                // 871: add [64], #1, [64]
+                ic.inner[64] = ic.add(871, ic.inner[64], 1)?;
+                875
+            }
+            875 => {
+                // 875: mul [64], #2, [64]
+                ic.inner[64] = ic.mul(875, ic.inner[64], 2)?;
+                // 879: arb #-2
+                ic.set_base(879, -2)?;
+                // 881: add rb+9, #0, [63]
+                ic.inner[63] = ic.add(881, ic.inner[ic.rel_addr(881, 9)?], 0)?;
+                // 885: eq [63], #22, [63]
+                ic.inner[63] = if ic.inner[63] == 22 { 1 } else { 0 };
+                // 889: jt [63], #897
+                if ic.inner[63] != 0 {
+                    897
+                } else {
+                    892
+                }
+            }
+            892 => {
+                // 892: out [881]
+                ic.write_output(892, ic.inner[881])?;
+                // 894: jf #0, #901
+                901
+            }
+            897 => {
+                // 897: add [64], #1, [64]
+                ic.inner[64] = ic.add(897, ic.inner[64], 1)?;
+                901
+            }
+            901 => {
+                // 901: out [64]
+                ic.write_output(901, ic.inner[64])?;
+                // 903: hlt
+                return ic.halt_at(903);
+            }
+            904 => {
+                // 904: add #27, #0, rb+1
+                let v = ic.add(904, 27, 0)?;
+                let a = ic.rel_addr(904, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(908);
+                }
+                // 908: mul #1, #915, rb+0
+                let v = ic.mul(908, 1, 915)?;
+                let a = ic.rel_addr(908, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(912);
+                }
+                // 912: jf #0, #922
+                922
+            }
+            915 => {
+                // 915: add rb+1, #66266, rb+1
+                let v = ic.add(915, ic.inner[ic.rel_addr(915, 1)?], 66266)?;
+                let a = ic.rel_addr(915, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(919);
+                }
+                // 919: out rb+1
+                ic.write_output(919, ic.inner[ic.rel_addr(919, 1)?])?;
+                // 921: hlt
+                return ic.halt_at(921);
+            }
+            922 => {
+                // 922: arb #3
+                ic.set_base(922, 3)?;
+                // 924: lt rb-2, #3, [63]
+                ic.inner[63] = if ic.inner[ic.rel_addr(924, -2)?] < 3 { 1 } else { 0 };
+                // 928: jt [63], #964
+                if ic.inner[63] != 0 {
+                    964
+                } else {
+                    931
+                }
+            }
+            931 => {
+                // 931: add rb-2, #-1, rb+1
+                let v = ic.add(931, ic.inner[ic.rel_addr(931, -2)?], -1)?;
+                let a = ic.rel_addr(931, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(935);
+                }
+                // 935: mul #942, #1, rb+0
+                let v = ic.mul(935, 942, 1)?;
+                let a = ic.rel_addr(935, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(939);
+                }
+                // 939: jt #1, #922
+                922
+            }
+            942 => {
+                // 942: add #0, rb+1, rb-1
+                let v = ic.add(942, 0, ic.inner[ic.rel_addr(942, 1)?])?;
+                let a = ic.rel_addr(942, -1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(946);
+                }
+                // 946: add rb-2, #-3, rb+1
+                let v = ic.add(946, ic.inner[ic.rel_addr(946, -2)?], -3)?;
+                let a = ic.rel_addr(946, 1)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(950);
+                }
+                // 950: add #0, #957, rb+0
+                let v = ic.add(950, 0, 957)?;
+                let a = ic.rel_addr(950, 0)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(954);
+                }
+                // 954: jf #0, #922
+                922
+            }
+            957 => {
+                // 957: add rb+1, rb-1, rb-2
+                let v = ic.add(957, ic.inner[ic.rel_addr(957, 1)?], ic.inner[ic.rel_addr(957, -1)?])?;
+                let a = ic.rel_addr(957, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(961);
+                }
+                // 961: jt #1, #968
+                968
+            }
+            964 => {
+                // 964: mul rb-2, #1, rb-2
+                let v = ic.mul(964, ic.inner[ic.rel_addr(964, -2)?], 1)?;
+                let a = ic.rel_addr(964, -2)?;
+                ic.inner[a] = v;
+                if is_code(a) {
+                    return ic.resume_at(968);
+                }
+                968
+            }
+            968 => {
+                // 968: arb #-3
+                ic.set_base(968, -3)?;
+                // 970: jf #0, rb+0
+                ic.checked_addr(970, ic.inner[ic.rel_addr(970, 0)?])?
+            }
+            _ => return ic.resume_at(pc),
+        };
+    }
+}
+
+fn is_code(addr: usize) -> bool {
+    matches!(addr, 0..=62 | 65..=972)
+}
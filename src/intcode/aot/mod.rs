@@ -0,0 +1,781 @@
+//! Ahead-of-time compilation of Intcode programs to Rust.
+//!
+//! `transpile` turns each basic block of a program's static control-flow
+//! graph into a match arm, so a run is a loop over plain Rust code with no
+//! decoding. The generated `run` drives an ordinary `IntCode` machine, using
+//! its memory, registers and IO device.
+//!
+//! Programs commonly patch their own instructions, writing to a fixed
+//! address inside the code. Operand words which are written like that are
+//! read from memory when the instruction runs. A patched opcode is compiled
+//! for every opcode it could hold with the same parameter modes, and picked
+//! between by the word in memory, so the code around it still compiles.
+//! Anything else the compiled code can't follow hands the machine back to
+//! the interpreter, which runs the program to the end: an opcode patched to
+//! anything else, any other write into the code, a jump to an address which
+//! doesn't start a block, or anything the interpreter would fault on.
+//!
+//! Compiled code writes memory directly and doesn't count steps, so a
+//! machine with mapped devices, a journal, a profiler or loop detection is
+//! handed straight to the interpreter, which sees every step.
+//!
+//! The generated code uses the machine's private state, so it only builds
+//! as a module inside `crate::intcode`, the way `aot`'s own are.
+
+use super::cfg::{successors, Block, Cfg};
+use super::disasm::{Line, Operand, Statement};
+use super::icache::InstructionCache;
+use super::intcode_io::{Input, Output};
+use super::opcodes::{parse_instruction, Instruction, ParameterMode, MAX_ARITY};
+use super::{ErrorKind, Int, IntCode, Memory, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+
+/// Where statements inside a match arm start.
+const INDENT: &str = "                ";
+
+// compiled from the puzzle inputs, to check against the interpreter
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(clippy::all)]
+mod day5;
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(clippy::all)]
+mod day9;
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(clippy::all)]
+mod day19;
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(clippy::all)]
+mod day21;
+
+/// What the generated code calls back into.
+#[allow(dead_code)]
+impl<IO: Input + Output> IntCode<IO> {
+    fn rel_addr(&self, pc: usize, offset: Int) -> Result<usize> {
+        let addr = self.relative_base.checked_add(offset).ok_or_else(|| {
+            let op = parse_instruction(self.inner[pc]).map_or("?", |(i, _)| i.mnemonic());
+            self.fault_at(pc, self.base_overflow(op, offset))
+        })?;
+        if addr < 0 {
+            return Err(self.fault_at(pc, ErrorKind::NegativeAddress(addr)));
+        }
+        Ok(addr as usize)
+    }
+
+    fn set_base(&mut self, pc: usize, offset: Int) -> Result<()> {
+        self.relative_base = match self.relative_base.checked_add(offset) {
+            Some(base) => base,
+            None => return Err(self.fault_at(pc, self.base_overflow("arb", offset))),
+        };
+        Ok(())
+    }
+
+    fn base_overflow(&self, op: &'static str, offset: Int) -> ErrorKind {
+        ErrorKind::Overflow {
+            op,
+            lhs: self.relative_base,
+            rhs: offset,
+        }
+    }
+
+    fn checked_addr(&self, pc: usize, addr: Int) -> Result<usize> {
+        if addr < 0 {
+            return Err(self.fault_at(pc, ErrorKind::NegativeAddress(addr)));
+        }
+        Ok(addr as usize)
+    }
+
+    fn add(&self, pc: usize, lhs: Int, rhs: Int) -> Result<Int> {
+        let arith = self.arithmetic;
+        arith
+            .apply(Instruction::Add, lhs, rhs)
+            .map_err(|kind| self.fault_at(pc, kind))
+    }
+
+    fn mul(&self, pc: usize, lhs: Int, rhs: Int) -> Result<Int> {
+        let arith = self.arithmetic;
+        arith
+            .apply(Instruction::Mul, lhs, rhs)
+            .map_err(|kind| self.fault_at(pc, kind))
+    }
+
+    fn read_input(&mut self, pc: usize) -> Result<Int> {
+        if let Some(x) = self.pending_input.pop_front() {
+            return Ok(x);
+        }
+        self.io_device.input().map_err(|e| self.io_fault(pc, e))
+    }
+
+    fn write_output(&mut self, pc: usize, value: Int) -> Result<()> {
+        let res = self.io_device.output(value);
+        res.map_err(|e| self.io_fault(pc, e))
+    }
+
+    fn halt_at(&mut self, pc: usize) -> Result<()> {
+        self.pc = pc;
+        self.halt = true;
+        Ok(())
+    }
+
+    /// Whether anything attached needs to see every step.
+    fn watched(&self) -> bool {
+        self.devices.is_some()
+            || self.journal.is_some()
+            || self.loops.is_some()
+            || self.profiler.is_some()
+    }
+
+    /// Leave compiled code, interpreting the rest of the run from `pc`.
+    fn resume_at(&mut self, pc: usize) -> Result<()> {
+        log::debug!("compiled code fell back to the interpreter at {}", pc);
+        self.pc = pc;
+        self.icache = InstructionCache::new(self.inner.len());
+        self.run_till_end()
+    }
+}
+
+/// Rust source for a module with a `run` function which executes `intcode`
+/// on a machine loaded with it. `source` names the program in the header.
+/// The module has to live inside `crate::intcode`.
+pub fn transpile(intcode: &[Int], source: &str) -> String {
+    let cfg = static_cfg(intcode);
+    let blocks = cfg.blocks();
+    let mut compiler = Compiler::new(intcode, &blocks);
+    for block in &blocks {
+        compiler.block(block);
+    }
+
+    let mut out = String::new();
+    writeln!(out, "//! `{}`, compiled by `transpile`.", source).unwrap();
+    writeln!(out, "//! Regenerate this file rather than editing it.").unwrap();
+    writeln!(out, "use crate::intcode::intcode_io::{{Input, Output}};").unwrap();
+    writeln!(out, "use crate::intcode::{{IntCode, Result}};").unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "pub fn run<IO: Input + Output>(ic: &mut IntCode<IO>) -> Result<()> {{"
+    )
+    .unwrap();
+    writeln!(out, "    if ic.halt {{").unwrap();
+    writeln!(out, "        return Ok(());").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "    if ic.watched() {{").unwrap();
+    writeln!(out, "        return ic.resume_at(ic.pc);").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "    let mut pc = ic.pc;").unwrap();
+    writeln!(out, "    loop {{").unwrap();
+    writeln!(out, "        pc = match pc {{").unwrap();
+    out.push_str(&compiler.out);
+    writeln!(out, "            _ => return ic.resume_at(pc),").unwrap();
+    writeln!(out, "        }};").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+    if compiler.checks_code {
+        let ranges = compiler
+            .fixed_ranges()
+            .iter()
+            .map(|(start, end)| format!("{}..={}", start, end - 1))
+            .collect::<Vec<_>>();
+        writeln!(out).unwrap();
+        writeln!(out, "fn is_code(addr: usize) -> bool {{").unwrap();
+        writeln!(out, "    matches!(addr, {})", ranges.join(" | ")).unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+    out
+}
+
+/// The control-flow graph, including the code dynamic jumps return to.
+///
+/// Calls push an immediate return address and jump away unconditionally,
+/// so an immediate operand naming the address just past such a jump is
+/// taken to be where a dynamic jump will land, as long as there is code
+/// there. The jump itself needn't be reachable: day 5 stores the address
+/// past a dead `jt #1, #99999` and jumps to it.
+fn static_cfg(intcode: &[Int]) -> Cfg {
+    let mut entries = vec![0];
+    loop {
+        let cfg = Cfg::with_entries(intcode, &entries);
+        let lines = cfg
+            .blocks()
+            .into_iter()
+            .flat_map(|b| b.lines.iter())
+            .collect::<Vec<_>>();
+        let sites = lines
+            .iter()
+            .filter_map(|l| match &l.stmt {
+                Statement::Instr { operands, .. } => Some(operands),
+                Statement::Data(_) => None,
+            })
+            .flatten()
+            .filter(|op| op.mode == ParameterMode::Immediate && op.value >= 0)
+            .map(|op| op.value as usize)
+            .filter(|addr| *addr < intcode.len() && after_jump(intcode, *addr))
+            .filter(|addr| cfg.block(*addr).is_none() && plausible(intcode, *addr));
+        // a patched opcode goes on to wherever any of its variants go. The
+        // word as loaded is followed like any other code, but the others
+        // only to plausible code which doesn't start inside an instruction.
+        let code = code_words(lines.iter().cloned());
+        let patched = patched(lines.iter().cloned(), &code);
+        let variants = lines
+            .iter()
+            .filter(|l| patched.contains(&l.addr))
+            .flat_map(|l| variants(intcode, l.addr))
+            .collect::<Vec<_>>();
+        let operands = lines
+            .iter()
+            .cloned()
+            .chain(&variants)
+            .flat_map(|l| l.addr + 1..l.addr + l.stmt.size())
+            .collect::<BTreeSet<_>>();
+        let dispatched = variants
+            .iter()
+            .flat_map(|v| {
+                let loaded = v.words[0] == intcode[v.addr];
+                successors(v)
+                    .into_iter()
+                    .map(move |(addr, _)| (addr, loaded))
+            })
+            .filter(|(addr, _)| *addr < intcode.len() && cfg.block(*addr).is_none())
+            .filter(|(addr, loaded)| {
+                *loaded || (!operands.contains(addr) && plausible(intcode, *addr))
+            })
+            .map(|(addr, _)| addr);
+        let sites = sites.chain(dispatched).collect::<BTreeSet<_>>();
+        if sites.is_empty() {
+            return cfg;
+        }
+        entries.extend(sites);
+    }
+}
+
+/// Whether `addr` is just past an unconditional jump, reachable or not.
+fn after_jump(intcode: &[Int], addr: usize) -> bool {
+    let jump = 1 + Instruction::JumpTrue.arity();
+    if addr < jump {
+        return false;
+    }
+    match Line::decode(intcode, addr - jump).stmt {
+        Statement::Instr { instr, operands } => {
+            let always = match instr {
+                Instruction::JumpTrue => operands[0].value != 0,
+                Instruction::JumpFalse => operands[0].value == 0,
+                _ => false,
+            };
+            always && operands[0].mode == ParameterMode::Immediate
+        }
+        Statement::Data(_) => false,
+    }
+}
+
+/// Whether the straight-line code at `addr` decodes up to a jump or halt.
+fn plausible(intcode: &[Int], mut addr: usize) -> bool {
+    while addr < intcode.len() {
+        let line = Line::decode(intcode, addr);
+        match line.stmt {
+            Statement::Data(_) => return false,
+            Statement::Instr {
+                instr: Instruction::JumpTrue,
+                ..
+            }
+            | Statement::Instr {
+                instr: Instruction::JumpFalse,
+                ..
+            }
+            | Statement::Instr {
+                instr: Instruction::Halt,
+                ..
+            } => return true,
+            _ => addr += line.stmt.size(),
+        }
+    }
+    false
+}
+
+/// Addresses of every word of `lines`.
+fn code_words<'a>(lines: impl Iterator<Item = &'a Line>) -> BTreeSet<usize> {
+    lines.flat_map(|l| l.addr..l.addr + l.stmt.size()).collect()
+}
+
+/// The words of `code` which `lines` write to by absolute address.
+fn patched<'a>(lines: impl Iterator<Item = &'a Line>, code: &BTreeSet<usize>) -> BTreeSet<usize> {
+    lines
+        .filter_map(|l| match &l.stmt {
+            Statement::Instr { instr, operands } if writes(*instr) => operands.last().cloned(),
+            _ => None,
+        })
+        .filter(|op| op.mode == ParameterMode::Position && op.value >= 0)
+        .map(|op| op.value as usize)
+        .filter(|addr| code.contains(addr))
+        .collect()
+}
+
+/// The instructions a patched opcode word at `addr` might hold: the word as
+/// loaded, and every other opcode with the same parameter modes. Day 5
+/// picks its instruction like that, adding its input to an opcode which
+/// doesn't decode by itself.
+fn variants(intcode: &[Int], addr: usize) -> Vec<Line> {
+    let word = intcode[addr];
+    let modes = word - word % 100;
+    let mut opcodes = (1..=9)
+        .chain(Some(99))
+        .filter_map(|op| modes.checked_add(op))
+        .chain(Some(word))
+        .collect::<Vec<_>>();
+    opcodes.sort_unstable();
+    opcodes.dedup();
+    let mut words = intcode[addr..(addr + 1 + MAX_ARITY).min(intcode.len())].to_vec();
+    opcodes
+        .into_iter()
+        .filter_map(|op| {
+            words[0] = op;
+            let line = Line::from_words(addr, &words);
+            match &line.stmt {
+                // writing to an immediate only ever faults
+                Statement::Instr { instr, operands }
+                    if writes(*instr)
+                        && operands.last().unwrap().mode == ParameterMode::Immediate =>
+                {
+                    None
+                }
+                Statement::Instr { .. } => Some(line),
+                Statement::Data(_) => None,
+            }
+        })
+        .collect()
+}
+
+/// Whether `instr` writes to its last operand.
+fn writes(instr: Instruction) -> bool {
+    match instr {
+        Instruction::Add
+        | Instruction::Mul
+        | Instruction::LessThan
+        | Instruction::EqualTo
+        | Instruction::Input => true,
+        Instruction::Output
+        | Instruction::SetBase
+        | Instruction::JumpTrue
+        | Instruction::JumpFalse
+        | Instruction::Halt => false,
+    }
+}
+
+struct Compiler {
+    out: String,
+    /// Addresses of every word of compiled code.
+    code: BTreeSet<usize>,
+    /// Code words the program writes to by absolute address.
+    patched: BTreeSet<usize>,
+    /// What each patched opcode might hold.
+    variants: BTreeMap<usize, Vec<Line>>,
+    /// How far statements are nested inside their match arm.
+    depth: usize,
+    /// Whether any emitted code calls `is_code`.
+    checks_code: bool,
+}
+
+impl Compiler {
+    fn new(intcode: &[Int], blocks: &[&Block]) -> Compiler {
+        let lines = blocks.iter().flat_map(|b| b.lines.iter());
+        let mut code = code_words(lines.clone());
+        let variants = patched(lines.clone(), &code)
+            .into_iter()
+            .filter(|addr| lines.clone().any(|l| l.addr == *addr))
+            .map(|addr| (addr, variants(intcode, addr)))
+            .collect::<BTreeMap<_, _>>();
+        // a variant's operands are code too, even where the loaded word
+        // doesn't decode
+        code.extend(code_words(variants.values().flatten()));
+        let patched = patched(lines, &code);
+        Compiler {
+            out: String::new(),
+            code,
+            patched,
+            variants,
+            depth: 0,
+            checks_code: false,
+        }
+    }
+
+    /// The code words which are never patched, as merged ranges.
+    fn fixed_ranges(&self) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for &addr in self.code.difference(&self.patched) {
+            match ranges.last_mut() {
+                Some(last) if last.1 == addr => last.1 += 1,
+                _ => ranges.push((addr, addr + 1)),
+            }
+        }
+        ranges
+    }
+
+    fn line(&mut self, line: fmt::Arguments) {
+        self.out.push_str(INDENT);
+        for _ in 0..self.depth {
+            self.out.push_str("    ");
+        }
+        self.out.write_fmt(line).unwrap();
+        self.out.push('\n');
+    }
+
+    /// Give up on compiled code at `pc`.
+    fn fallback(&mut self, pc: usize) {
+        self.line(format_args!("return ic.resume_at({});", pc));
+    }
+
+    /// The address written by operand `nth` of the instruction at `pc`, as
+    /// an expression. It is a constant unless the program patches it.
+    fn operand_value(&self, pc: usize, nth: usize, op: &Operand) -> String {
+        let word = pc + 1 + nth;
+        if self.patched.contains(&word) {
+            format!("ic.inner[{}]", word)
+        } else {
+            op.value.to_string()
+        }
+    }
+
+    /// An expression reading operand `nth`, or `None` if only the
+    /// interpreter can.
+    fn read(&self, pc: usize, nth: usize, op: &Operand) -> Option<String> {
+        let value = self.operand_value(pc, nth, op);
+        let dynamic = self.patched.contains(&(pc + 1 + nth));
+        Some(match op.mode {
+            ParameterMode::Immediate => value,
+            ParameterMode::Position if dynamic => {
+                format!("ic.inner[ic.checked_addr({}, {})?]", pc, value)
+            }
+            ParameterMode::Position if op.value >= 0 => format!("ic.inner[{}]", value),
+            ParameterMode::Position => return None,
+            ParameterMode::Relative => format!("ic.inner[ic.rel_addr({}, {})?]", pc, value),
+        })
+    }
+
+    fn block(&mut self, block: &Block) {
+        writeln!(self.out, "            {} => {{", block.start()).unwrap();
+        let last = block.lines.last().unwrap().addr;
+        for line in &block.lines {
+            let pc = line.addr;
+            let next = pc + line.stmt.size();
+            self.line(format_args!("// {}: {}", pc, line.stmt));
+            if let Some(variants) = self.variants.get(&pc).cloned() {
+                self.dispatch(pc, &variants);
+                break;
+            }
+            let (instr, ops) = match &line.stmt {
+                Statement::Instr { instr, operands } => (*instr, operands),
+                Statement::Data(_) => {
+                    self.fallback(pc);
+                    break;
+                }
+            };
+            if !self.exit(pc, next, instr, ops) {
+                break;
+            }
+            if pc == last {
+                self.line(format_args!("{}", next));
+            }
+        }
+        writeln!(self.out, "            }}").unwrap();
+    }
+
+    /// Statements for one instruction, ending in the next pc if it leaves
+    /// the block. Returns false once the block can't go on.
+    fn exit(&mut self, pc: usize, next: usize, instr: Instruction, ops: &[Operand]) -> bool {
+        match instr {
+            Instruction::Halt => {
+                self.line(format_args!("return ic.halt_at({});", pc));
+                false
+            }
+            Instruction::JumpTrue | Instruction::JumpFalse => {
+                self.jump(pc, next, instr, ops);
+                false
+            }
+            _ => self.instr(pc, next, instr, ops),
+        }
+    }
+
+    /// A patched opcode, which picks what to run by the word in memory. It
+    /// always ends its block.
+    fn dispatch(&mut self, pc: usize, variants: &[Line]) {
+        self.line(format_args!("match ic.inner[{}] {{", pc));
+        self.depth += 1;
+        for line in variants {
+            let (instr, ops) = match &line.stmt {
+                Statement::Instr { instr, operands } => (*instr, operands),
+                Statement::Data(_) => unreachable!("variants always decode"),
+            };
+            let next = pc + line.stmt.size();
+            self.line(format_args!("{} => {{", line.words[0]));
+            self.depth += 1;
+            self.line(format_args!("// {}", line.stmt));
+            if self.exit(pc, next, instr, ops) {
+                self.line(format_args!("{}", next));
+            }
+            self.depth -= 1;
+            self.line(format_args!("}}"));
+        }
+        self.line(format_args!("_ => return ic.resume_at({}),", pc));
+        self.depth -= 1;
+        self.line(format_args!("}}"));
+    }
+
+    /// Statements for one instruction. Returns false once the block can't
+    /// go on, having emitted a return.
+    fn instr(&mut self, pc: usize, next: usize, instr: Instruction, ops: &[Operand]) -> bool {
+        let inputs = if writes(instr) {
+            instr.arity() - 1
+        } else {
+            instr.arity()
+        };
+        let reads = match ops
+            .iter()
+            .enumerate()
+            .take(inputs)
+            .map(|(nth, op)| self.read(pc, nth, op))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(reads) => reads,
+            None => {
+                self.fallback(pc);
+                return false;
+            }
+        };
+        let value = match instr {
+            Instruction::Add => format!("ic.add({}, {}, {})?", pc, reads[0], reads[1]),
+            Instruction::Mul => format!("ic.mul({}, {}, {})?", pc, reads[0], reads[1]),
+            Instruction::LessThan => {
+                format!("if {} < {} {{ 1 }} else {{ 0 }}", reads[0], reads[1])
+            }
+            Instruction::EqualTo => {
+                format!("if {} == {} {{ 1 }} else {{ 0 }}", reads[0], reads[1])
+            }
+            Instruction::Input => format!("ic.read_input({})?", pc),
+            Instruction::Output => {
+                self.line(format_args!("ic.write_output({}, {})?;", pc, reads[0]));
+                return true;
+            }
+            Instruction::SetBase => {
+                self.line(format_args!("ic.set_base({}, {})?;", pc, reads[0]));
+                return true;
+            }
+            Instruction::Halt | Instruction::JumpTrue | Instruction::JumpFalse => {
+                unreachable!("block exits are compiled by Compiler::block")
+            }
+        };
+
+        let nth = inputs;
+        let dst = ops[nth];
+        let dynamic = self.patched.contains(&(pc + 1 + nth));
+        let addr = self.operand_value(pc, nth, &dst);
+        match dst.mode {
+            // a patch the rest of the code expects
+            ParameterMode::Position if !dynamic && self.patched.contains(&(dst.value as usize)) => {
+                self.line(format_args!("ic.inner[{}] = {};", addr, value));
+            }
+            ParameterMode::Position
+                if !dynamic && dst.value >= 0 && !self.code.contains(&(dst.value as usize)) =>
+            {
+                self.line(format_args!("ic.inner[{}] = {};", addr, value));
+            }
+            ParameterMode::Position | ParameterMode::Relative => {
+                self.checks_code = true;
+                let check = if dst.mode == ParameterMode::Relative {
+                    "rel_addr"
+                } else {
+                    "checked_addr"
+                };
+                self.line(format_args!("let v = {};", value));
+                self.line(format_args!("let a = ic.{}({}, {})?;", check, pc, addr));
+                self.line(format_args!("ic.inner[a] = v;"));
+                self.line(format_args!("if is_code(a) {{"));
+                self.line(format_args!("    return ic.resume_at({});", next));
+                self.line(format_args!("}}"));
+            }
+            ParameterMode::Immediate => {
+                self.fallback(pc);
+                return false;
+            }
+        }
+        true
+    }
+
+    fn jump(&mut self, pc: usize, next: usize, instr: Instruction, ops: &[Operand]) {
+        let (cond, target) = match (self.read(pc, 0, &ops[0]), self.read(pc, 1, &ops[1])) {
+            (Some(cond), Some(target)) => (cond, target),
+            _ => return self.fallback(pc),
+        };
+        let target = match ops[1].mode {
+            ParameterMode::Immediate if ops[1].value >= 0 && !self.patched.contains(&(pc + 2)) => {
+                target
+            }
+            _ => format!("ic.checked_addr({}, {})?", pc, target),
+        };
+        let test = if instr == Instruction::JumpTrue {
+            "!="
+        } else {
+            "=="
+        };
+        if ops[0].mode == ParameterMode::Immediate && !self.patched.contains(&(pc + 1)) {
+            let taken = (ops[0].value != 0) == (instr == Instruction::JumpTrue);
+            let dst = if taken { target } else { next.to_string() };
+            self.line(format_args!("{}", dst));
+        } else {
+            self.line(format_args!("if {} {} 0 {{", cond, test));
+            self.line(format_args!("    {}", target));
+            self.line(format_args!("}} else {{"));
+            self.line(format_args!("    {}", next));
+            self.line(format_args!("}}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::intcode_io::{AsciiIO, FusedIO, VecIO};
+    use crate::util::parse_intcode;
+
+    type Compiled<IO> = fn(&mut IntCode<IO>) -> Result<()>;
+
+    const DAY5: &str = include_str!("../../../input/day5");
+    const DAY9: &str = include_str!("../../../input/day9");
+    const DAY19: &str = include_str!("../../../input/day19");
+    const DAY21: &str = include_str!("../../../input/day21");
+
+    /// Run `input` through both the interpreter and `compiled`, returning
+    /// both machines.
+    fn both<IO: Input + Output>(
+        input: &str,
+        io: impl Fn() -> IO,
+        compiled: Compiled<IO>,
+    ) -> (IntCode<IO>, IntCode<IO>) {
+        let code = parse_intcode(input).unwrap();
+        let mut interpreted = IntCode::new_from_device(code.clone(), io());
+        interpreted.run_till_end().unwrap();
+        let mut ic = IntCode::new_from_device(code, io());
+        compiled(&mut ic).unwrap();
+        assert!(ic.halt);
+        assert_eq!(ic.peek_range(0, 4096), interpreted.peek_range(0, 4096));
+        (interpreted, ic)
+    }
+
+    fn vec_io(input: &[Int]) -> impl Fn() -> FusedIO<VecIO, VecIO> + '_ {
+        move || FusedIO {
+            input: VecIO::input(input.to_vec()),
+            output: VecIO::default(),
+        }
+    }
+
+    #[test]
+    fn generated_code_is_current() {
+        for (name, input, generated) in &[
+            ("day5", DAY5, include_str!("day5.rs")),
+            ("day9", DAY9, include_str!("day9.rs")),
+            ("day19", DAY19, include_str!("day19.rs")),
+            ("day21", DAY21, include_str!("day21.rs")),
+        ] {
+            let code = parse_intcode(input).unwrap();
+            let source = transpile(&code, &format!("input/{}", name));
+            assert!(
+                source == *generated,
+                "src/intcode/aot/{0}.rs is out of date, run transpile input/{0}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn day5_matches_interpreter() {
+        for input in &[1, 5] {
+            let (expected, actual) = both(DAY5, vec_io(&[*input]), day5::run);
+            // compiled code doesn't count steps, so none ran interpreted
+            assert!(expected.steps() > 50);
+            assert_eq!(actual.steps(), 0);
+            assert_eq!(actual.into_device().output, expected.into_device().output);
+        }
+    }
+
+    #[test]
+    fn day9_matches_interpreter() {
+        for input in &[1, 2] {
+            let (expected, actual) = both(DAY9, vec_io(&[*input]), day9::run);
+            assert_eq!(actual.into_device().output, expected.into_device().output);
+        }
+    }
+
+    #[test]
+    fn day19_matches_interpreter() {
+        for y in 0..10 {
+            for x in 0..10 {
+                let (expected, actual) = both(DAY19, vec_io(&[x, y]), day19::run);
+                let (expected, actual) = (expected.into_device(), actual.into_device());
+                assert_eq!(actual.output, expected.output, "at ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn day21_matches_interpreter() {
+        let io = || {
+            let mut io = AsciiIO::new();
+            io.send(include_str!("../../challenges/day21/part1"));
+            io
+        };
+        let (expected, actual) = both(DAY21, io, day21::run);
+        let (expected, actual) = (expected.into_device(), actual.into_device());
+        assert_eq!(actual.text(), expected.text());
+        assert_eq!(actual.result(), expected.result());
+        assert!(actual.result().is_some());
+    }
+
+    #[test]
+    fn watched_machines_are_interpreted() {
+        let code = parse_intcode(DAY9).unwrap();
+        let mut ic = IntCode::new_from_device(code, vec_io(&[1])());
+        ic.set_journal(16);
+        day9::run(&mut ic).unwrap();
+        assert!(ic.halt);
+        assert!(ic.steps() > 0);
+        assert_eq!(ic.journal_len(), 16);
+        assert_eq!(ic.step_back(1).steps, 1);
+    }
+
+    #[test]
+    fn patched_code() {
+        // patches the operand of `out [0]` to print the 42 at the end
+        let source = transpile(&[1101, 9, 0, 5, 4, 0, 99, 0, 0, 42], "operand");
+        assert!(
+            source.contains("ic.inner[5] = ic.add(0, 9, 0)?;"),
+            "{}",
+            source
+        );
+        assert!(
+            source.contains("ic.write_output(4, ic.inner[ic.checked_addr(4, ic.inner[5])?])?;"),
+            "{}",
+            source
+        );
+
+        // turns `hlt` into `out`, compiled alongside the `hlt`
+        let source = transpile(&[1101, 0, 4, 4, 99, 0], "opcode");
+        assert!(
+            source.contains("ic.inner[4] = ic.add(0, 0, 4)?;"),
+            "{}",
+            source
+        );
+        assert!(source.contains("match ic.inner[4] {"), "{}", source);
+        assert!(
+            source.contains("4 => {\n                        // out [0]\n                        ic.write_output(4, ic.inner[0])?;"),
+            "{}",
+            source
+        );
+        assert!(
+            source.contains("99 => {\n                        // hlt\n                        return ic.halt_at(4);"),
+            "{}",
+            source
+        );
+    }
+}
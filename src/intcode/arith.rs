@@ -0,0 +1,80 @@
+use super::opcodes::Instruction;
+use super::{ErrorKind, Word};
+use anyhow::anyhow as ah;
+use std::fmt;
+use std::str::FromStr;
+
+/// What `add` and `mul` do when the result doesn't fit in an `Int`.
+///
+/// Machines wrap by default, which is what release builds always did. Under
+/// `cargo test` they check instead, so an overflow fails the test rather
+/// than producing a wrong answer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arithmetic {
+    Wrapping,
+    /// Stop with `ErrorKind::Overflow`.
+    Checked,
+    Saturating,
+}
+
+impl Default for Arithmetic {
+    fn default() -> Arithmetic {
+        if cfg!(test) {
+            Arithmetic::Checked
+        } else {
+            Arithmetic::Wrapping
+        }
+    }
+}
+
+impl Arithmetic {
+    pub(crate) fn apply<W: Word>(
+        self,
+        instr: Instruction,
+        lhs: W,
+        rhs: W,
+    ) -> Result<W, ErrorKind<W>> {
+        let (result, overflow) = match instr {
+            Instruction::Add => lhs.add_overflowing(&rhs),
+            Instruction::Mul => lhs.mul_overflowing(&rhs),
+            _ => unreachable!("{} is not arithmetic", instr.mnemonic()),
+        };
+        if !overflow {
+            return Ok(result);
+        }
+        match self {
+            Arithmetic::Wrapping => Ok(result),
+            Arithmetic::Saturating if instr == Instruction::Add => Ok(lhs.add_saturating(&rhs)),
+            Arithmetic::Saturating => Ok(lhs.mul_saturating(&rhs)),
+            Arithmetic::Checked => Err(ErrorKind::Overflow {
+                op: instr.mnemonic(),
+                lhs,
+                rhs,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Arithmetic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Arithmetic::Wrapping => "wrapping",
+            Arithmetic::Checked => "checked",
+            Arithmetic::Saturating => "saturating",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Arithmetic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Arithmetic> {
+        match s {
+            "wrapping" => Ok(Arithmetic::Wrapping),
+            "checked" => Ok(Arithmetic::Checked),
+            "saturating" => Ok(Arithmetic::Saturating),
+            _ => Err(ah!("unknown arithmetic '{}'", s)),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use super::opcodes::{encode_instruction, Instruction, ParameterMode, ParameterModes};
+use super::Int;
+use anyhow::{anyhow as ah, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref RE_LISTING: Regex =
+        Regex::new(r##"^\s*(?P<addr>\d+):\s+-?\d+(,-?\d+)*(\s+(?P<stmt>.*))?$"##).unwrap();
+    static ref RE_LABEL: Regex =
+        Regex::new(r##"^\s*(?P<label>[A-Za-z_][A-Za-z0-9_]*):(?P<rest>.*)$"##).unwrap();
+    static ref RE_EXPR: Regex =
+        Regex::new(r##"^(?P<label>[A-Za-z_][A-Za-z0-9_]*)?(?P<offset>[+-]?\d+)?$"##).unwrap();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Expr {
+    label: Option<String>,
+    offset: Int,
+}
+
+impl Expr {
+    fn parse(s: &str) -> Result<Expr> {
+        let s = s.trim();
+        let caps = RE_EXPR
+            .captures(s)
+            .filter(|_| !s.is_empty())
+            .ok_or_else(|| ah!("invalid expression '{}'", s))?;
+        let label = caps.name("label").map(|m| m.as_str().to_string());
+        let offset = match caps.name("offset") {
+            Some(m) => {
+                let digits = m.as_str();
+                if label.is_some() && !(digits.starts_with('+') || digits.starts_with('-')) {
+                    return Err(ah!("invalid expression '{}'", s));
+                }
+                crate::util::parse_str::<Int>(digits.trim_start_matches('+'))?
+            }
+            None => 0,
+        };
+        Ok(Expr { label, offset })
+    }
+
+    fn resolve(&self, labels: &HashMap<String, usize>) -> Result<Int> {
+        let base = match &self.label {
+            Some(l) => *labels
+                .get(l)
+                .ok_or_else(|| ah!("undefined label '{}'", l))? as Int,
+            None => 0,
+        };
+        base.checked_add(self.offset).ok_or_else(|| {
+            ah!(
+                "'{}{:+}' is out of range",
+                self.label.as_deref().unwrap_or(""),
+                self.offset
+            )
+        })
+    }
+}
+
+fn parse_operand(s: &str) -> Result<(ParameterMode, Expr)> {
+    let s = s.trim();
+    if let Some(imm) = s.strip_prefix('#') {
+        Ok((ParameterMode::Immediate, Expr::parse(imm)?))
+    } else if s.starts_with('[') && s.ends_with(']') {
+        Ok((ParameterMode::Position, Expr::parse(&s[1..s.len() - 1])?))
+    } else if let Some(offset) = s.strip_prefix("rb") {
+        let offset = offset.trim();
+        let expr = if offset.is_empty() {
+            Expr {
+                label: None,
+                offset: 0,
+            }
+        } else if let Some(pos) = offset.strip_prefix('+') {
+            Expr::parse(pos)?
+        } else if offset.starts_with('-') {
+            Expr::parse(offset)?
+        } else {
+            return Err(ah!("invalid relative operand '{}'", s));
+        };
+        Ok((ParameterMode::Relative, expr))
+    } else {
+        Err(ah!("invalid operand '{}'", s))
+    }
+}
+
+#[derive(Debug)]
+enum Statement {
+    Instr(Instruction, Vec<(ParameterMode, Expr)>),
+    Data(Vec<Expr>),
+}
+
+impl Statement {
+    fn parse(s: &str) -> Result<Statement> {
+        let s = s.trim();
+        let (head, tail) = match s.find(char::is_whitespace) {
+            Some(idx) => (&s[..idx], s[idx..].trim()),
+            None => (s, ""),
+        };
+        let args = if tail.is_empty() {
+            vec![]
+        } else {
+            tail.split(',').collect::<Vec<_>>()
+        };
+        if head == ".data" {
+            if args.is_empty() {
+                return Err(ah!(".data requires at least one value"));
+            }
+            return Ok(Statement::Data(
+                args.into_iter().map(Expr::parse).collect::<Result<_>>()?,
+            ));
+        }
+        let instr =
+            Instruction::from_mnemonic(head).ok_or_else(|| ah!("unknown mnemonic '{}'", head))?;
+        if args.len() != instr.arity() {
+            return Err(ah!(
+                "{} takes {} operands, found {}",
+                head,
+                instr.arity(),
+                args.len()
+            ));
+        }
+        Ok(Statement::Instr(
+            instr,
+            args.into_iter().map(parse_operand).collect::<Result<_>>()?,
+        ))
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Statement::Instr(instr, _) => 1 + instr.arity(),
+            Statement::Data(values) => values.len(),
+        }
+    }
+
+    fn emit(&self, labels: &HashMap<String, usize>, out: &mut Vec<Int>) -> Result<()> {
+        match self {
+            Statement::Instr(instr, operands) => {
+                let mut modes = ParameterModes::default();
+                for (idx, (mode, _)) in operands.iter().enumerate() {
+                    modes.inner[idx] = *mode;
+                }
+                out.push(encode_instruction(*instr, modes));
+                for (_, expr) in operands {
+                    out.push(expr.resolve(labels)?);
+                }
+            }
+            Statement::Data(values) => {
+                for expr in values {
+                    out.push(expr.resolve(labels)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Label names and the addresses they stand for.
+pub type Symbols = Vec<(String, usize)>;
+
+/// Assemble intcode source into a program.
+///
+/// Accepts the mnemonics and operand notation printed by the disassembler
+/// (`[pos]`, `#imm`, `rb+off`), `label:` definitions, `.data` directives and
+/// `;` comments. Lines of a disassembly listing are accepted as-is, with the
+/// leading address checked against where the statement assembles.
+pub fn assemble(source: &str) -> Result<Vec<Int>> {
+    assemble_with_symbols(source).map(|(intcode, _)| intcode)
+}
+
+/// Like `assemble`, also returning every label and its address, in address
+/// order.
+pub fn assemble_with_symbols(source: &str) -> Result<(Vec<Int>, Symbols)> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut addr = 0;
+
+    for (idx, raw) in source.lines().enumerate() {
+        let line_no = idx + 1;
+        let mut parse_line = || -> Result<Option<Statement>> {
+            let mut text = raw.split(';').next().unwrap_or("");
+            if let Some(caps) = RE_LISTING.captures(text) {
+                let listed = crate::util::parse_str::<usize>(&caps["addr"])?;
+                if listed != addr {
+                    return Err(ah!(
+                        "listing address {} does not match assembled address {}",
+                        listed,
+                        addr
+                    ));
+                }
+                text = caps.name("stmt").map(|m| m.as_str()).unwrap_or("");
+            }
+            while let Some(caps) = RE_LABEL.captures(text) {
+                let label = caps.name("label").unwrap().as_str();
+                if labels.insert(label.to_string(), addr).is_some() {
+                    return Err(ah!("duplicate label '{}'", label));
+                }
+                text = caps.name("rest").unwrap().as_str();
+            }
+            if text.trim().is_empty() {
+                Ok(None)
+            } else {
+                Statement::parse(text).map(Some)
+            }
+        };
+        if let Some(stmt) = parse_line().with_context(|| format!("line {}", line_no))? {
+            addr += stmt.size();
+            statements.push((line_no, stmt));
+        }
+    }
+
+    let mut intcode = Vec::with_capacity(addr);
+    for (line_no, stmt) in &statements {
+        stmt.emit(&labels, &mut intcode)
+            .with_context(|| format!("line {}", line_no))?;
+    }
+    let mut symbols = labels.into_iter().collect::<Vec<_>>();
+    symbols.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    Ok((intcode, symbols))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::disasm::{disassemble, Listing};
+    use crate::intcode::run_intcode;
+
+    #[test]
+    fn assemble_modes() {
+        let code = assemble("mul [4], #3, [4]\n.data 33").unwrap();
+        assert_eq!(code, vec![1002, 4, 3, 4, 33]);
+        let code = assemble("out rb-34\narb #19\nadd #1, #2, rb+5\nout rb\nhlt").unwrap();
+        assert_eq!(code, vec![204, -34, 109, 19, 21101, 1, 2, 5, 204, 0, 99]);
+    }
+
+    #[test]
+    fn assemble_labels() {
+        let source = "
+            ; count down from the input, emitting each value
+                    in [counter]
+            loop:   out [counter]
+                    add [counter], #-1, [counter]
+                    jt [counter], #loop
+                    hlt
+            counter: .data 0
+        ";
+        let code = assemble(source).unwrap();
+        assert_eq!(
+            code,
+            vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, 0]
+        );
+        let (_, out) = run_intcode(code, vec![3]).unwrap();
+        assert_eq!(out, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn assemble_label_offsets() {
+        let code = assemble("start: jt #1, #end-1\n.data start+2, 7\nend:").unwrap();
+        assert_eq!(code, vec![1105, 1, 4, 2, 7]);
+    }
+
+    #[test]
+    fn errors_have_line_numbers() {
+        let err = assemble("hlt\nfoo [1]").unwrap_err();
+        assert_eq!(format!("{:#}", err), "line 2: unknown mnemonic 'foo'");
+        let err = assemble("add #1, #2").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "line 1: add takes 3 operands, found 2"
+        );
+        let err = assemble("\n\njt #1, #nowhere").unwrap_err();
+        assert_eq!(format!("{:#}", err), "line 3: undefined label 'nowhere'");
+        let err = assemble("a: hlt\na: hlt").unwrap_err();
+        assert_eq!(format!("{:#}", err), "line 2: duplicate label 'a'");
+        let err = assemble("out {3}").unwrap_err();
+        assert_eq!(format!("{:#}", err), "line 1: invalid operand '{3}'");
+        let err = assemble("hlt\nend: .data end+9223372036854775807").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "line 2: 'end+9223372036854775807' is out of range"
+        );
+    }
+
+    #[test]
+    fn listing_address_is_checked() {
+        let err = assemble("    0: 99   hlt\n    2: 99   hlt").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "line 2: listing address 2 does not match assembled address 1"
+        );
+    }
+
+    #[test]
+    fn roundtrip_disassembly() {
+        for input in &[
+            include_str!("../../input/day5"),
+            include_str!("../../input/day9"),
+            include_str!("../../input/day25"),
+        ] {
+            let intcode = crate::util::parse_intcode(input).unwrap();
+            let listing = Listing(&disassemble(&intcode)).to_string();
+            assert_eq!(assemble(&listing).unwrap(), intcode);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use super::disasm::{Line, Operand, Statement};
+use super::opcodes::{Instruction, ParameterMode};
+use super::Int;
+use petgraph::graph::{DiGraph, NodeIndex};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// How control gets from one block to the next.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Edge {
+    Jump,
+    Fallthrough,
+}
+
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Edge::Jump => write!(f, "jump"),
+            Edge::Fallthrough => write!(f, "next"),
+        }
+    }
+}
+
+/// A straight run of instructions, only entered at the top.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub lines: Vec<Line>,
+}
+
+impl Block {
+    pub fn start(&self) -> usize {
+        self.lines[0].addr
+    }
+
+    /// The address just past the last instruction.
+    pub fn end(&self) -> usize {
+        let last = self.last();
+        last.addr + last.stmt.size()
+    }
+
+    fn last(&self) -> &Line {
+        self.lines.last().unwrap()
+    }
+
+    /// Whether the block ends in a jump whose target is only known at run
+    /// time.
+    pub fn dynamic_jump(&self) -> bool {
+        matches!(jump(&self.last().stmt), Some((_, None)))
+    }
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}: {}", line.addr, line.stmt)?;
+        }
+        if self.dynamic_jump() {
+            writeln!(f, "-> ?")?;
+        }
+        Ok(())
+    }
+}
+
+/// The condition and, when it is immediate, the target of a jump.
+fn jump(stmt: &Statement) -> Option<(Operand, Option<usize>)> {
+    match stmt {
+        Statement::Instr { instr, operands }
+            if *instr == Instruction::JumpTrue || *instr == Instruction::JumpFalse =>
+        {
+            let target = match operands[1] {
+                Operand {
+                    mode: ParameterMode::Immediate,
+                    value,
+                } if value >= 0 => Some(value as usize),
+                _ => None,
+            };
+            Some((operands[0], target))
+        }
+        _ => None,
+    }
+}
+
+/// Where control can statically go after `line`. A jump with an immediate
+/// condition only has the one successor it takes.
+pub(crate) fn successors(line: &Line) -> Vec<(usize, Edge)> {
+    let next = (line.addr + line.stmt.size(), Edge::Fallthrough);
+    let (instr, cond, target) = match &line.stmt {
+        Statement::Data(_) => return vec![],
+        Statement::Instr {
+            instr: Instruction::Halt,
+            ..
+        } => return vec![],
+        Statement::Instr { instr, .. } => match jump(&line.stmt) {
+            Some((cond, target)) => (*instr, cond, target),
+            None => return vec![next],
+        },
+    };
+    let taken = match cond.mode {
+        ParameterMode::Immediate => Some((cond.value != 0) == (instr == Instruction::JumpTrue)),
+        _ => None,
+    };
+    let mut succ = Vec::new();
+    if let (Some(target), Some(true) | None) = (target, taken) {
+        succ.push((target, Edge::Jump));
+    }
+    if taken != Some(true) {
+        succ.push(next);
+    }
+    succ
+}
+
+/// The control-flow graph of the code reachable from address 0, found
+/// without running the program.
+///
+/// Jumps are only followed when their target is immediate; the others are
+/// marked with `Block::dynamic_jump` and have no outgoing jump edge. The
+/// code is read as it is before the program starts, so self-modifying
+/// programs can have paths that aren't in the graph.
+#[derive(Debug, Clone)]
+pub struct Cfg {
+    graph: DiGraph<Block, Edge>,
+    blocks: HashMap<usize, NodeIndex>,
+}
+
+impl Cfg {
+    pub fn build(intcode: &[Int]) -> Cfg {
+        Cfg::with_entries(intcode, &[0])
+    }
+
+    /// The graph of the code reachable from any of `entries`, for when more
+    /// is known about where dynamic jumps go.
+    pub fn with_entries(intcode: &[Int], entries: &[usize]) -> Cfg {
+        let decode = |addr: usize| Line::from_words(addr, &intcode[addr.min(intcode.len())..]);
+
+        // find every reachable instruction and where blocks must start
+        let mut leaders = entries.iter().cloned().collect::<BTreeSet<_>>();
+        let mut seen = BTreeSet::new();
+        let mut todo = entries.to_vec();
+        while let Some(addr) = todo.pop() {
+            if addr >= intcode.len() || !seen.insert(addr) {
+                continue;
+            }
+            let line = decode(addr);
+            let succ = successors(&line);
+            let branches = jump(&line.stmt).is_some();
+            for (next, _) in succ {
+                if branches {
+                    leaders.insert(next);
+                }
+                todo.push(next);
+            }
+        }
+
+        let mut graph = DiGraph::new();
+        let mut blocks = HashMap::new();
+        let mut exits = Vec::new();
+        for &start in leaders.iter().filter(|addr| seen.contains(addr)) {
+            let mut lines = vec![decode(start)];
+            loop {
+                let last = lines.last().unwrap();
+                let succ = successors(last);
+                match succ.as_slice() {
+                    [(next, Edge::Fallthrough)]
+                        if !leaders.contains(next) && seen.contains(next) =>
+                    {
+                        lines.push(decode(*next))
+                    }
+                    _ => {
+                        exits.push((start, succ));
+                        break;
+                    }
+                }
+            }
+            blocks.insert(start, graph.add_node(Block { lines }));
+        }
+        for (start, succ) in exits {
+            for (next, edge) in succ {
+                if let Some(&to) = blocks.get(&next) {
+                    graph.add_edge(blocks[&start], to, edge);
+                }
+            }
+        }
+        Cfg { graph, blocks }
+    }
+
+    pub fn graph(&self) -> &DiGraph<Block, Edge> {
+        &self.graph
+    }
+
+    /// The block starting at `addr`.
+    pub fn block(&self, addr: usize) -> Option<&Block> {
+        self.blocks.get(&addr).map(|idx| &self.graph[*idx])
+    }
+
+    /// Every block, in address order.
+    pub fn blocks(&self) -> Vec<&Block> {
+        let mut blocks = self
+            .graph
+            .raw_nodes()
+            .iter()
+            .map(|n| &n.weight)
+            .collect::<Vec<_>>();
+        blocks.sort_by_key(|b| b.start());
+        blocks
+    }
+
+    /// Start addresses of the blocks control can move to from the block
+    /// starting at `addr`.
+    pub fn successors(&self, addr: usize) -> Vec<usize> {
+        let mut succ = match self.blocks.get(&addr) {
+            Some(idx) => self
+                .graph
+                .neighbors(*idx)
+                .map(|n| self.graph[n].start())
+                .collect(),
+            None => vec![],
+        };
+        succ.sort_unstable();
+        succ
+    }
+
+    pub fn dot(&self) -> String {
+        format!("{}", petgraph::dot::Dot::new(&self.graph))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    /// Counts down from the input, with an indirect jump at the end.
+    const COUNTDOWN: &str = "
+                in [n]
+        loop:   jf [n], #done
+                add [n], #-1, [n]
+                out [n]
+                jt #1, #loop
+        done:   jt #1, [ret]
+        n:      .data 0
+        ret:    .data 0
+    ";
+
+    #[test]
+    fn splits_at_jumps() {
+        let code = assemble(COUNTDOWN).unwrap();
+        let cfg = Cfg::build(&code);
+        let starts = cfg.blocks().iter().map(|b| b.start()).collect::<Vec<_>>();
+        assert_eq!(starts, vec![0, 2, 5, 14]);
+        assert_eq!(cfg.successors(0), vec![2]);
+        assert_eq!(cfg.successors(2), vec![5, 14]);
+        assert_eq!(cfg.successors(5), vec![2]);
+        assert_eq!(cfg.block(5).unwrap().end(), 14);
+        assert_eq!(cfg.graph().edge_count(), 4);
+
+        let done = cfg.block(14).unwrap();
+        assert!(done.dynamic_jump());
+        assert!(cfg.successors(14).is_empty());
+        assert!(!cfg.block(2).unwrap().dynamic_jump());
+        // the data after the last jump is never decoded
+        assert!(cfg.block(17).is_none());
+    }
+
+    #[test]
+    fn dot_output() {
+        let code = assemble(COUNTDOWN).unwrap();
+        let dot = Cfg::build(&code).dot();
+        assert!(dot.starts_with("digraph {"), "{}", dot);
+        assert!(dot.contains("\"2: jf [17], #14\\l\""), "{}", dot);
+        assert!(dot.contains("\"14: jt #1, [18]\\l-> ?\\l\""), "{}", dot);
+        assert!(dot.contains("[ label = \"jump\" ]"), "{}", dot);
+    }
+
+    #[test]
+    fn decodes_day9() {
+        let code = crate::util::parse_intcode(include_str!("../../input/day9")).unwrap();
+        let cfg = Cfg::build(&code);
+        let blocks = cfg.blocks();
+        assert_eq!(blocks[0].start(), 0);
+        assert!(blocks.len() > 10);
+        assert!(blocks.iter().any(|b| b.dynamic_jump()));
+        for block in blocks {
+            for succ in cfg.successors(block.start()) {
+                assert!(cfg.block(succ).is_some());
+            }
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use super::disasm::Line;
+use super::intcode_io::{Input, Output};
+use super::{DenseMemory, Int, IntCode, Memory, Rewind};
+use anyhow::{anyhow as ah, Context, Result};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{BufRead, Write};
+
+/// Instructions the debugger keeps to step back over.
+const HISTORY: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchKind {
+    Read,
+    Write,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stop {
+    Stepped,
+    Breakpoint(usize),
+    Watch {
+        kind: WatchKind,
+        addr: usize,
+        pc: usize,
+    },
+    Halted,
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::Stepped => write!(f, "stepped"),
+            Stop::Breakpoint(pc) => write!(f, "breakpoint at {}", pc),
+            Stop::Watch { kind, addr, pc } => {
+                let verb = match kind {
+                    WatchKind::Read => "read",
+                    WatchKind::Write => "written",
+                };
+                write!(f, "[{}] {} by instruction at {}", addr, verb, pc)
+            }
+            Stop::Halted => write!(f, "halted"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Registers {
+    pub pc: usize,
+    pub relative_base: Int,
+}
+
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pc={} rb={}", self.pc, self.relative_base)
+    }
+}
+
+#[derive(Debug)]
+pub struct Debugger<IO, M: Memory = DenseMemory> {
+    ic: IntCode<IO, M>,
+    breakpoints: BTreeSet<usize>,
+    watch_read: BTreeSet<usize>,
+    watch_write: BTreeSet<usize>,
+}
+
+impl<IO, M: Memory<Word = Int>> Debugger<IO, M> {
+    pub fn new(mut ic: IntCode<IO, M>) -> Debugger<IO, M> {
+        ic.set_journal(HISTORY);
+        Debugger {
+            ic,
+            breakpoints: BTreeSet::new(),
+            watch_read: BTreeSet::new(),
+            watch_write: BTreeSet::new(),
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn remove_breakpoint(&mut self, pc: usize) -> bool {
+        self.breakpoints.remove(&pc)
+    }
+
+    pub fn watch(&mut self, addr: usize, kind: WatchKind) {
+        match kind {
+            WatchKind::Read => self.watch_read.insert(addr),
+            WatchKind::Write => self.watch_write.insert(addr),
+        };
+    }
+
+    pub fn unwatch(&mut self, addr: usize) -> bool {
+        let r = self.watch_read.remove(&addr);
+        let w = self.watch_write.remove(&addr);
+        r || w
+    }
+
+    pub fn registers(&self) -> Registers {
+        Registers {
+            pc: self.ic.pc,
+            relative_base: self.ic.relative_base,
+        }
+    }
+
+    pub fn memory(&self, start: usize, len: usize) -> Vec<Int> {
+        self.ic.peek_range(start, len)
+    }
+
+    /// Disassemble `count` instructions starting at `addr`.
+    pub fn listing(&self, mut addr: usize, count: usize) -> Vec<Line> {
+        let mut lines = Vec::with_capacity(count);
+        for _ in 0..count {
+            let line = Line::from_words(addr, &self.ic.peek_range(addr, 4));
+            addr += line.stmt.size();
+            lines.push(line);
+        }
+        lines
+    }
+
+    pub fn current(&self) -> Line {
+        self.listing(self.ic.pc, 1).remove(0)
+    }
+
+    pub fn into_inner(self) -> IntCode<IO, M> {
+        self.ic
+    }
+
+    fn rewound<W: Write>(&self, rewind: Rewind, out: &mut W) -> Result<()> {
+        writeln!(
+            out,
+            "back {} steps, {} more kept",
+            rewind.steps,
+            self.ic.journal_len()
+        )?;
+        if !rewind.outputs.is_empty() {
+            writeln!(out, "undid output {:?}", rewind.outputs)?;
+        }
+        writeln!(out, "{}", self.current())?;
+        Ok(())
+    }
+}
+
+impl<IO: Input + Output, M: Memory<Word = Int>> Debugger<IO, M> {
+    /// Execute exactly one instruction, reporting any watchpoint it tripped.
+    pub fn step(&mut self) -> Result<Stop> {
+        if self.ic.halt {
+            return Ok(Stop::Halted);
+        }
+        let pc = self.ic.pc;
+        let accesses = self.ic.pending_accesses().ok();
+        self.ic
+            .run_one()
+            .with_context(|| format!("executing: {}", self.current()))?;
+        if let Some(acc) = accesses {
+            if let Some(addr) = acc.write.filter(|a| self.watch_write.contains(a)) {
+                return Ok(Stop::Watch {
+                    kind: WatchKind::Write,
+                    addr,
+                    pc,
+                });
+            }
+            if let Some(addr) = acc.reads.into_iter().find(|a| self.watch_read.contains(a)) {
+                return Ok(Stop::Watch {
+                    kind: WatchKind::Read,
+                    addr,
+                    pc,
+                });
+            }
+        }
+        Ok(if self.ic.halt {
+            Stop::Halted
+        } else {
+            Stop::Stepped
+        })
+    }
+
+    /// Run until a breakpoint, watchpoint or halt.
+    ///
+    /// A breakpoint on the current `pc` does not stop us, so that repeated
+    /// calls make progress.
+    pub fn cont(&mut self) -> Result<Stop> {
+        loop {
+            match self.step()? {
+                Stop::Stepped => {}
+                stop => return Ok(stop),
+            }
+            if self.breakpoints.contains(&self.ic.pc) {
+                return Ok(Stop::Breakpoint(self.ic.pc));
+            }
+        }
+    }
+
+    /// Drive the debugger from line-based commands until `quit` or EOF.
+    pub fn repl<R: BufRead, W: Write>(&mut self, input: R, mut out: W) -> Result<()> {
+        writeln!(out, "{}", self.current())?;
+        for line in input.lines() {
+            let line = line?;
+            let mut words = line.split_whitespace();
+            let cmd = match words.next() {
+                Some(c) => c,
+                None => continue,
+            };
+            let args = words
+                .map(crate::util::parse_str::<usize>)
+                .collect::<Result<Vec<_>>>();
+            let args = match args {
+                Ok(a) => a,
+                Err(e) => {
+                    writeln!(out, "bad argument: {}", e)?;
+                    continue;
+                }
+            };
+            let arg = |idx: usize| args.get(idx).cloned();
+            match self.command(cmd, &arg, &mut out) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(e) => writeln!(out, "error: {:#}", e)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn command<W: Write, F: Fn(usize) -> Option<usize>>(
+        &mut self,
+        cmd: &str,
+        arg: &F,
+        out: &mut W,
+    ) -> Result<bool> {
+        let required = |idx: usize| arg(idx).ok_or_else(|| ah!("{} requires an address", cmd));
+        match cmd {
+            "s" | "step" => {
+                let mut stop = Stop::Stepped;
+                for _ in 0..arg(0).unwrap_or(1) {
+                    stop = self.step()?;
+                    if stop != Stop::Stepped {
+                        break;
+                    }
+                }
+                writeln!(out, "{}\n{}", stop, self.current())?;
+            }
+            "c" | "continue" => {
+                let stop = self.cont()?;
+                writeln!(out, "{}\n{}", stop, self.current())?;
+            }
+            "b" | "break" => self.add_breakpoint(required(0)?),
+            "d" | "delete" => {
+                let pc = required(0)?;
+                if !self.remove_breakpoint(pc) {
+                    writeln!(out, "no breakpoint at {}", pc)?;
+                }
+            }
+            "wr" | "watch-read" => self.watch(required(0)?, WatchKind::Read),
+            "ww" | "watch-write" => self.watch(required(0)?, WatchKind::Write),
+            "uw" | "unwatch" => {
+                let addr = required(0)?;
+                if !self.unwatch(addr) {
+                    writeln!(out, "no watchpoint on {}", addr)?;
+                }
+            }
+            "bs" | "back" => {
+                let rewind = self.ic.step_back(arg(0).unwrap_or(1) as u64);
+                self.rewound(rewind, out)?;
+            }
+            "bt" | "back-to" => {
+                let rewind = self.ic.run_back_to(required(0)?);
+                self.rewound(rewind, out)?;
+            }
+            "di" | "discard-input" => {
+                let dropped = self.ic.discard_input();
+                writeln!(out, "discarded input {:?}", dropped)?;
+            }
+            "r" | "regs" => writeln!(out, "{}", self.registers())?,
+            "x" | "mem" => {
+                let start = required(0)?;
+                let words = self.memory(start, arg(1).unwrap_or(1));
+                for (row, chunk) in words.chunks(8).enumerate() {
+                    let values = chunk
+                        .iter()
+                        .map(|w| format!("{:>8}", w))
+                        .collect::<String>();
+                    writeln!(out, "{:>5}:{}", start + row * 8, values)?;
+                }
+            }
+            "l" | "list" => {
+                let start = arg(0).unwrap_or(self.ic.pc);
+                for line in self.listing(start, arg(1).unwrap_or(10)) {
+                    writeln!(out, "{}", line)?;
+                }
+            }
+            "q" | "quit" => return Ok(false),
+            _ => writeln!(
+                out,
+                "commands: step [n], continue, back [n], back-to <pc>, discard-input, \
+                 break <pc>, delete <pc>, watch-read <addr>, watch-write <addr>, \
+                 unwatch <addr>, regs, mem <addr> [len], list [addr] [n], quit"
+            )?,
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm::assemble;
+    use crate::intcode::intcode_io::{FusedIO, VecIO};
+
+    fn debugger(source: &str, input: Vec<Int>) -> Debugger<FusedIO<VecIO, VecIO>> {
+        let intcode = assemble(source).unwrap();
+        Debugger::new(IntCode::new(intcode, VecIO::input(input), VecIO::default()))
+    }
+
+    const COUNTDOWN: &str = "
+                in [counter]
+        loop:   out [counter]
+                add [counter], #-1, [counter]
+                jt [counter], #loop
+                hlt
+        counter: .data 0
+    ";
+
+    #[test]
+    fn single_step() {
+        let mut dbg = debugger(COUNTDOWN, vec![2]);
+        assert_eq!(dbg.step().unwrap(), Stop::Stepped);
+        assert_eq!(dbg.registers().pc, 2);
+        assert_eq!(
+            dbg.current().to_string().trim(),
+            "2: 4,12                 out [12]"
+        );
+    }
+
+    #[test]
+    fn breakpoints() {
+        let mut dbg = debugger(COUNTDOWN, vec![3]);
+        dbg.add_breakpoint(2);
+        assert_eq!(dbg.cont().unwrap(), Stop::Breakpoint(2));
+        assert_eq!(dbg.cont().unwrap(), Stop::Breakpoint(2));
+        assert_eq!(dbg.memory(12, 1), vec![2]);
+        assert!(dbg.remove_breakpoint(2));
+        assert_eq!(dbg.cont().unwrap(), Stop::Halted);
+        let io = dbg.into_inner().into_device();
+        assert_eq!(io.output.into_vec(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn watchpoints() {
+        let mut dbg = debugger(COUNTDOWN, vec![3]);
+        dbg.watch(12, WatchKind::Write);
+        let write = |pc| Stop::Watch {
+            kind: WatchKind::Write,
+            addr: 12,
+            pc,
+        };
+        assert_eq!(dbg.cont().unwrap(), write(0));
+        assert_eq!(dbg.cont().unwrap(), write(4));
+        dbg.unwatch(12);
+        dbg.watch(12, WatchKind::Read);
+        assert_eq!(
+            dbg.cont().unwrap(),
+            Stop::Watch {
+                kind: WatchKind::Read,
+                addr: 12,
+                pc: 8
+            }
+        );
+    }
+
+    #[test]
+    fn errors_carry_context() {
+        let mut dbg = debugger(".data 42", vec![]);
+        let err = dbg.step().unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "executing:     0: 42                   .data 42: unknown opcode 42 (pc=0 word=42 rb=0)"
+        );
+    }
+
+    #[test]
+    fn repl_session() {
+        let mut dbg = debugger(COUNTDOWN, vec![1]);
+        let script = "b 11\nc\nr\nx 12\nbogus 1\nq\n";
+        let mut out = Vec::new();
+        dbg.repl(script.as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], "breakpoint at 11");
+        assert_eq!(lines[3], "pc=11 rb=0");
+        assert_eq!(lines[4], "   12:       0");
+        assert!(lines[5].starts_with("commands:"));
+    }
+
+    #[test]
+    fn stepping_back() {
+        let mut dbg = debugger(COUNTDOWN, vec![2]);
+        let script = "b 2\nc\nc\nbs 2\nbt 0\ndi\nbs\nq\n";
+        let mut out = Vec::new();
+        dbg.repl(script.as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines[5], "back 2 steps, 2 more kept");
+        assert_eq!(
+            lines[6].trim(),
+            "4: 1001,12,-1,12        add [12], #-1, [12]"
+        );
+        assert_eq!(lines[7], "back 2 steps, 0 more kept");
+        assert_eq!(lines[8], "undid output [2]");
+        assert_eq!(lines[10], "discarded input [2]");
+        assert_eq!(lines[11], "back 0 steps, 0 more kept");
+        assert_eq!(dbg.registers().pc, 0);
+        assert_eq!(dbg.memory(12, 1), vec![0]);
+    }
+}
@@ -0,0 +1,194 @@
+use super::opcodes::{encode_instruction, parse_instruction, Instruction, ParameterMode};
+use super::Int;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Operand {
+    pub mode: ParameterMode,
+    pub value: Int,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            ParameterMode::Position => write!(f, "[{}]", self.value),
+            ParameterMode::Immediate => write!(f, "#{}", self.value),
+            ParameterMode::Relative if self.value < 0 => write!(f, "rb{}", self.value),
+            ParameterMode::Relative => write!(f, "rb+{}", self.value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Instr {
+        instr: Instruction,
+        operands: Vec<Operand>,
+    },
+    Data(Int),
+}
+
+impl Statement {
+    /// Decode the statement starting at `words[0]`.
+    ///
+    /// Anything which would not re-encode to the exact same words (unknown
+    /// opcodes, stray mode digits, operands running off the end) is data.
+    pub fn decode(words: &[Int]) -> Statement {
+        let word = match words.first() {
+            Some(w) => *w,
+            None => return Statement::Data(0),
+        };
+        let (instr, modes) = match parse_instruction(word) {
+            Ok(decoded) => decoded,
+            Err(_) => return Statement::Data(word),
+        };
+        if encode_instruction(instr, modes) != word || words.len() <= instr.arity() {
+            return Statement::Data(word);
+        }
+        let operands = modes
+            .inner
+            .iter()
+            .zip(&words[1..=instr.arity()])
+            .map(|(mode, value)| Operand {
+                mode: *mode,
+                value: *value,
+            })
+            .collect();
+        Statement::Instr { instr, operands }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Statement::Instr { operands, .. } => 1 + operands.len(),
+            Statement::Data(_) => 1,
+        }
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Statement::Instr { instr, operands } => {
+                write!(f, "{}", instr.mnemonic())?;
+                for (idx, op) in operands.iter().enumerate() {
+                    if idx == 0 {
+                        write!(f, " {}", op)?;
+                    } else {
+                        write!(f, ", {}", op)?;
+                    }
+                }
+                Ok(())
+            }
+            Statement::Data(x) => write!(f, ".data {}", x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub addr: usize,
+    pub words: Vec<Int>,
+    pub stmt: Statement,
+}
+
+impl Line {
+    pub fn decode(intcode: &[Int], addr: usize) -> Line {
+        Line::from_words(addr, &intcode[addr..])
+    }
+
+    /// Decode a line from `words`, which start at `addr` in the program.
+    pub fn from_words(addr: usize, words: &[Int]) -> Line {
+        let stmt = Statement::decode(words);
+        let words = words[..stmt.size().min(words.len())].to_vec();
+        Line { addr, words, stmt }
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let words = self
+            .words
+            .iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{:>5}: {:<20} {}", self.addr, words, self.stmt)
+    }
+}
+
+pub fn disassemble(intcode: &[Int]) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut addr = 0;
+    while addr < intcode.len() {
+        let line = Line::decode(intcode, addr);
+        addr += line.stmt.size();
+        lines.push(line);
+    }
+    lines
+}
+
+pub struct Listing<'a>(pub &'a [Line]);
+
+impl<'a> fmt::Display for Listing<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.0 {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(intcode: &[Int]) -> Vec<String> {
+        disassemble(intcode)
+            .iter()
+            .map(|l| {
+                l.to_string()
+                    .split_whitespace()
+                    .skip(2)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn operand_notation() {
+        assert_eq!(
+            render(&[1002, 4, 3, 4, 33]),
+            vec!["mul [4], #3, [4]", ".data 33"]
+        );
+        assert_eq!(render(&[204, -34, 109, 19]), vec!["out rb-34", "arb #19"]);
+        assert_eq!(
+            render(&[21101, 1, 2, 5, 99]),
+            vec!["add #1, #2, rb+5", "hlt"]
+        );
+    }
+
+    #[test]
+    fn listing_columns() {
+        let lines = disassemble(&[1105, 1, 7]);
+        assert_eq!(
+            lines[0].to_string(),
+            "    0: 1105,1,7             jt #1, #7"
+        );
+    }
+
+    #[test]
+    fn undecodable_words_are_data() {
+        // bad opcode, stray mode digit past arity, negative, truncated operands
+        assert_eq!(
+            render(&[42, 10099, -1, 1101, 1]),
+            vec![
+                ".data 42",
+                ".data 10099",
+                ".data -1",
+                ".data 1101",
+                ".data 1"
+            ]
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use super::{Int, Word};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ErrorKind<W = Int> {
+    InvalidOpcode(W),
+    InvalidParameterMode(Int),
+    NegativeAddress(W),
+    AddressOutOfRange(W),
+    WriteToImmediate,
+    InputExhausted,
+    InfiniteLoop,
+    Overflow { op: &'static str, lhs: W, rhs: W },
+    Io(anyhow::Error),
+}
+
+impl ErrorKind {
+    /// The same error, for a machine with a wider word.
+    pub(crate) fn widen<W: Word>(self) -> ErrorKind<W> {
+        match self {
+            ErrorKind::InvalidOpcode(n) => ErrorKind::InvalidOpcode(W::from(n)),
+            ErrorKind::InvalidParameterMode(m) => ErrorKind::InvalidParameterMode(m),
+            ErrorKind::NegativeAddress(a) => ErrorKind::NegativeAddress(W::from(a)),
+            ErrorKind::AddressOutOfRange(a) => ErrorKind::AddressOutOfRange(W::from(a)),
+            ErrorKind::WriteToImmediate => ErrorKind::WriteToImmediate,
+            ErrorKind::InputExhausted => ErrorKind::InputExhausted,
+            ErrorKind::InfiniteLoop => ErrorKind::InfiniteLoop,
+            ErrorKind::Overflow { op, lhs, rhs } => ErrorKind::Overflow {
+                op,
+                lhs: W::from(lhs),
+                rhs: W::from(rhs),
+            },
+            ErrorKind::Io(e) => ErrorKind::Io(e),
+        }
+    }
+}
+
+impl<W: fmt::Display> fmt::Display for ErrorKind<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidOpcode(n) => write!(f, "unknown opcode {:02}", n),
+            ErrorKind::InvalidParameterMode(m) => write!(f, "unknown parameter mode: {}", m),
+            ErrorKind::NegativeAddress(addr) => write!(f, "negative address {}", addr),
+            ErrorKind::AddressOutOfRange(addr) => write!(f, "address {} out of range", addr),
+            ErrorKind::WriteToImmediate => write!(f, "write to immediate operand"),
+            ErrorKind::InputExhausted => write!(f, "no more input"),
+            ErrorKind::InfiniteLoop => write!(f, "machine state repeated without io"),
+            ErrorKind::Overflow { op, lhs, rhs } => {
+                write!(f, "arithmetic overflow: {} {}, {}", op, lhs, rhs)
+            }
+            ErrorKind::Io(e) => write!(f, "io device failed: {}", e),
+        }
+    }
+}
+
+/// A machine fault, along with where the machine was when it happened.
+#[derive(Debug)]
+pub struct IntCodeError<W = Int> {
+    pub kind: ErrorKind<W>,
+    pub pc: usize,
+    pub word: W,
+    pub relative_base: Int,
+}
+
+impl<W: fmt::Display> fmt::Display for IntCodeError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (pc={} word={} rb={})",
+            self.kind, self.pc, self.word, self.relative_base
+        )
+    }
+}
+
+/// An `Io` fault's cause is part of its message rather than its `source`, so
+/// it isn't printed twice when the error is shown with its chain.
+impl<W: fmt::Debug + fmt::Display> std::error::Error for IntCodeError<W> {}
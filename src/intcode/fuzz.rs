@@ -0,0 +1,560 @@
+use super::disasm::{disassemble, Listing};
+use super::intcode_io::VecIO;
+use super::opcodes::{encode_instruction, Instruction, ParameterMode, ParameterModes};
+use super::{Arithmetic, ErrorKind, Int, IntCode};
+use std::collections::HashMap;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Writes at or past this address end a production run early, rather than
+/// have a random program allocate all of memory.
+const MEMORY_LIMIT: usize = 1 << 16;
+
+/// Words after the code for generated programs to use as data.
+const DATA_WORDS: usize = 16;
+
+/// A small seeded PRNG (splitmix64), so runs can be repeated.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `lo..hi`.
+    pub fn range(&mut self, lo: Int, hi: Int) -> Int {
+        lo + (self.next_u64() % (hi - lo) as u64) as Int
+    }
+
+    /// True one time in `n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(0, n as Int) == 0
+    }
+}
+
+/// A program and the input to run it with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub program: Vec<Int>,
+    pub input: Vec<Int>,
+}
+
+impl Case {
+    /// A random program which is mostly well formed: valid instructions
+    /// addressing its own code and data, jumping to instruction boundaries
+    /// and ending in `hlt`. Now and then it has a bad word or operand, to
+    /// check faults match too.
+    pub fn generate(rng: &mut Rng) -> Case {
+        const INSTRUCTIONS: &[Instruction] = &[
+            Instruction::Add,
+            Instruction::Mul,
+            Instruction::Input,
+            Instruction::Output,
+            Instruction::JumpTrue,
+            Instruction::JumpFalse,
+            Instruction::LessThan,
+            Instruction::EqualTo,
+            Instruction::SetBase,
+        ];
+        let count = rng.range(1, 30) as usize;
+        let mut instrs = (0..count)
+            .map(|_| match rng.range(0, 40) {
+                0 => Instruction::Halt,
+                n => INSTRUCTIONS[n as usize % INSTRUCTIONS.len()],
+            })
+            .collect::<Vec<_>>();
+        instrs.push(Instruction::Halt);
+        let mut starts = Vec::new();
+        let mut len = 0;
+        for instr in &instrs {
+            starts.push(len as Int);
+            len += 1 + instr.arity();
+        }
+        let total = (len + DATA_WORDS) as Int;
+
+        let mut program = Vec::new();
+        for instr in instrs {
+            if rng.one_in(50) {
+                program.push(rng.range(-200, 30000));
+                continue;
+            }
+            let mut modes = ParameterModes::default();
+            let mut args = Vec::new();
+            for nth in 0..instr.arity() {
+                let writes = match instr {
+                    Instruction::Input => true,
+                    Instruction::Output | Instruction::JumpTrue | Instruction::JumpFalse => false,
+                    Instruction::SetBase => false,
+                    _ => nth == 2,
+                };
+                let target = nth == 1 && !writes && instr.arity() == 2;
+                let mode = match rng.range(0, 10) {
+                    _ if instr == Instruction::SetBase => ParameterMode::Immediate,
+                    0..=3 => ParameterMode::Position,
+                    4..=5 => ParameterMode::Relative,
+                    _ if writes && !rng.one_in(20) => ParameterMode::Position,
+                    _ => ParameterMode::Immediate,
+                };
+                let arg = match mode {
+                    _ if instr == Instruction::SetBase => rng.range(-3, 10),
+                    ParameterMode::Immediate if target => match rng.one_in(10) {
+                        true => rng.range(-1, total),
+                        false => starts[rng.range(0, starts.len() as Int) as usize],
+                    },
+                    ParameterMode::Immediate => rng.range(-20, 20),
+                    ParameterMode::Position if rng.one_in(5) => rng.range(0, len as Int),
+                    ParameterMode::Position => rng.range(len as Int, total),
+                    ParameterMode::Relative => rng.range(-5, total),
+                };
+                modes.inner[nth] = mode;
+                args.push(arg);
+            }
+            program.push(encode_instruction(instr, modes));
+            program.extend(args);
+        }
+        for _ in 0..DATA_WORDS {
+            program.push(rng.range(-10, 10));
+        }
+        let input = (0..rng.range(0, 8)).map(|_| rng.range(-50, 50)).collect();
+        Case { program, input }
+    }
+
+    /// Smaller, simpler cases, most promising first.
+    fn shrink_candidates(&self) -> Vec<Case> {
+        let mut out = Vec::new();
+        for idx in 0..self.input.len() {
+            let mut case = self.clone();
+            case.input.remove(idx);
+            out.push(case);
+        }
+        for len in [self.program.len() / 2, self.program.len() - 1].iter() {
+            if *len < self.program.len() {
+                let mut case = self.clone();
+                case.program.truncate(*len);
+                out.push(case);
+            }
+        }
+        for idx in 0..self.program.len() {
+            let mut case = self.clone();
+            case.program.remove(idx);
+            out.push(case);
+        }
+        for idx in 0..self.program.len() {
+            let word = self.program[idx];
+            for simpler in [0, 99, word / 2].iter() {
+                if simpler.abs() < word.abs() || (*simpler == 99 && word != 99 && word != 0) {
+                    let mut case = self.clone();
+                    case.program[idx] = *simpler;
+                    out.push(case);
+                }
+            }
+        }
+        for idx in 0..self.input.len() {
+            if self.input[idx] != 0 {
+                let mut case = self.clone();
+                case.input[idx] /= 2;
+                out.push(case);
+            }
+        }
+        out
+    }
+}
+
+/// How a run stopped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum End {
+    Halted,
+    OutOfSteps,
+    /// The program wrote past `MEMORY_LIMIT`.
+    OutOfMemory,
+    /// The machine faulted at `pc`.
+    Fault(&'static str, usize),
+    Panicked,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub end: End,
+    pub steps: u64,
+    pub outputs: Vec<Int>,
+    /// Memory, without trailing zeros.
+    pub memory: Vec<Int>,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} after {} steps, output {:?}, memory {:?}",
+            self.end, self.steps, self.outputs, self.memory
+        )
+    }
+}
+
+fn trim(mut memory: Vec<Int>) -> Vec<Int> {
+    while memory.last() == Some(&0) {
+        memory.pop();
+    }
+    memory
+}
+
+fn fault_name(kind: &ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::InvalidOpcode(_) => "invalid opcode",
+        ErrorKind::InvalidParameterMode(_) => "invalid parameter mode",
+        ErrorKind::NegativeAddress(_) => "negative address",
+        ErrorKind::AddressOutOfRange(_) => "address out of range",
+        ErrorKind::WriteToImmediate => "write to immediate",
+        ErrorKind::InputExhausted => "input exhausted",
+        ErrorKind::InfiniteLoop => "infinite loop",
+        ErrorKind::Overflow { .. } => "overflow",
+        ErrorKind::Io(_) => "io",
+    }
+}
+
+/// Run `case` on the real machine, through `run_one`, for up to `budget`
+/// instructions.
+pub fn production(case: &Case, budget: u64, predecode: bool) -> Outcome {
+    let mut ic = IntCode::new(
+        case.program.clone(),
+        VecIO::input(case.input.clone()),
+        VecIO::default(),
+    );
+    ic.set_arithmetic(Arithmetic::Wrapping);
+    if predecode {
+        ic.predecode();
+    }
+    let end = catch_unwind(AssertUnwindSafe(|| loop {
+        if ic.halt {
+            return End::Halted;
+        }
+        if ic.steps() >= budget {
+            return End::OutOfSteps;
+        }
+        if let Ok(acc) = ic.pending_accesses() {
+            if matches!(acc.write, Some(addr) if addr >= MEMORY_LIMIT) {
+                return End::OutOfMemory;
+            }
+        }
+        if let Err(e) = ic.run_one() {
+            return End::Fault(fault_name(&e.kind), e.pc);
+        }
+    }))
+    .unwrap_or(End::Panicked);
+    let steps = ic.steps();
+    let (memory, io) = ic.emit();
+    Outcome {
+        end,
+        steps,
+        outputs: io.output.into_vec(),
+        memory: trim(memory),
+    }
+}
+
+/// The simplest machine that could work: sparse memory, every instruction
+/// decoded from scratch, and no caching or other cleverness.
+struct Reference {
+    memory: HashMap<usize, Int>,
+    pc: usize,
+    relative_base: Int,
+    input: Vec<Int>,
+    outputs: Vec<Int>,
+}
+
+type Step<T> = std::result::Result<T, &'static str>;
+
+impl Reference {
+    fn read(&self, addr: usize) -> Int {
+        self.memory.get(&addr).cloned().unwrap_or(0)
+    }
+
+    fn mode(&self, nth: usize) -> Step<Int> {
+        let word = self.read(self.pc);
+        let mode = (word / 100 / 10i64.pow(nth as u32)) % 10;
+        if (0..=2).contains(&mode) {
+            Ok(mode)
+        } else {
+            Err("invalid parameter mode")
+        }
+    }
+
+    fn addr(&self, nth: usize) -> Step<usize> {
+        let arg = self.read(self.pc + 1 + nth);
+        let addr = match self.mode(nth)? {
+            0 => arg,
+            1 => return Ok(self.pc + 1 + nth),
+            _ => self.relative_base.checked_add(arg).ok_or("overflow")?,
+        };
+        if addr < 0 {
+            Err("negative address")
+        } else {
+            Ok(addr as usize)
+        }
+    }
+
+    fn get(&self, nth: usize) -> Step<Int> {
+        Ok(self.read(self.addr(nth)?))
+    }
+
+    fn put(&mut self, nth: usize, value: Int) -> Step<()> {
+        if self.mode(nth)? == 1 {
+            return Err("write to immediate");
+        }
+        let addr = self.addr(nth)?;
+        self.memory.insert(addr, value);
+        Ok(())
+    }
+
+    /// Execute one instruction, returning whether it was `hlt`.
+    fn step(&mut self) -> Step<bool> {
+        let opcode = self.read(self.pc) % 100;
+        if ![1, 2, 3, 4, 5, 6, 7, 8, 9, 99].contains(&opcode) {
+            return Err("invalid opcode");
+        }
+        for nth in 0..3 {
+            self.mode(nth)?;
+        }
+        let mut next = self.pc
+            + match opcode {
+                1 | 2 | 7 | 8 => 4,
+                5 | 6 => 3,
+                99 => 1,
+                _ => 2,
+            };
+        match opcode {
+            1 => {
+                let value = self.get(0)?.wrapping_add(self.get(1)?);
+                self.put(2, value)?;
+            }
+            2 => {
+                let value = self.get(0)?.wrapping_mul(self.get(1)?);
+                self.put(2, value)?;
+            }
+            3 => {
+                let value = self.input.pop().ok_or("input exhausted")?;
+                self.put(0, value)?;
+            }
+            4 => {
+                let value = self.get(0)?;
+                self.outputs.push(value);
+            }
+            5 | 6 => {
+                if (self.get(0)? != 0) == (opcode == 5) {
+                    let target = self.get(1)?;
+                    if target < 0 {
+                        return Err("negative address");
+                    }
+                    next = target as usize;
+                }
+            }
+            7 => {
+                let value = (self.get(0)? < self.get(1)?) as Int;
+                self.put(2, value)?;
+            }
+            8 => {
+                let value = (self.get(0)? == self.get(1)?) as Int;
+                self.put(2, value)?;
+            }
+            9 => {
+                self.relative_base = self
+                    .relative_base
+                    .checked_add(self.get(0)?)
+                    .ok_or("overflow")?;
+            }
+            _ => return Ok(true),
+        }
+        self.pc = next;
+        Ok(false)
+    }
+}
+
+/// Run `case` on the reference machine for up to `budget` instructions.
+pub fn reference(case: &Case, budget: u64) -> Outcome {
+    let mut machine = Reference {
+        memory: case.program.iter().cloned().enumerate().collect(),
+        pc: 0,
+        relative_base: 0,
+        input: case.input.iter().rev().cloned().collect(),
+        outputs: Vec::new(),
+    };
+    let mut steps = 0;
+    let end = loop {
+        if steps >= budget {
+            break End::OutOfSteps;
+        }
+        match machine.step() {
+            Ok(halted) => {
+                steps += 1;
+                if halted {
+                    break End::Halted;
+                }
+            }
+            Err(fault) => break End::Fault(fault, machine.pc),
+        }
+    };
+    let len = machine.memory.keys().max().map_or(0, |addr| addr + 1);
+    let memory = (0..len).map(|addr| machine.read(addr)).collect();
+    Outcome {
+        end,
+        steps,
+        outputs: machine.outputs,
+        memory: trim(memory),
+    }
+}
+
+/// A case the two machines disagree on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    pub case: Case,
+    pub predecoded: bool,
+    pub expected: Outcome,
+    pub actual: Outcome,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let csv = |words: &[Int]| {
+            words
+                .iter()
+                .map(|w| w.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        writeln!(f, "program: {}", csv(&self.case.program))?;
+        writeln!(f, "input: {}", csv(&self.case.input))?;
+        writeln!(f, "predecoded: {}", self.predecoded)?;
+        writeln!(f, "reference: {}", self.expected)?;
+        writeln!(f, "production: {}", self.actual)?;
+        write!(f, "{}", Listing(&disassemble(&self.case.program)))
+    }
+}
+
+/// Run `case` on both machines, with and without a predecoded instruction
+/// cache. `None` if they agree, or if the production machine hit the
+/// memory limit and there's nothing to compare.
+pub fn check(case: &Case, budget: u64) -> Option<Divergence> {
+    let expected = reference(case, budget);
+    for &predecoded in [false, true].iter() {
+        let actual = production(case, budget, predecoded);
+        if actual.end == End::OutOfMemory {
+            return None;
+        }
+        if actual != expected {
+            return Some(Divergence {
+                case: case.clone(),
+                predecoded,
+                expected,
+                actual,
+            });
+        }
+    }
+    None
+}
+
+/// Shrink `case` for as long as `diverges` still holds.
+fn minimize<F: Fn(&Case) -> bool>(mut case: Case, diverges: F) -> Case {
+    'outer: loop {
+        for candidate in case.shrink_candidates() {
+            if diverges(&candidate) {
+                case = candidate;
+                continue 'outer;
+            }
+        }
+        return case;
+    }
+}
+
+/// Make `divergence` as small as possible while the machines still
+/// disagree.
+pub fn shrink(divergence: Divergence, budget: u64) -> Divergence {
+    let case = minimize(divergence.case.clone(), |c| check(c, budget).is_some());
+    check(&case, budget).unwrap_or(divergence)
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub cases: usize,
+    /// Cases which hit the memory limit.
+    pub skipped: usize,
+    /// The first divergence found, shrunk.
+    pub divergence: Option<Divergence>,
+}
+
+/// Check `cases` random cases generated from `seed`, stopping at the first
+/// divergence.
+pub fn fuzz(seed: u64, cases: usize, budget: u64) -> Report {
+    let mut rng = Rng::new(seed);
+    let mut report = Report {
+        cases: 0,
+        skipped: 0,
+        divergence: None,
+    };
+    for _ in 0..cases {
+        let case = Case::generate(&mut rng);
+        report.cases += 1;
+        if production(&case, budget, false).end == End::OutOfMemory {
+            report.skipped += 1;
+            continue;
+        }
+        if let Some(divergence) = check(&case, budget) {
+            log::info!("divergence in case {}, shrinking", report.cases);
+            report.divergence = Some(shrink(divergence, budget));
+            break;
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn machines_agree() {
+        let report = fuzz(2019, 2000, 500);
+        if let Some(d) = report.divergence {
+            panic!("{}", d);
+        }
+        assert!(report.skipped < report.cases / 10);
+    }
+
+    #[test]
+    fn generated_programs_do_something() {
+        let mut rng = Rng::new(7);
+        let mut ends = HashMap::new();
+        for _ in 0..500 {
+            let case = Case::generate(&mut rng);
+            let end = match reference(&case, 500).end {
+                End::Fault(..) => "fault",
+                End::Halted => "halted",
+                _ => "other",
+            };
+            *ends.entry(end).or_insert(0) += 1;
+        }
+        assert!(ends["halted"] > 100, "{:?}", ends);
+        assert!(ends["fault"] > 10, "{:?}", ends);
+    }
+
+    #[test]
+    fn shrinks_a_planted_bug() {
+        // pretend the production machine gets programs with an immediate
+        // `mul` wrong
+        let case = Case {
+            program: vec![1101, 3, 4, 20, 1102, 5, 6, 21, 4, 21, 99],
+            input: vec![1, 2, 3],
+        };
+        let diverges = |c: &Case| c.program.contains(&1102) && reference(c, 100).end == End::Halted;
+        let min = minimize(case, diverges);
+        assert_eq!(min.program, vec![99, 1102]);
+        assert!(min.input.is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+use super::opcodes::{parse_instruction, Instruction, ParameterModes, MAX_ARITY};
+use super::{ErrorKind, Int, Memory, Word};
+use std::sync::Arc;
+
+/// An instruction along with its raw operand words.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Decoded<W = Int> {
+    pub instr: Instruction,
+    pub modes: ParameterModes,
+    pub args: [W; MAX_ARITY],
+}
+
+impl<W: Word> Decoded<W> {
+    pub fn read<M: Memory<Word = W>>(mem: &M, pc: usize) -> Result<Decoded<W>, ErrorKind<W>> {
+        let word = &mem[pc];
+        let (instr, modes) = match word.to_int() {
+            Some(word) => parse_instruction(word).map_err(ErrorKind::widen)?,
+            None => return Err(ErrorKind::InvalidOpcode(word.clone())),
+        };
+        let mut args: [W; MAX_ARITY] = Default::default();
+        for (nth, arg) in args.iter_mut().enumerate().take(instr.arity()) {
+            *arg = mem[pc + 1 + nth].clone();
+        }
+        Ok(Decoded { instr, modes, args })
+    }
+}
+
+/// Never cache more than this many addresses, whatever the memory size.
+const MAX_SLOTS: usize = 1 << 20;
+
+/// Decoded instructions by address, covering the program as loaded.
+///
+/// An entry holds the words at `pc..=pc + MAX_ARITY`, so a write to `addr`
+/// invalidates the entries which might start up to `MAX_ARITY` words before
+/// it. Code outside the loaded program is decoded every time.
+///
+/// Clones share their entries until one of them changes, so forked machines
+/// don't each copy the cache.
+///
+/// Decoding is only worth caching on long runs through the same code, like
+/// day 19's probes. Day 23 is no faster for it: its time goes to an idle
+/// loop of three instructions, which decode quickly either way.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InstructionCache<W = Int> {
+    slots: Arc<Vec<Option<Decoded<W>>>>,
+}
+
+impl<W: Word> InstructionCache<W> {
+    pub fn new(len: usize) -> InstructionCache<W> {
+        InstructionCache {
+            slots: Arc::new(vec![None; len.min(MAX_SLOTS)]),
+        }
+    }
+
+    /// Number of addresses, starting from zero, which can be cached.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[inline]
+    pub fn get(&self, pc: usize) -> Option<Decoded<W>> {
+        self.slots.get(pc).cloned().flatten()
+    }
+
+    #[inline]
+    pub fn insert(&mut self, pc: usize, decoded: Decoded<W>) {
+        if pc < self.slots.len() {
+            Arc::make_mut(&mut self.slots)[pc] = Some(decoded);
+        }
+    }
+
+    #[inline]
+    pub fn invalidate(&mut self, addr: usize) {
+        let start = addr.saturating_sub(MAX_ARITY);
+        let end = (addr + 1).min(self.slots.len());
+        if start < end && self.slots[start..end].iter().any(Option::is_some) {
+            for slot in &mut Arc::make_mut(&mut self.slots)[start..end] {
+                *slot = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::DenseMemory;
+
+    #[test]
+    fn writes_invalidate_overlapping_entries() {
+        let mem: DenseMemory = DenseMemory::from(vec![1101, 1, 2, 3, 99, 0, 0]);
+        let mut cache = InstructionCache::new(7);
+        let add = Decoded::read(&mem, 0).unwrap();
+        assert_eq!(add.args, [1, 2, 3]);
+        cache.insert(0, add);
+        cache.insert(4, Decoded::read(&mem, 4).unwrap());
+
+        cache.invalidate(5);
+        assert_eq!(cache.get(0), Some(add));
+        assert!(cache.get(4).is_none());
+        cache.invalidate(3);
+        assert!(cache.get(0).is_none());
+
+        // outside the cached range
+        cache.insert(100, add);
+        cache.invalidate(100);
+        assert!(cache.get(100).is_none());
+    }
+}
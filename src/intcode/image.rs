@@ -0,0 +1,361 @@
+use super::asm::Symbols;
+use super::{DenseMemory, Int, Memory};
+use crate::util::parse_intcode;
+use anyhow::{anyhow as ah, Context, Result};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"\0icb";
+const VERSION: u8 = 1;
+
+const HAS_SYMBOLS: u8 = 1;
+const HAS_PATCHES: u8 = 2;
+
+/// Patches may write past the words, and `program` fills the gap with
+/// zeros, so they can't go past the most a machine's dense memory holds.
+const MAX_PATCH_ADDR: usize = DenseMemory::<Int>::MAX_LEN - 1;
+
+/// A program in the binary image format, along with what it knows about
+/// itself.
+///
+/// The layout is the magic bytes `\0icb`, a version byte and a flags byte,
+/// then the words as zigzag LEB128 varints behind a varint count. A symbol
+/// table (address and name per label) and a patch list (address and value
+/// per write applied at load time) follow if their flag is set. The image
+/// ends with an FNV-1a checksum of everything before it, as 4 little endian
+/// bytes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Image {
+    pub words: Vec<Int>,
+    /// Labels and their addresses, in address order.
+    pub symbols: Symbols,
+    pub patches: Vec<(usize, Int)>,
+}
+
+impl Image {
+    pub fn new(words: Vec<Int>) -> Image {
+        Image {
+            words,
+            ..Image::default()
+        }
+    }
+
+    /// The words to load, with the patches applied.
+    pub fn program(&self) -> Vec<Int> {
+        let mut program = self.words.clone();
+        for &(addr, value) in &self.patches {
+            if addr >= program.len() {
+                program.resize(addr + 1, 0);
+            }
+            program[addr] = value;
+        }
+        program
+    }
+
+    /// Whether `bytes` starts like an image rather than text.
+    pub fn is_image(bytes: &[u8]) -> bool {
+        bytes.starts_with(MAGIC)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.push(VERSION);
+        let mut flags = 0;
+        if !self.symbols.is_empty() {
+            flags |= HAS_SYMBOLS;
+        }
+        if !self.patches.is_empty() {
+            flags |= HAS_PATCHES;
+        }
+        out.push(flags);
+        put_varint(&mut out, self.words.len() as u64);
+        for &word in &self.words {
+            put_varint(&mut out, zigzag(word));
+        }
+        if !self.symbols.is_empty() {
+            put_varint(&mut out, self.symbols.len() as u64);
+            for (name, addr) in &self.symbols {
+                put_varint(&mut out, *addr as u64);
+                put_varint(&mut out, name.len() as u64);
+                out.extend(name.as_bytes());
+            }
+        }
+        if !self.patches.is_empty() {
+            put_varint(&mut out, self.patches.len() as u64);
+            for &(addr, value) in &self.patches {
+                put_varint(&mut out, addr as u64);
+                put_varint(&mut out, zigzag(value));
+            }
+        }
+        let checksum = fnv1a(&out);
+        out.extend(&checksum.to_le_bytes());
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Image> {
+        if !Image::is_image(bytes) {
+            return Err(ah!("not an intcode image"));
+        }
+        if bytes.len() < MAGIC.len() + 6 {
+            return Err(ah!("image is truncated"));
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - 4);
+        let mut expected = [0; 4];
+        expected.copy_from_slice(checksum);
+        if fnv1a(body) != u32::from_le_bytes(expected) {
+            return Err(ah!("image checksum mismatch"));
+        }
+
+        let mut reader = Reader {
+            bytes: body,
+            pos: MAGIC.len(),
+        };
+        let version = reader.byte()?;
+        if version != VERSION {
+            return Err(ah!(
+                "unsupported image version {} (expected {})",
+                version,
+                VERSION
+            ));
+        }
+        let flags = reader.byte()?;
+        if flags & !(HAS_SYMBOLS | HAS_PATCHES) != 0 {
+            return Err(ah!("unknown image flags {:#04x}", flags));
+        }
+        let mut image = Image::default();
+        for _ in 0..reader.len()? {
+            image.words.push(unzigzag(reader.varint()?));
+        }
+        if flags & HAS_SYMBOLS != 0 {
+            for _ in 0..reader.len()? {
+                let addr = reader.varint()? as usize;
+                let len = reader.len()?;
+                let name = String::from_utf8(reader.take(len)?.to_vec())
+                    .context("symbol name is not utf-8")?;
+                image.symbols.push((name, addr));
+            }
+        }
+        if flags & HAS_PATCHES != 0 {
+            for _ in 0..reader.len()? {
+                let addr = reader.varint()?;
+                if addr > MAX_PATCH_ADDR as u64 {
+                    return Err(ah!(
+                        "patch address {} is past the largest program (at most {})",
+                        addr,
+                        MAX_PATCH_ADDR
+                    ));
+                }
+                image
+                    .patches
+                    .push((addr as usize, unzigzag(reader.varint()?)));
+            }
+        }
+        if reader.pos != body.len() {
+            return Err(ah!(
+                "{} unexpected bytes at the end",
+                body.len() - reader.pos
+            ));
+        }
+        Ok(image)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(&path, self.to_bytes())
+            .with_context(|| format!("unable to write image {}", path.as_ref().display()))
+    }
+
+    /// Read `path` as an image if it is one, or as comma separated text.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Image> {
+        let bytes = std::fs::read(&path)
+            .with_context(|| format!("Unable to open path: {}", path.as_ref().display()))?;
+        Image::parse(&bytes).with_context(|| format!("invalid program {}", path.as_ref().display()))
+    }
+
+    /// Parse `bytes` in either format.
+    pub fn parse(bytes: &[u8]) -> Result<Image> {
+        if Image::is_image(bytes) {
+            Image::from_bytes(bytes)
+        } else {
+            let text = std::str::from_utf8(bytes).context("program is not text")?;
+            Ok(Image::new(parse_intcode(text)?))
+        }
+    }
+
+    /// The words as comma separated text, the way puzzle inputs are
+    /// written. Symbols are dropped and patches applied.
+    pub fn to_text(&self) -> String {
+        let words = self.program();
+        let words = words.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        format!("{}\n", words.join(","))
+    }
+}
+
+/// The program in `path`, whichever format it is in.
+pub fn load_program<P: AsRef<Path>>(path: P) -> Result<Vec<Int>> {
+    Ok(Image::load(path)?.program())
+}
+
+/// The contents of `path` as text, with an image turned into comma
+/// separated words, for code which parses its input itself.
+pub fn read_source<P: AsRef<Path>>(path: P) -> Result<String> {
+    let bytes = std::fs::read(&path)
+        .with_context(|| format!("Unable to open path: {}", path.as_ref().display()))?;
+    if Image::is_image(&bytes) {
+        Ok(Image::from_bytes(&bytes)?.to_text())
+    } else {
+        String::from_utf8(bytes).with_context(|| format!("{} is not text", path.as_ref().display()))
+    }
+}
+
+fn zigzag(n: Int) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+fn unzigzag(n: u64) -> Int {
+    ((n >> 1) as Int) ^ -((n & 1) as Int)
+}
+
+fn put_varint(out: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        out.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn fnv1a(bytes: &[u8]) -> u32 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for &b in bytes {
+        hash ^= b as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.bytes.len());
+        let end = end.ok_or_else(|| ah!("image is truncated"))?;
+        let taken = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            n |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(ah!("varint at {} is too long", self.pos))
+    }
+
+    /// A count, which can't be more than the bytes left.
+    fn len(&mut self) -> Result<usize> {
+        let n = self.varint()?;
+        if n > (self.bytes.len() - self.pos) as u64 {
+            return Err(ah!("count {} is more than the image holds", n));
+        }
+        Ok(n as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm::assemble_with_symbols;
+
+    #[test]
+    fn zigzag_round_trip() {
+        for &n in &[0, 1, -1, 63, -64, 64, Int::MAX, Int::MIN] {
+            assert_eq!(unzigzag(zigzag(n)), n);
+        }
+        assert_eq!(zigzag(-1), 1);
+        assert_eq!(zigzag(1), 2);
+        let mut out = Vec::new();
+        put_varint(&mut out, 300);
+        assert_eq!(out, vec![0xac, 0x02]);
+    }
+
+    #[test]
+    fn puzzle_input_round_trip() {
+        let text = include_str!("../../input/day9");
+        let image = Image::parse(text.as_bytes()).unwrap();
+        let bytes = image.to_bytes();
+        assert!(bytes.len() < text.len() / 2, "{} bytes", bytes.len());
+        let loaded = Image::parse(&bytes).unwrap();
+        assert_eq!(loaded, image);
+        assert_eq!(loaded.program(), parse_intcode(text).unwrap());
+        assert_eq!(
+            parse_intcode(&loaded.to_text()).unwrap(),
+            parse_intcode(text).unwrap()
+        );
+    }
+
+    #[test]
+    fn symbols_and_patches() {
+        let (words, symbols) =
+            assemble_with_symbols("start: in [x]\nout [x]\nhlt\nx: .data 0").unwrap();
+        let image = Image {
+            words,
+            symbols,
+            patches: vec![(7, -3), (10, 5)],
+        };
+        let loaded = Image::from_bytes(&image.to_bytes()).unwrap();
+        assert_eq!(loaded, image);
+        assert_eq!(
+            loaded.symbols,
+            vec![("start".to_string(), 0), ("x".to_string(), 5)]
+        );
+        assert_eq!(loaded.program(), vec![3, 5, 4, 5, 99, 0, 0, -3, 0, 0, 5]);
+    }
+
+    #[test]
+    fn rejects_bad_images() {
+        let err = |b: &[u8]| format!("{:#}", Image::from_bytes(b).unwrap_err());
+        let good = Image::new(vec![1, 2, 3]).to_bytes();
+        assert_eq!(err(b"1,2,3"), "not an intcode image");
+        assert_eq!(err(&good[..7]), "image is truncated");
+
+        let mut corrupt = good.clone();
+        corrupt[7] ^= 1;
+        assert_eq!(err(&corrupt), "image checksum mismatch");
+
+        let mut body = good[..good.len() - 4].to_vec();
+        body[4] = 2;
+        body.extend(&fnv1a(&body).to_le_bytes());
+        assert_eq!(err(&body), "unsupported image version 2 (expected 1)");
+
+        let mut body = good[..good.len() - 4].to_vec();
+        body[6] = 100;
+        body.extend(&fnv1a(&body).to_le_bytes());
+        assert_eq!(err(&body), "count 100 is more than the image holds");
+
+        let mut image = Image::new(vec![1, 2, 3]);
+        image.patches.push((MAX_PATCH_ADDR, 1));
+        assert!(Image::from_bytes(&image.to_bytes()).is_ok());
+        image.patches.push((usize::MAX, 1));
+        assert_eq!(
+            err(&image.to_bytes()),
+            format!(
+                "patch address {} is past the largest program (at most {})",
+                usize::MAX,
+                MAX_PATCH_ADDR
+            )
+        );
+    }
+}
@@ -1,22 +1,39 @@
-use super::Int;
+use super::{Event, Int, IntCode, Memory, Word};
 use anyhow::{anyhow as ah, Context, Result};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
 use std::ops::DerefMut;
 
-pub trait Input {
-    fn input(&mut self) -> Result<Int>;
+/// Returned by input devices which have nothing left to give, so the machine
+/// can report it as `ErrorKind::InputExhausted` rather than a device failure.
+#[derive(Debug)]
+pub struct InputExhausted;
+
+impl fmt::Display for InputExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no more input")
+    }
 }
 
-pub trait Output {
-    fn output(&mut self, out: Int) -> Result<()>;
+impl std::error::Error for InputExhausted {}
+
+pub trait Input<W = Int> {
+    fn input(&mut self) -> Result<W>;
 }
 
-impl Input for Box<dyn Input + Send> {
-    fn input(&mut self) -> Result<Int> {
+pub trait Output<W = Int> {
+    fn output(&mut self, out: W) -> Result<()>;
+}
+
+impl<W> Input<W> for Box<dyn Input<W> + Send> {
+    fn input(&mut self) -> Result<W> {
         self.deref_mut().input()
     }
 }
-impl Output for Box<dyn Output + Send> {
-    fn output(&mut self, out: Int) -> Result<()> {
+impl<W> Output<W> for Box<dyn Output<W> + Send> {
+    fn output(&mut self, out: W) -> Result<()> {
         self.deref_mut().output(out)
     }
 }
@@ -26,14 +43,14 @@ pub struct FusedIO<I, O> {
     pub output: O,
 }
 
-impl<I: Input, O> Input for FusedIO<I, O> {
-    fn input(&mut self) -> Result<i64> {
+impl<W, I: Input<W>, O> Input<W> for FusedIO<I, O> {
+    fn input(&mut self) -> Result<W> {
         self.input.input()
     }
 }
 
-impl<I, O: Output> Output for FusedIO<I, O> {
-    fn output(&mut self, out: i64) -> Result<()> {
+impl<W, I, O: Output<W>> Output<W> for FusedIO<I, O> {
+    fn output(&mut self, out: W) -> Result<()> {
         self.output.output(out)
     }
 }
@@ -41,14 +58,14 @@ impl<I, O: Output> Output for FusedIO<I, O> {
 #[derive(Debug)]
 pub struct NullIO;
 
-impl Input for NullIO {
-    fn input(&mut self) -> Result<Int> {
-        Ok(0)
+impl<W: Word> Input<W> for NullIO {
+    fn input(&mut self) -> Result<W> {
+        Ok(W::zero())
     }
 }
 
-impl Output for NullIO {
-    fn output(&mut self, out: Int) -> Result<()> {
+impl<W: Word> Output<W> for NullIO {
+    fn output(&mut self, out: W) -> Result<()> {
         log::trace!("NullIO output => {}", out);
         Ok(())
     }
@@ -71,7 +88,7 @@ impl VecIO {
 
 impl Input for VecIO {
     fn input(&mut self) -> Result<Int> {
-        self.inner.pop().ok_or_else(|| ah!("no more input"))
+        self.inner.pop().ok_or_else(|| InputExhausted.into())
     }
 }
 
@@ -83,28 +100,6 @@ impl Output for VecIO {
     }
 }
 
-#[derive(Debug)]
-pub struct StreamInput {
-    inner: std::sync::mpsc::Receiver<Int>,
-}
-
-impl Input for StreamInput {
-    fn input(&mut self) -> Result<Int> {
-        self.inner.recv().context("input stream closed")
-    }
-}
-
-#[derive(Debug)]
-pub struct StreamOutput {
-    inner: std::sync::mpsc::Sender<Int>,
-}
-
-impl Output for StreamOutput {
-    fn output(&mut self, out: Int) -> Result<()> {
-        self.inner.send(out).context("output stream closed")
-    }
-}
-
 #[derive(Debug)]
 pub struct MultiIO<U, V> {
     first: U,
@@ -113,6 +108,7 @@ pub struct MultiIO<U, V> {
 }
 
 impl<U, V> MultiIO<U, V> {
+    #[allow(dead_code)]
     pub fn new(first: U, second: V) -> MultiIO<U, V> {
         MultiIO {
             first,
@@ -158,17 +154,313 @@ impl<U: Output, V: Output> Output for MultiIO<U, V> {
     }
 }
 
-pub fn create_stream_io() -> (StreamInput, StreamOutput) {
-    let (tx, rx) = std::sync::mpsc::channel();
-    let input = StreamInput { inner: rx };
-    let output = StreamOutput { inner: tx };
-    (input, output)
+/// Line-oriented text IO for the programs that talk in ASCII.
+///
+/// Input is queued as text, output collects as text, and any value outside
+/// the ASCII range (the answer most of these programs finish with) is kept
+/// aside as the `result`.
+#[derive(Debug, Clone, Default)]
+pub struct AsciiIO {
+    input: VecDeque<Int>,
+    output: String,
+    result: Option<Int>,
 }
 
-pub fn create_stream_tap() -> (StreamOutput, std::sync::mpsc::Receiver<Int>) {
-    let (tx, rx) = std::sync::mpsc::channel();
-    let output = StreamOutput { inner: tx };
-    (output, rx)
+impl AsciiIO {
+    pub fn new() -> AsciiIO {
+        AsciiIO::default()
+    }
+
+    /// Queue `text` exactly as given.
+    pub fn send(&mut self, text: &str) {
+        self.input.extend(text.bytes().map(Int::from));
+    }
+
+    /// Queue `line` followed by a newline.
+    pub fn send_line(&mut self, line: &str) {
+        self.send(line);
+        self.input.push_back(Int::from(b'\n'));
+    }
+
+    /// All output text which hasn't been taken yet.
+    pub fn text(&self) -> &str {
+        &self.output
+    }
+
+    pub fn take_output(&mut self) -> String {
+        std::mem::take(&mut self.output)
+    }
+
+    /// The next complete line of output, without its newline.
+    pub fn take_line(&mut self) -> Option<String> {
+        let end = self.output.find('\n')?;
+        let line = self.output[..end].to_string();
+        self.output.drain(..=end);
+        Some(line)
+    }
+
+    /// The last value the program output which wasn't ASCII.
+    pub fn result(&self) -> Option<Int> {
+        self.result
+    }
+
+    fn push(&mut self, out: Int) {
+        if (0..128).contains(&out) {
+            self.output.push(out as u8 as char);
+        } else {
+            self.result = Some(out);
+        }
+    }
+}
+
+impl Input for AsciiIO {
+    fn input(&mut self) -> Result<Int> {
+        self.input.pop_front().ok_or_else(|| InputExhausted.into())
+    }
+}
+
+impl Output for AsciiIO {
+    fn output(&mut self, out: Int) -> Result<()> {
+        self.push(out);
+        Ok(())
+    }
+}
+
+impl<M: Memory<Word = Int>> IntCode<AsciiIO, M> {
+    /// Run until the output ends with `prompt`, the machine halts, or it
+    /// wants input which hasn't been sent yet, then take the output text.
+    pub fn read_until_prompt(&mut self, prompt: &str) -> super::Result<String> {
+        loop {
+            match self.step()? {
+                Some(Event::NeedsInput) => match self.io_device.input.pop_front() {
+                    Some(x) => self.provide_input(x),
+                    None => break,
+                },
+                Some(Event::Output(x)) => {
+                    self.io_device.push(x);
+                    if self.io_device.output.ends_with(prompt) {
+                        break;
+                    }
+                }
+                Some(_) => break,
+                None => {}
+            }
+        }
+        Ok(self.io_device.take_output())
+    }
+}
+
+/// A message made of a fixed number of output words.
+pub trait FromWords: Sized {
+    const WORDS: usize;
+    fn from_words(words: &[Int]) -> Result<Self>;
+}
+
+/// A message which can be sent to a machine as input words.
+pub trait ToWords {
+    fn to_words(self, out: &mut VecDeque<Int>);
+}
+
+fn word<T>(x: Int) -> Result<T>
+where
+    T: TryFrom<Int>,
+    T::Error: Into<anyhow::Error>,
+{
+    T::try_from(x).map_err(Into::into)
+}
+
+impl FromWords for Int {
+    const WORDS: usize = 1;
+    fn from_words(words: &[Int]) -> Result<Int> {
+        Ok(words[0])
+    }
+}
+
+impl<A, B> FromWords for (A, B)
+where
+    A: TryFrom<Int>,
+    A::Error: Into<anyhow::Error>,
+    B: TryFrom<Int>,
+    B::Error: Into<anyhow::Error>,
+{
+    const WORDS: usize = 2;
+    fn from_words(words: &[Int]) -> Result<(A, B)> {
+        Ok((word(words[0])?, word(words[1])?))
+    }
+}
+
+impl<A, B, C> FromWords for (A, B, C)
+where
+    A: TryFrom<Int>,
+    A::Error: Into<anyhow::Error>,
+    B: TryFrom<Int>,
+    B::Error: Into<anyhow::Error>,
+    C: TryFrom<Int>,
+    C::Error: Into<anyhow::Error>,
+{
+    const WORDS: usize = 3;
+    fn from_words(words: &[Int]) -> Result<(A, B, C)> {
+        Ok((word(words[0])?, word(words[1])?, word(words[2])?))
+    }
+}
+
+impl ToWords for Int {
+    fn to_words(self, out: &mut VecDeque<Int>) {
+        out.push_back(self);
+    }
+}
+
+impl<A: Into<Int>, B: Into<Int>> ToWords for (A, B) {
+    fn to_words(self, out: &mut VecDeque<Int>) {
+        out.push_back(self.0.into());
+        out.push_back(self.1.into());
+    }
+}
+
+impl<A: Into<Int>, B: Into<Int>, C: Into<Int>> ToWords for (A, B, C) {
+    fn to_words(self, out: &mut VecDeque<Int>) {
+        out.push_back(self.0.into());
+        out.push_back(self.1.into());
+        out.push_back(self.2.into());
+    }
+}
+
+/// Receives the messages a `FramedOutput` decodes.
+pub trait FrameHandler<T> {
+    fn handle(&mut self, msg: T) -> Result<()>;
+}
+
+impl<T, F: FnMut(T) -> Result<()>> FrameHandler<T> for F {
+    fn handle(&mut self, msg: T) -> Result<()> {
+        self(msg)
+    }
+}
+
+/// Supplies the messages a `FramedInput` sends once its queue runs dry.
+pub trait FrameSource<T> {
+    fn next_frame(&mut self) -> Result<T>;
+}
+
+impl<T, F: FnMut() -> Result<T>> FrameSource<T> for F {
+    fn next_frame(&mut self) -> Result<T> {
+        self()
+    }
+}
+
+/// Buffers output words and passes each complete `T` to the handler.
+///
+/// Input is passed through to the handler, so a device can implement
+/// `Input` alongside `FrameHandler` and be wrapped as a whole.
+#[derive(Debug)]
+pub struct FramedOutput<T, H> {
+    handler: H,
+    buffer: Vec<Int>,
+    frame: PhantomData<fn(T)>,
+}
+
+impl<T: FromWords, H: FrameHandler<T>> FramedOutput<T, H> {
+    pub fn new(handler: H) -> FramedOutput<T, H> {
+        FramedOutput {
+            handler,
+            buffer: Vec::with_capacity(T::WORDS),
+            frame: PhantomData,
+        }
+    }
+}
+
+impl<T, H> FramedOutput<T, H> {
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
+    pub fn into_inner(self) -> H {
+        self.handler
+    }
+}
+
+impl<T: FromWords, H: FrameHandler<T>> Output for FramedOutput<T, H> {
+    fn output(&mut self, out: Int) -> Result<()> {
+        self.buffer.push(out);
+        if self.buffer.len() == T::WORDS {
+            let msg = T::from_words(&self.buffer)
+                .with_context(|| format!("bad message {:?}", self.buffer))?;
+            self.buffer.clear();
+            self.handler.handle(msg)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, H: Input> Input for FramedOutput<T, H> {
+    fn input(&mut self) -> Result<Int> {
+        self.handler.input()
+    }
+}
+
+impl<T, U, H: FrameSource<U>> FrameSource<U> for FramedOutput<T, H> {
+    fn next_frame(&mut self) -> Result<U> {
+        self.handler.next_frame()
+    }
+}
+
+/// Feeds queued messages to the machine a word at a time, asking the source
+/// for the next message whenever the queue is empty.
+///
+/// Output is passed through to the source, the mirror of `FramedOutput`.
+#[derive(Debug)]
+pub struct FramedInput<T, S> {
+    source: S,
+    queue: VecDeque<Int>,
+    frame: PhantomData<fn() -> T>,
+}
+
+impl<T: ToWords, S: FrameSource<T>> FramedInput<T, S> {
+    pub fn new(source: S) -> FramedInput<T, S> {
+        FramedInput {
+            source,
+            queue: VecDeque::new(),
+            frame: PhantomData,
+        }
+    }
+}
+
+impl<T: ToWords, S> FramedInput<T, S> {
+    /// Queue `msg` ahead of anything from the source.
+    pub fn send(&mut self, msg: T) {
+        msg.to_words(&mut self.queue);
+    }
+
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+}
+
+impl<T: ToWords, S: FrameSource<T>> Input for FramedInput<T, S> {
+    fn input(&mut self) -> Result<Int> {
+        if self.queue.is_empty() {
+            let msg = self.source.next_frame()?;
+            msg.to_words(&mut self.queue);
+        }
+        self.queue
+            .pop_front()
+            .ok_or_else(|| ah!("frame source sent an empty message"))
+    }
+}
+
+impl<T, S: Output> Output for FramedInput<T, S> {
+    fn output(&mut self, out: Int) -> Result<()> {
+        self.source.output(out)
+    }
+}
+
+impl<T, U, S: FrameHandler<U>> FrameHandler<U> for FramedInput<T, S> {
+    fn handle(&mut self, msg: U) -> Result<()> {
+        self.source.handle(msg)
+    }
 }
 
 #[cfg(test)]
@@ -208,4 +500,92 @@ mod test {
         assert_eq!(vio1.into_vec(), data);
         assert_eq!(vio2.into_vec(), data);
     }
+
+    #[test]
+    fn ascii_lines_and_result() {
+        let mut io = AsciiIO::new();
+        io.send_line("NOT A J");
+        io.send("WALK\n");
+        let sent = std::iter::from_fn(|| io.input().ok()).collect::<Vec<_>>();
+        assert_eq!(sent.len(), 13);
+        assert_eq!(sent[7], 10);
+
+        for b in b"one\ntwo\nthr" {
+            io.output(Int::from(*b)).unwrap();
+        }
+        io.output(19355645).unwrap();
+        assert_eq!(io.take_line().as_deref(), Some("one"));
+        assert_eq!(io.take_line().as_deref(), Some("two"));
+        assert_eq!(io.take_line(), None);
+        assert_eq!(io.text(), "thr");
+        assert_eq!(io.result(), Some(19355645));
+    }
+
+    #[test]
+    fn read_until_prompt() {
+        let code = crate::util::parse_intcode(include_str!("../../input/day25")).unwrap();
+        let mut ic = IntCode::new_from_device(code, AsciiIO::new());
+        let text = ic.read_until_prompt("Command?\n").unwrap();
+        assert!(text.contains("== Hull Breach =="), "{}", text);
+        assert!(text.ends_with("Command?\n"));
+
+        ic.device_mut().send_line("inv");
+        let text = ic.read_until_prompt("Command?\n").unwrap();
+        assert!(text.contains("You aren't carrying any items."), "{}", text);
+        // nothing more to send: stops at the next input
+        assert_eq!(ic.read_until_prompt("never printed").unwrap(), "");
+        assert!(!ic.halt);
+    }
+
+    #[test]
+    fn framed_output_buffers_messages() {
+        let mut seen = Vec::new();
+        let mut framed = FramedOutput::new(|msg: (Int, Int, Int)| {
+            seen.push(msg);
+            Ok(())
+        });
+        for x in 1..=7 {
+            framed.output(x).unwrap();
+        }
+        drop(framed);
+        assert_eq!(seen, vec![(1, 2, 3), (4, 5, 6)]);
+
+        let mut strict = FramedOutput::new(|_: (Int, u8)| Ok(()));
+        strict.output(1).unwrap();
+        let err = strict.output(-1).unwrap_err();
+        assert_eq!(format!("{}", err), "bad message [1, -1]");
+    }
+
+    #[test]
+    fn framed_input_splits_messages() {
+        let mut packets = vec![(5, 6), (3, 4)];
+        let mut framed = FramedInput::new(|| packets.pop().ok_or_else(|| InputExhausted.into()));
+        framed.send((1, 2));
+        let sent = std::iter::from_fn(|| framed.input().ok()).collect::<Vec<_>>();
+        assert_eq!(sent, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn framed_devices_compose() {
+        // echo pairs back, summed
+        struct Adder(VecDeque<Int>);
+        impl FrameHandler<(Int, Int)> for Adder {
+            fn handle(&mut self, (a, b): (Int, Int)) -> Result<()> {
+                self.0.push_back(a + b);
+                Ok(())
+            }
+        }
+        impl FrameSource<Int> for Adder {
+            fn next_frame(&mut self) -> Result<Int> {
+                self.0.pop_front().ok_or_else(|| InputExhausted.into())
+            }
+        }
+        let mut device = FramedInput::new(FramedOutput::new(Adder(VecDeque::new())));
+        device.send(10);
+        assert_eq!(device.input().unwrap(), 10);
+        device.output(2).unwrap();
+        assert!(device.input().is_err());
+        device.output(3).unwrap();
+        assert_eq!(device.input().unwrap(), 5);
+    }
 }
@@ -0,0 +1,298 @@
+use super::{Int, Word};
+use num::Zero;
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+use std::sync::Arc;
+
+/// Backing store for an `IntCode` machine.
+///
+/// Reads past anything written are zero and must not allocate; writes grow
+/// the memory as needed.
+pub trait Memory:
+    Index<usize, Output = <Self as Memory>::Word>
+    + IndexMut<usize>
+    + From<Vec<<Self as Memory>::Word>>
+    + Clone
+{
+    /// The integer type stored in each address.
+    type Word: Word;
+
+    /// The largest `len()` worth building from a snapshot or image, which
+    /// may name any address.
+    const MAX_LEN: usize = usize::MAX;
+
+    /// One past the highest address that has been loaded or written.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Flatten into a dense vector of `len()` words. This allocates every
+    /// address up to the highest one written, so use `regions` to look at
+    /// memory which may be sparse.
+    fn into_inner(self) -> Vec<Self::Word>;
+
+    /// Contiguous stretches which may hold nonzero words, in address order.
+    /// Everything outside them reads as zero.
+    fn regions(&self) -> Vec<(usize, &[Self::Word])>;
+
+    fn window(&self, start: usize, len: usize) -> Vec<Self::Word> {
+        (start..start + len).map(|idx| self[idx].clone()).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DenseMemory<W = Int> {
+    inner: Vec<W>,
+    zero: W,
+}
+
+impl<W: Word> Memory for DenseMemory<W> {
+    type Word = W;
+
+    /// Every word up to `len()` is allocated, so stop at 2^27 of them.
+    const MAX_LEN: usize = 1 << 27;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn into_inner(self) -> Vec<W> {
+        self.inner
+    }
+
+    fn regions(&self) -> Vec<(usize, &[W])> {
+        vec![(0, &self.inner[..])]
+    }
+}
+
+impl<W: Word> From<Vec<W>> for DenseMemory<W> {
+    fn from(v: Vec<W>) -> Self {
+        DenseMemory {
+            inner: v,
+            zero: W::zero(),
+        }
+    }
+}
+
+impl<W: Word, T: AsRef<[W]>> From<&T> for DenseMemory<W> {
+    fn from(v: &T) -> Self {
+        DenseMemory::from(v.as_ref().to_vec())
+    }
+}
+
+impl<W> Index<usize> for DenseMemory<W> {
+    type Output = W;
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.inner.len() {
+            // log::warn!("trying to read beyond mem: {}/{}", index, self.inner.len());
+            &self.zero
+        } else {
+            &self.inner[index]
+        }
+    }
+}
+
+impl<W: Word> IndexMut<usize> for DenseMemory<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        if index >= self.inner.len() {
+            self.inner.resize(index + 1, W::zero());
+        }
+        &mut self.inner[index]
+    }
+}
+
+const PAGE_BITS: usize = 10;
+const PAGE_SIZE: usize = 1 << PAGE_BITS;
+const PAGE_MASK: usize = PAGE_SIZE - 1;
+
+/// `PAGE_SIZE` words.
+type Page<W> = Vec<W>;
+
+/// Memory split into fixed size pages which are only allocated once written,
+/// so a stray write to a huge address costs one page rather than gigabytes.
+///
+/// Pages are shared copy-on-write: a clone copies only the page table, and
+/// a page is copied the first time either side writes to it.
+#[derive(Debug, Clone)]
+pub struct PagedMemory<W = Int> {
+    pages: HashMap<usize, Arc<Page<W>>>,
+    len: usize,
+    zero: W,
+}
+
+impl<W: Word> Default for PagedMemory<W> {
+    fn default() -> Self {
+        PagedMemory {
+            pages: HashMap::new(),
+            len: 0,
+            zero: W::zero(),
+        }
+    }
+}
+
+impl<W> PagedMemory<W> {
+    /// Number of pages currently allocated.
+    pub fn resident_pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Number of pages also held by another copy of this memory.
+    pub fn shared_pages(&self) -> usize {
+        self.pages
+            .values()
+            .filter(|page| Arc::strong_count(page) > 1)
+            .count()
+    }
+}
+
+impl<W: Word> Memory for PagedMemory<W> {
+    type Word = W;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn into_inner(self) -> Vec<W> {
+        let mut dense = vec![W::zero(); self.len];
+        for (start, words) in self.regions() {
+            dense[start..start + words.len()].clone_from_slice(words);
+        }
+        dense
+    }
+
+    fn regions(&self) -> Vec<(usize, &[W])> {
+        let mut pages = self.pages.iter().collect::<Vec<_>>();
+        pages.sort_by_key(|(page, _)| **page);
+        pages
+            .into_iter()
+            .map(|(page, data)| {
+                let start = page << PAGE_BITS;
+                let end = (start + PAGE_SIZE).min(self.len);
+                (start, &data[..end - start])
+            })
+            .collect()
+    }
+}
+
+impl<W: Word> From<Vec<W>> for PagedMemory<W> {
+    fn from(v: Vec<W>) -> Self {
+        let mut pages = HashMap::new();
+        for (page, chunk) in v.chunks(PAGE_SIZE).enumerate() {
+            if chunk.iter().all(Zero::is_zero) {
+                continue;
+            }
+            let mut data = vec![W::zero(); PAGE_SIZE];
+            data[..chunk.len()].clone_from_slice(chunk);
+            pages.insert(page, Arc::new(data));
+        }
+        PagedMemory {
+            pages,
+            len: v.len(),
+            zero: W::zero(),
+        }
+    }
+}
+
+impl<W> Index<usize> for PagedMemory<W> {
+    type Output = W;
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.pages.get(&(index >> PAGE_BITS)) {
+            Some(page) => &page[index & PAGE_MASK],
+            None => &self.zero,
+        }
+    }
+}
+
+impl<W: Word> IndexMut<usize> for PagedMemory<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        if index >= self.len {
+            self.len = index + 1;
+        }
+        let page = self
+            .pages
+            .entry(index >> PAGE_BITS)
+            .or_insert_with(|| Arc::new(vec![W::zero(); PAGE_SIZE]));
+        &mut Arc::make_mut(page)[index & PAGE_MASK]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn read_without_extend() {
+        let m: DenseMemory = DenseMemory::from(&[]);
+        assert_eq!(m[0], 0);
+        assert_eq!(m[10], 0);
+        assert_eq!(m[100], 0);
+        assert_eq!(m[1000], 0);
+        assert_eq!(m[10000000], 0);
+        assert_eq!(m.len(), 0)
+    }
+
+    #[test]
+    fn read_mut() {
+        let mut m: DenseMemory = DenseMemory::from(&[]);
+        m[2] = 10;
+        assert_eq!(m[1], 0);
+        assert_eq!(m[2], 10);
+        assert_eq!(m[5], 0);
+        assert_eq!(m.len(), 3);
+        {
+            let dst = &mut m[5];
+            *dst = 50;
+        }
+        assert_eq!(m[1], 0);
+        assert_eq!(m[2], 10);
+        assert_eq!(m[5], 50);
+        assert_eq!(m.len(), 6);
+    }
+
+    #[test]
+    fn paged_sparse_writes() {
+        let mut m: PagedMemory = PagedMemory::from(vec![1, 2, 3]);
+        assert_eq!(m.resident_pages(), 1);
+        m[1 << 40] = 7;
+        m[(1 << 40) + 1] = 8;
+        assert_eq!(m[1 << 40], 7);
+        assert_eq!(m[(1 << 40) + 1], 8);
+        assert_eq!(m[1 << 41], 0);
+        assert_eq!(m[2], 3);
+        assert_eq!(m.resident_pages(), 2);
+        assert_eq!(m.len(), (1 << 40) + 2);
+    }
+
+    #[test]
+    fn paged_matches_dense() {
+        let init = (0..3000).map(|x| x % 7).collect::<Vec<Int>>();
+        let mut dense = DenseMemory::from(init.clone());
+        let mut paged = PagedMemory::from(init);
+        for idx in &[0, 1023, 1024, 2999, 3000, 5000] {
+            dense[*idx] = *idx as Int;
+            paged[*idx] = *idx as Int;
+        }
+        assert_eq!(paged.len(), dense.len());
+        assert_eq!(paged.window(1020, 8), dense.window(1020, 8));
+        assert_eq!(paged.into_inner(), dense.into_inner());
+    }
+
+    #[test]
+    fn paged_clones_share_pages() {
+        let init = (0..3000).collect::<Vec<Int>>();
+        let mut original = PagedMemory::from(init.clone());
+        let mut copy = original.clone();
+        assert_eq!(copy.shared_pages(), 3);
+
+        copy[5] = -1;
+        assert_eq!(copy.shared_pages(), 2);
+        assert_eq!(original[5], 5);
+        original[2048] = -2;
+        assert_eq!(original.shared_pages(), 1);
+        assert_eq!(copy[2048], 2048);
+        assert_eq!(copy.window(4, 2), vec![4, -1]);
+    }
+}
@@ -0,0 +1,256 @@
+use super::{Int, IntCode, Memory};
+use std::collections::VecDeque;
+
+/// What one executed instruction changed, so it can be undone.
+#[derive(Debug, Clone)]
+struct Entry<W> {
+    pc: usize,
+    relative_base: Int,
+    /// The address written and the value it held before.
+    write: Option<(usize, W)>,
+    input: Option<W>,
+    output: Option<W>,
+}
+
+/// An undo log of the most recently executed instructions.
+#[derive(Debug, Clone)]
+pub(crate) struct Journal<W> {
+    entries: VecDeque<Entry<W>>,
+    capacity: usize,
+    current: Option<Entry<W>>,
+}
+
+impl<W> Journal<W> {
+    fn new(capacity: usize) -> Journal<W> {
+        Journal {
+            entries: VecDeque::new(),
+            capacity,
+            current: None,
+        }
+    }
+
+    /// Start recording the instruction at `pc`.
+    pub(crate) fn begin(&mut self, pc: usize, relative_base: Int) {
+        self.current = Some(Entry {
+            pc,
+            relative_base,
+            write: None,
+            input: None,
+            output: None,
+        });
+    }
+
+    pub(crate) fn record_write(&mut self, addr: usize, old: W) {
+        if let Some(entry) = &mut self.current {
+            entry.write = Some((addr, old));
+        }
+    }
+
+    pub(crate) fn record_input(&mut self, input: W) {
+        if let Some(entry) = &mut self.current {
+            entry.input = Some(input);
+        }
+    }
+
+    pub(crate) fn record_output(&mut self, output: W) {
+        if let Some(entry) = &mut self.current {
+            entry.output = Some(output);
+        }
+    }
+
+    /// The instruction finished, so keep its entry.
+    pub(crate) fn commit(&mut self) {
+        if let Some(entry) = self.current.take() {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            self.entries.push_back(entry);
+        }
+    }
+}
+
+/// What a `step_back` or `run_back_to` undid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rewind<W = Int> {
+    pub steps: u64,
+    /// Values output by the undone instructions, in the order they were
+    /// output. The IO device or caller already has them.
+    pub outputs: Vec<W>,
+}
+
+impl<IO, M: Memory> IntCode<IO, M> {
+    /// Keep an undo journal of the last `capacity` instructions executed, so
+    /// the machine can be stepped backwards. A capacity of 0 turns it off.
+    ///
+    /// Writes to mapped devices can't be undone and aren't journaled.
+    pub fn set_journal(&mut self, capacity: usize) {
+        self.journal = if capacity > 0 {
+            Some(Journal::new(capacity))
+        } else {
+            None
+        };
+    }
+
+    /// How many instructions can currently be undone.
+    pub fn journal_len(&self) -> usize {
+        self.journal.as_ref().map_or(0, |j| j.entries.len())
+    }
+
+    /// Undo the last `n` instructions, or as many as the journal holds.
+    ///
+    /// Memory, `pc` and the relative base go back to how they were, and
+    /// input the undone instructions read goes back on the front of the
+    /// pending input, so running forward again replays the same steps.
+    /// Use `discard_input` to take a different path instead.
+    pub fn step_back(&mut self, n: u64) -> Rewind<M::Word> {
+        let mut rewind = Rewind {
+            steps: 0,
+            outputs: Vec::new(),
+        };
+        while rewind.steps < n && self.undo(&mut rewind) {}
+        rewind.outputs.reverse();
+        rewind
+    }
+
+    /// Undo instructions until the machine is about to execute the one at
+    /// `pc`, at least one step back. If the journal runs out first, the
+    /// machine is left at the oldest state it holds; check `pc()` to tell.
+    pub fn run_back_to(&mut self, pc: usize) -> Rewind<M::Word> {
+        let mut rewind = Rewind {
+            steps: 0,
+            outputs: Vec::new(),
+        };
+        while self.undo(&mut rewind) && self.pc != pc {}
+        rewind.outputs.reverse();
+        rewind
+    }
+
+    /// Drop any input waiting to be read, returning it.
+    pub fn discard_input(&mut self) -> Vec<M::Word> {
+        self.pending_input.drain(..).collect()
+    }
+
+    fn undo(&mut self, rewind: &mut Rewind<M::Word>) -> bool {
+        let entry = match self.journal.as_mut().and_then(|j| j.entries.pop_back()) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        if let Some((addr, old)) = entry.write {
+            let new = std::mem::replace(&mut self.inner[addr], old);
+            self.icache.invalidate(addr);
+            if let Some(loops) = &mut self.loops {
+                loops.record_write(addr, &new, &self.inner[addr]);
+            }
+        }
+        if let Some(input) = entry.input {
+            self.pending_input.push_front(input);
+        }
+        rewind.outputs.extend(entry.output);
+        // states seen after this point may be visited again
+        if let Some(loops) = &mut self.loops {
+            loops.record_io();
+        }
+        self.pc = entry.pc;
+        self.relative_base = entry.relative_base;
+        self.halt = false;
+        self.steps -= 1;
+        rewind.steps += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm::assemble;
+    use crate::intcode::Event;
+
+    /// Adds up its inputs until it reads a zero, printing the running total.
+    const SUM: &str = "
+        loop:   in [x]
+                jf [x], #done
+                add [x], [total], [total]
+                out [total]
+                jt #1, #loop
+        done:   hlt
+        x:      .data 0
+        total:  .data 0
+    ";
+
+    #[test]
+    fn step_back_replays_the_same_run() {
+        let code = assemble(SUM).unwrap();
+        let len = code.len();
+        let mut ic = IntCode::resumable(code.clone());
+        ic.set_journal(100);
+        for x in &[3, 4, 0] {
+            ic.provide_input(*x);
+        }
+        let mut outputs = Vec::new();
+        while let Event::Output(x) = ic.run_until_event().unwrap() {
+            outputs.push(x);
+        }
+        assert_eq!(outputs, vec![3, 7]);
+        assert!(ic.halt);
+        let end = ic.peek_range(0, len);
+
+        let rewind = ic.step_back(u64::MAX);
+        assert_eq!(rewind.steps, 13);
+        assert_eq!(rewind.outputs, vec![3, 7]);
+        assert_eq!(ic.pc(), 0);
+        assert_eq!(ic.steps(), 0);
+        assert_eq!(ic.peek_range(0, len), code);
+        assert_eq!(ic.discard_input(), vec![3, 4, 0]);
+
+        for x in &[3, 4, 0] {
+            ic.provide_input(*x);
+        }
+        while ic.run_until_event().unwrap() != Event::Halted {}
+        assert_eq!(ic.peek_range(0, len), end);
+    }
+
+    #[test]
+    fn take_a_different_path() {
+        let code = assemble(SUM).unwrap();
+        let total = code.len() - 1;
+        let mut ic = IntCode::resumable(code);
+        ic.set_journal(100);
+        ic.provide_input(5);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(5));
+        ic.provide_input(6);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(11));
+
+        // back to just before the second input was read
+        let rewind = ic.run_back_to(0);
+        assert_eq!(rewind.outputs, vec![11]);
+        assert_eq!(ic.peek(total), 5);
+        assert_eq!(ic.discard_input(), vec![6]);
+        ic.provide_input(1);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(6));
+    }
+
+    #[test]
+    fn journal_is_bounded() {
+        let code = assemble(SUM).unwrap();
+        let mut ic = IntCode::resumable(code);
+        ic.set_journal(3);
+        for x in 1..10 {
+            ic.provide_input(x);
+        }
+        for _ in 0..20 {
+            ic.step().unwrap();
+        }
+        assert_eq!(ic.journal_len(), 3);
+        let pc = ic.pc();
+        assert_eq!(ic.step_back(5).steps, 3);
+        assert_eq!(ic.steps(), 17);
+        ic.step().unwrap();
+        ic.step().unwrap();
+        ic.step().unwrap();
+        assert_eq!(ic.pc(), pc);
+        // the oldest state is kept when the target isn't in the journal
+        ic.run_back_to(1000);
+        assert_eq!(ic.steps(), 17);
+        assert_eq!(ic.step_back(1).steps, 0);
+    }
+}
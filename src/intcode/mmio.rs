@@ -0,0 +1,254 @@
+use super::{Int, IntCode, Memory, Word};
+use anyhow::{anyhow as ah, Result};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+/// A peripheral which answers for a range of addresses, so programs reach
+/// it with ordinary reads and writes instead of `in` and `out`.
+///
+/// Offsets are relative to the start of the range the device is mapped at.
+pub trait MappedDevice<W = Int>: Send {
+    fn read(&mut self, offset: usize) -> Result<W>;
+    fn write(&mut self, offset: usize, value: W) -> Result<()>;
+}
+
+type Shared<W> = Arc<Mutex<dyn MappedDevice<W>>>;
+
+/// The devices mapped into one machine's memory.
+#[derive(Clone)]
+pub(crate) struct DeviceMap<W> {
+    devices: Vec<(Range<usize>, Shared<W>)>,
+    /// Whether a device was read or written since the last `take_accessed`.
+    accessed: bool,
+}
+
+impl<W: Word> DeviceMap<W> {
+    fn new() -> DeviceMap<W> {
+        DeviceMap {
+            devices: Vec::new(),
+            accessed: false,
+        }
+    }
+
+    fn insert(&mut self, range: Range<usize>, device: Shared<W>) -> Result<()> {
+        if range.start >= range.end {
+            return Err(ah!("empty device range {:?}", range));
+        }
+        if let Some((other, _)) = self
+            .devices
+            .iter()
+            .find(|(r, _)| r.start < range.end && range.start < r.end)
+        {
+            return Err(ah!("device range {:?} overlaps {:?}", range, other));
+        }
+        self.devices.push((range, device));
+        Ok(())
+    }
+
+    #[inline]
+    fn find(&self, addr: usize) -> Option<(usize, &Shared<W>)> {
+        self.devices
+            .iter()
+            .find(|(r, _)| r.contains(&addr))
+            .map(|(r, dev)| (addr - r.start, dev))
+    }
+
+    /// The value a device gives for `addr`, or `None` if nothing is mapped
+    /// there.
+    pub(crate) fn read(&mut self, addr: usize) -> Option<Result<W>> {
+        let (offset, dev) = self.find(addr)?;
+        let value = dev.lock().unwrap().read(offset);
+        self.accessed = true;
+        Some(value)
+    }
+
+    /// Hand `value` to the device at `addr`, or give it back if nothing is
+    /// mapped there.
+    pub(crate) fn write(&mut self, addr: usize, value: W) -> std::result::Result<Result<()>, W> {
+        match self.find(addr) {
+            Some((offset, dev)) => {
+                let res = dev.lock().unwrap().write(offset, value);
+                self.accessed = true;
+                Ok(res)
+            }
+            None => Err(value),
+        }
+    }
+
+    pub(crate) fn take_accessed(&mut self) -> bool {
+        std::mem::replace(&mut self.accessed, false)
+    }
+}
+
+impl<IO, M: Memory> IntCode<IO, M> {
+    /// Map `device` over `range`, so instructions reading or writing those
+    /// addresses call it rather than touching memory. Returns a handle for
+    /// looking at the device afterwards.
+    ///
+    /// Only data accesses go to the device; instructions are always fetched
+    /// from memory. Forks of the machine share its devices. A device access
+    /// counts as IO for `detect_loops`.
+    pub fn map_device<D: MappedDevice<M::Word> + 'static>(
+        &mut self,
+        range: Range<usize>,
+        device: D,
+    ) -> Result<Arc<Mutex<D>>> {
+        let device = Arc::new(Mutex::new(device));
+        self.devices
+            .get_or_insert_with(DeviceMap::new)
+            .insert(range, device.clone())?;
+        Ok(device)
+    }
+}
+
+/// A grid of pixels, one word each, laid out a row at a time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Framebuffer {
+    pub width: usize,
+    pub pixels: Vec<Int>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize) -> Framebuffer {
+        Framebuffer {
+            width,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    /// The addresses to map it at, starting from `base`.
+    pub fn range(&self, base: usize) -> Range<usize> {
+        base..base + self.pixels.len()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Int]> {
+        self.pixels.chunks(self.width)
+    }
+}
+
+impl MappedDevice for Framebuffer {
+    fn read(&mut self, offset: usize) -> Result<Int> {
+        Ok(self.pixels[offset])
+    }
+
+    fn write(&mut self, offset: usize, value: Int) -> Result<()> {
+        self.pixels[offset] = value;
+        Ok(())
+    }
+}
+
+/// A single register which reads as a new pseudo-random number in
+/// `0..bound` every time. Writing to it reseeds the generator.
+#[derive(Debug, Clone)]
+pub struct Random {
+    state: u64,
+    bound: Int,
+}
+
+impl Random {
+    pub fn new(seed: u64, bound: Int) -> Random {
+        Random {
+            state: seed | 1,
+            bound: bound.max(1),
+        }
+    }
+}
+
+impl MappedDevice for Random {
+    fn read(&mut self, _: usize) -> Result<Int> {
+        // xorshift64
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        Ok((self.state % self.bound as u64) as Int)
+    }
+
+    fn write(&mut self, _: usize, value: Int) -> Result<()> {
+        self.state = value as u64 | 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm::assemble;
+    use crate::intcode::{ErrorKind, Event};
+
+    /// Counts up on every read.
+    struct Ticker(Int);
+
+    impl MappedDevice for Ticker {
+        fn read(&mut self, _: usize) -> Result<Int> {
+            self.0 += 1;
+            Ok(self.0)
+        }
+
+        fn write(&mut self, _: usize, _: Int) -> Result<()> {
+            Err(ah!("ticker is read only"))
+        }
+    }
+
+    #[test]
+    fn reads_and_writes_reach_the_device() {
+        // draw a diagonal on a 3x3 framebuffer mapped at 100
+        let code = assemble(
+            "
+                    arb #100
+            loop:   add #1, #0, rb
+                    arb #4
+                    add [i], #1, [i]
+                    lt [i], #3, [t]
+                    jt [t], #loop
+                    out [104]
+                    hlt
+            i:      .data 0
+            t:      .data 0
+            ",
+        )
+        .unwrap();
+        let mut ic = IntCode::resumable(code);
+        let fb = Framebuffer::new(3, 3);
+        let fb = ic.map_device(fb.range(100), fb).unwrap();
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(1));
+        let fb = fb.lock().unwrap();
+        let rows = fb.rows().collect::<Vec<_>>();
+        assert_eq!(rows, vec![&[1, 0, 0], &[0, 1, 0], &[0, 0, 1]]);
+        assert_eq!(ic.peek(104), 0);
+    }
+
+    #[test]
+    fn ticker_and_faults() {
+        let code = assemble("out [1000]\nout [1000]\nadd #1, #1, [1000]\nhlt").unwrap();
+        let mut ic = IntCode::resumable(code);
+        ic.map_device(1000..1001, Ticker(0)).unwrap();
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(1));
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(2));
+        let err = ic.run_until_event().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::Io(_)), "{}", err);
+        assert_eq!(
+            err.to_string(),
+            "io device failed: ticker is read only (pc=4 word=1101 rb=0)"
+        );
+        // memory under the device is untouched
+        assert_eq!(ic.peek(1000), 0);
+    }
+
+    #[test]
+    fn polling_a_device_is_not_a_loop() {
+        let code = assemble("loop: lt [1000], #5, [t]\njt [t], #loop\nhlt\nt: .data 0").unwrap();
+        let mut ic = IntCode::resumable(code);
+        ic.detect_loops(true);
+        ic.map_device(1000..1001, Ticker(0)).unwrap();
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+    }
+
+    #[test]
+    fn overlapping_ranges() {
+        let mut ic = IntCode::resumable(vec![99]);
+        ic.map_device(10..20, Random::new(1, 10)).unwrap();
+        assert!(ic.map_device(15..25, Random::new(1, 10)).is_err());
+        assert!(ic.map_device(5..5, Random::new(1, 10)).is_err());
+        assert!(ic.map_device(20..25, Random::new(1, 10)).is_ok());
+    }
+}
@@ -1,253 +1,623 @@
-use crate::intcode::intcode_io::{FusedIO, Input, Output, VecIO};
-use crate::intcode::intcode_mem::Memory;
-use crate::intcode::opcodes::{parse_instruction, Instruction, ParameterMode, ParameterModes};
-use anyhow::Result;
+use crate::intcode::intcode_io::{FusedIO, Input, InputExhausted, Output};
+use crate::intcode::opcodes::{Instruction, ParameterMode};
+pub use arith::Arithmetic;
+pub use error::{ErrorKind, IntCodeError};
+use icache::{Decoded, InstructionCache};
+pub use intcode_mem::{DenseMemory, Memory, PagedMemory};
+use journal::Journal;
+pub use journal::Rewind;
+use mmio::DeviceMap;
+pub use mmio::{Framebuffer, MappedDevice, Random};
+use num::Zero;
+use profile::Profiler;
+pub use snapshot::Snapshot;
+use std::collections::VecDeque;
+use watchdog::LoopDetector;
 
 pub type Int = i64;
 
+type Result<T, W = Int> = std::result::Result<T, IntCodeError<W>>;
+
+pub mod aot;
+mod arith;
+mod error;
+mod icache;
 mod opcodes;
 
+pub mod asm;
+pub mod cfg;
+pub mod debugger;
+pub mod disasm;
+pub mod fuzz;
+pub mod image;
 pub(crate) mod intcode_io;
+mod intcode_mem;
+mod journal;
+mod mmio;
+pub mod patch;
+pub mod profile;
+pub mod scheduler;
+mod snapshot;
+pub mod symbolic;
+pub mod transcript;
+mod watchdog;
+mod word;
 
-pub fn run_intcode(intcode: Vec<Int>, input: Vec<Int>) -> Result<(Vec<Int>, Vec<Int>)> {
-    let mut ic = IntCode::new(intcode, VecIO::input(input), VecIO::default());
-    ic.run_till_end()?;
-    let (mem, FusedIO { output, .. }) = ic.emit();
-    Ok((mem, output.into_vec()))
-}
-
-mod intcode_mem {
-    use super::Int;
-    use std::ops::{Index, IndexMut};
-    #[derive(Debug, Clone)]
-    pub struct Memory {
-        inner: Vec<Int>,
-    }
-
-    impl Memory {
-        #[inline]
-        #[allow(dead_code)]
-        fn len(&self) -> usize {
-            self.inner.len()
-        }
+pub use word::Word;
 
-        pub fn into_inner(self) -> Vec<Int> {
-            self.inner
-        }
+pub fn run_intcode(intcode: Vec<Int>, input: Vec<Int>) -> Result<(Vec<Int>, Vec<Int>)> {
+    let mut ic = IntCode::resumable(intcode);
+    for x in input {
+        ic.provide_input(x);
     }
-
-    impl From<Vec<Int>> for Memory {
-        fn from(v: Vec<Int>) -> Self {
-            Memory { inner: v }
+    let mut output = Vec::new();
+    loop {
+        match ic.run_until_event()? {
+            Event::Output(x) => output.push(x),
+            Event::Halted => break,
+            Event::NeedsInput => return Err(ic.fault(ErrorKind::InputExhausted)),
         }
     }
+    let (mem, _) = ic.emit();
+    Ok((mem, output))
+}
 
-    impl<T: AsRef<[Int]>> From<&T> for Memory {
-        fn from(v: &T) -> Self {
-            Memory {
-                inner: v.as_ref().to_vec(),
-            }
-        }
-    }
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Accesses {
+    pub reads: Vec<usize>,
+    pub write: Option<usize>,
+}
 
-    impl Index<usize> for Memory {
-        type Output = Int;
-        fn index(&self, index: usize) -> &Self::Output {
-            if index >= self.inner.len() {
-                // log::warn!("trying to read beyond mem: {}/{}", index, self.inner.len());
-                &0
-            } else {
-                &self.inner[index]
-            }
-        }
-    }
+/// Why a resumable machine stopped running.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event<W = Int> {
+    NeedsInput,
+    Output(W),
+    Halted,
+}
 
-    impl IndexMut<usize> for Memory {
-        fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-            if index >= self.inner.len() {
-                self.inner.resize(index + 1, 0);
-            }
-            &mut self.inner[index]
-        }
-    }
-
-    #[cfg(test)]
-    mod test {
-        use super::*;
-        #[test]
-        fn read_without_extend() {
-            let m = Memory::from(&[]);
-            assert_eq!(m[0], 0);
-            assert_eq!(m[10], 0);
-            assert_eq!(m[100], 0);
-            assert_eq!(m[1000], 0);
-            assert_eq!(m[10000000], 0);
-            assert_eq!(m.len(), 0)
-        }
-
-        #[test]
-        fn read_mut() {
-            let mut m = Memory::from(&[]);
-            m[2] = 10;
-            assert_eq!(m[1], 0);
-            assert_eq!(m[2], 10);
-            assert_eq!(m[5], 0);
-            assert_eq!(m.len(), 3);
-            {
-                let dst = &mut m[5];
-                *dst = 50;
-            }
-            assert_eq!(m[1], 0);
-            assert_eq!(m[2], 10);
-            assert_eq!(m[5], 50);
-            assert_eq!(m.len(), 6);
-        }
-    }
+/// How a `run_with_budget` call ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RunOutcome {
+    Halted,
+    BudgetExhausted,
+    LoopDetected,
 }
 
+/// An Intcode machine with IO device `IO`, computing with the words of its
+/// memory `M`.
 #[derive(Clone)]
-pub struct IntCode<IO> {
-    inner: Memory,
+pub struct IntCode<IO, M: Memory = DenseMemory> {
+    inner: M,
     pc: usize,
     relative_base: Int,
     pub halt: bool,
+    pending_input: VecDeque<M::Word>,
     io_device: IO,
+    devices: Option<DeviceMap<M::Word>>,
+    steps: u64,
+    arithmetic: Arithmetic,
+    loops: Option<LoopDetector<M>>,
+    journal: Option<Journal<M::Word>>,
+    profiler: Option<Profiler>,
+    icache: InstructionCache<M::Word>,
 }
 
-impl<IO> std::fmt::Debug for IntCode<IO> {
+impl<IO, M: Memory> std::fmt::Debug for IntCode<IO, M> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("IntCode")
             // .field("inner", &self.inner)
             .field("pc", &self.pc)
             .field("relative_base", &self.relative_base)
             .field("halt", &self.halt)
+            .field("pending_input", &self.pending_input)
+            .field("steps", &self.steps)
             .finish()
     }
 }
 
 impl<I: Input, O: Output> IntCode<FusedIO<I, O>> {
     pub fn new(intcode: Vec<Int>, input: I, output: O) -> IntCode<FusedIO<I, O>> {
-        IntCode {
-            inner: Memory::from(intcode),
-            pc: 0,
-            relative_base: 0,
-            halt: false,
-            io_device: FusedIO { input, output },
-        }
+        IntCode::with_device(intcode, FusedIO { input, output })
     }
 }
 
-impl<IO: Input + Output> IntCode<IO> {
-    pub fn new_from_device(intcode: Vec<Int>, io_device: IO) -> IntCode<IO> {
+impl IntCode<()> {
+    /// A machine with no IO device, driven with `run_until_event`.
+    pub fn resumable(intcode: Vec<Int>) -> IntCode<()> {
+        IntCode::with_device(intcode, ())
+    }
+}
+
+impl<IO: Clone, M: Memory> IntCode<IO, M> {
+    /// An independent copy of this machine, to run down another branch of
+    /// a search.
+    ///
+    /// With `PagedMemory` the copies share memory pages until one of them
+    /// writes, so a fork costs about as much as the page table. Other
+    /// backends are copied outright.
+    pub fn fork(&self) -> IntCode<IO, M> {
+        self.clone()
+    }
+}
+
+impl<IO, M: Memory> IntCode<IO, M> {
+    /// Load `intcode` into a machine attached to `io_device`.
+    ///
+    /// This is how to get a machine with another word type, for example
+    /// `IntCode::<(), DenseMemory<i128>>::with_device(program, ())`.
+    pub fn with_device(intcode: Vec<M::Word>, io_device: IO) -> IntCode<IO, M> {
+        let profiler = profile::global();
+        if let Some(p) = &profiler {
+            p.load(&intcode);
+        }
         IntCode {
-            inner: Memory::from(intcode),
+            icache: InstructionCache::new(intcode.len()),
+            inner: M::from(intcode),
             pc: 0,
             relative_base: 0,
             halt: false,
+            pending_input: VecDeque::new(),
             io_device,
+            devices: None,
+            steps: 0,
+            arithmetic: Arithmetic::default(),
+            loops: None,
+            journal: None,
+            profiler,
         }
     }
 
-    fn get_arg(&mut self, nth: usize, modes: ParameterModes) -> &mut Int {
-        let idx = self.pc + 1 + nth;
-        match modes.inner[nth] {
-            ParameterMode::Position => {
-                let pos = self.inner[idx] as usize;
-                &mut self.inner[pos]
-            }
-            ParameterMode::Immediate => &mut self.inner[idx],
+    /// Move the machine onto a different memory backend.
+    pub fn with_memory<N: Memory<Word = M::Word>>(self) -> IntCode<IO, N> {
+        IntCode {
+            inner: N::from(self.inner.into_inner()),
+            pc: self.pc,
+            relative_base: self.relative_base,
+            halt: self.halt,
+            pending_input: self.pending_input,
+            io_device: self.io_device,
+            devices: self.devices,
+            steps: self.steps,
+            arithmetic: self.arithmetic,
+            loops: self.loops.map(LoopDetector::with_memory),
+            journal: self.journal,
+            profiler: self.profiler,
+            icache: self.icache,
+        }
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn relative_base(&self) -> Int {
+        self.relative_base
+    }
+
+    /// Total number of instructions executed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Choose how `add` and `mul` handle overflow.
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.arithmetic = arithmetic;
+    }
+
+    /// Fail with `ErrorKind::InfiniteLoop` once the machine repeats an exact
+    /// earlier state without doing any IO in between. A loop is reported
+    /// within about twice its length of being entered, and never falsely.
+    pub fn detect_loops(&mut self, enabled: bool) {
+        self.loops = if enabled {
+            Some(LoopDetector::default())
+        } else {
+            None
+        };
+    }
+
+    pub fn peek(&self, addr: usize) -> M::Word {
+        self.inner[addr].clone()
+    }
+
+    pub fn peek_range(&self, start: usize, len: usize) -> Vec<M::Word> {
+        self.inner.window(start, len)
+    }
+
+    pub fn provide_input(&mut self, input: M::Word) {
+        self.pending_input.push_back(input);
+    }
+
+    /// Start recording this machine's execution into a fresh profiler.
+    pub fn attach_profiler(&mut self) -> Profiler {
+        let profiler = Profiler::new();
+        self.set_profiler(profiler.clone());
+        profiler
+    }
+
+    /// Record this machine's execution into `profiler`, which may be shared
+    /// with other machines running the same program.
+    pub fn set_profiler(&mut self, profiler: Profiler) {
+        profiler.load_regions(self.inner.regions());
+        self.profiler = Some(profiler);
+    }
+
+    fn fault(&self, kind: ErrorKind<M::Word>) -> IntCodeError<M::Word> {
+        self.fault_at(self.pc, kind)
+    }
+
+    fn fault_at(&self, pc: usize, kind: ErrorKind<M::Word>) -> IntCodeError<M::Word> {
+        IntCodeError {
+            kind,
+            pc,
+            word: self.inner[pc].clone(),
+            relative_base: self.relative_base,
+        }
+    }
+
+    /// `word` as an address, or the fault for using it as one.
+    #[inline]
+    fn address(&self, word: M::Word) -> Result<usize, M::Word> {
+        match word.to_addr() {
+            Some(addr) => Ok(addr),
+            None if word < M::Word::zero() => Err(self.fault(ErrorKind::NegativeAddress(word))),
+            None => Err(self.fault(ErrorKind::AddressOutOfRange(word))),
+        }
+    }
+
+    fn arg_addr(&self, decoded: &Decoded<M::Word>, nth: usize) -> Result<usize, M::Word> {
+        let arg = decoded.args[nth].clone();
+        let addr = match decoded.modes.inner[nth] {
+            ParameterMode::Position => arg,
+            ParameterMode::Immediate => return Ok(self.pc + 1 + nth),
             ParameterMode::Relative => {
-                let pos = self.relative_base + self.inner[idx];
-                &mut self.inner[pos as usize]
+                let base = M::Word::from(self.relative_base);
+                match base.add_overflowing(&arg) {
+                    (addr, false) => addr,
+                    (_, true) => {
+                        return Err(self.fault(ErrorKind::Overflow {
+                            op: decoded.instr.mnemonic(),
+                            lhs: base,
+                            rhs: arg,
+                        }))
+                    }
+                }
             }
+        };
+        self.address(addr)
+    }
+
+    #[inline]
+    fn get_arg(&mut self, decoded: &Decoded<M::Word>, nth: usize) -> Result<M::Word, M::Word> {
+        if decoded.modes.inner[nth] == ParameterMode::Immediate {
+            return Ok(decoded.args[nth].clone());
+        }
+        let addr = self.arg_addr(decoded, nth)?;
+        if let Some(devices) = &mut self.devices {
+            if let Some(res) = devices.read(addr) {
+                return res.map_err(|e| self.fault(ErrorKind::Io(e)));
+            }
+        }
+        Ok(self.inner[addr].clone())
+    }
+
+    fn put_arg(
+        &mut self,
+        decoded: &Decoded<M::Word>,
+        nth: usize,
+        value: M::Word,
+    ) -> Result<(), M::Word> {
+        if decoded.modes.inner[nth] == ParameterMode::Immediate {
+            return Err(self.fault(ErrorKind::WriteToImmediate));
         }
+        let addr = self.arg_addr(decoded, nth)?;
+        let value = match &mut self.devices {
+            Some(devices) => match devices.write(addr, value) {
+                Ok(res) => return res.map_err(|e| self.fault(ErrorKind::Io(e))),
+                Err(value) => value,
+            },
+            None => value,
+        };
+        let old = std::mem::replace(&mut self.inner[addr], value);
+        self.icache.invalidate(addr);
+        if let Some(journal) = &mut self.journal {
+            journal.record_write(addr, old.clone());
+        }
+        if let Some(loops) = &mut self.loops {
+            loops.record_write(addr, &old, &self.inner[addr]);
+        }
+        Ok(())
     }
 
-    pub fn run_one(&mut self) -> Result<()> {
-        let (instr, modes) = parse_instruction(self.inner[self.pc])?;
+    fn jump_target(&mut self, decoded: &Decoded<M::Word>) -> Result<usize, M::Word> {
+        let target = self.get_arg(decoded, 1)?;
+        self.address(target)
+    }
+
+    /// Fill the instruction cache for the whole loaded program up front.
+    ///
+    /// Worthwhile for a template machine which gets cloned for many short
+    /// runs, since each clone would otherwise start cold.
+    pub fn predecode(&mut self) {
+        for pc in 0..self.icache.capacity() {
+            if let Ok(decoded) = Decoded::read(&self.inner, pc) {
+                self.icache.insert(pc, decoded);
+            }
+        }
+    }
+
+    /// Decode the instruction at `pc`, going through the instruction cache.
+    #[inline]
+    fn decode(&mut self) -> Result<Decoded<M::Word>, M::Word> {
+        if let Some(decoded) = self.icache.get(self.pc) {
+            return Ok(decoded);
+        }
+        let decoded = Decoded::read(&self.inner, self.pc).map_err(|kind| self.fault(kind))?;
+        self.icache.insert(self.pc, decoded.clone());
+        Ok(decoded)
+    }
+
+    /// The data addresses the instruction at `pc` is about to read and write.
+    ///
+    /// Immediate operands are part of the instruction, so they only show up
+    /// here when they are (mis)used as a write destination.
+    pub fn pending_accesses(&self) -> Result<Accesses, M::Word> {
+        let decoded = Decoded::read(&self.inner, self.pc).map_err(|kind| self.fault(kind))?;
+        let data = |nth: usize| -> Result<Option<usize>, M::Word> {
+            if decoded.modes.inner[nth] == ParameterMode::Immediate {
+                Ok(None)
+            } else {
+                self.arg_addr(&decoded, nth).map(Some)
+            }
+        };
+        let mut acc = Accesses::default();
+        match decoded.instr {
+            Instruction::Add | Instruction::Mul | Instruction::LessThan | Instruction::EqualTo => {
+                acc.reads.extend(data(0)?);
+                acc.reads.extend(data(1)?);
+                acc.write = Some(self.arg_addr(&decoded, 2)?);
+            }
+            Instruction::Input => acc.write = Some(self.arg_addr(&decoded, 0)?),
+            Instruction::Output | Instruction::SetBase => acc.reads.extend(data(0)?),
+            Instruction::JumpTrue | Instruction::JumpFalse => {
+                let cond = match data(0)? {
+                    Some(addr) => self.inner[addr].clone(),
+                    None => decoded.args[0].clone(),
+                };
+                acc.reads.extend(data(0)?);
+                if cond.is_zero() != (decoded.instr == Instruction::JumpTrue) {
+                    acc.reads.extend(data(1)?);
+                }
+            }
+            Instruction::Halt => {}
+        }
+        Ok(acc)
+    }
+
+    /// Execute one instruction without touching an IO device.
+    ///
+    /// An `in` with no pending input is not executed; the machine reports
+    /// `NeedsInput` and stays on that instruction until `provide_input`.
+    pub fn step(&mut self) -> Result<Option<Event<M::Word>>, M::Word> {
+        if self.halt {
+            return Ok(Some(Event::Halted));
+        }
+        let decoded = self.decode()?;
+        let instr = decoded.instr;
+        let pc = self.pc;
+        let accesses = match self.profiler {
+            Some(_) => self.pending_accesses().ok(),
+            None => None,
+        };
+        if let Some(journal) = &mut self.journal {
+            journal.begin(pc, self.relative_base);
+        }
         let mut update_pc = true;
-        // log::trace!("{:?} {:?} {:?}", instr, modes, self);
+        let mut event = None;
+        // log::trace!("{:?} {:?}", decoded, self);
         match instr {
-            Instruction::Add => {
-                let lhs = *self.get_arg(0, modes);
-                let rhs = *self.get_arg(1, modes);
-                let dst = self.get_arg(2, modes);
-                *dst = lhs + rhs;
-            }
-            Instruction::Mul => {
-                let lhs = *self.get_arg(0, modes);
-                let rhs = *self.get_arg(1, modes);
-                let dst = self.get_arg(2, modes);
-                *dst = lhs * rhs;
+            Instruction::Add | Instruction::Mul => {
+                let lhs = self.get_arg(&decoded, 0)?;
+                let rhs = self.get_arg(&decoded, 1)?;
+                let result = self
+                    .arithmetic
+                    .apply(instr, lhs, rhs)
+                    .map_err(|kind| self.fault(kind))?;
+                self.put_arg(&decoded, 2, result)?;
             }
             Instruction::Input => {
-                let input = self.io_device.input()?;
-                let dst = self.get_arg(0, modes);
-                *dst = input;
+                let input = match self.pending_input.pop_front() {
+                    Some(x) => x,
+                    None => return Ok(Some(Event::NeedsInput)),
+                };
+                if let Some(journal) = &mut self.journal {
+                    journal.record_input(input.clone());
+                }
+                self.put_arg(&decoded, 0, input)?;
             }
             Instruction::Output => {
-                let src = *self.get_arg(0, modes);
-                self.io_device.output(src)?;
+                let src = self.get_arg(&decoded, 0)?;
+                if let Some(journal) = &mut self.journal {
+                    journal.record_output(src.clone());
+                }
+                event = Some(Event::Output(src));
             }
             Instruction::Halt => {
                 self.halt = true;
+                event = Some(Event::Halted);
             }
             Instruction::JumpTrue => {
-                let cond = *self.get_arg(0, modes);
-                if cond != 0 {
-                    self.pc = *self.get_arg(1, modes) as usize;
+                let cond = self.get_arg(&decoded, 0)?;
+                if !cond.is_zero() {
+                    self.pc = self.jump_target(&decoded)?;
                     update_pc = false;
                 }
             }
             Instruction::JumpFalse => {
-                let cond = *self.get_arg(0, modes);
-                if cond == 0 {
-                    self.pc = *self.get_arg(1, modes) as usize;
+                let cond = self.get_arg(&decoded, 0)?;
+                if cond.is_zero() {
+                    self.pc = self.jump_target(&decoded)?;
                     update_pc = false;
                 }
             }
             Instruction::LessThan => {
-                let lhs = *self.get_arg(0, modes);
-                let rhs = *self.get_arg(1, modes);
-                let dst = self.get_arg(2, modes);
-                *dst = if lhs < rhs { 1 } else { 0 };
+                let lhs = self.get_arg(&decoded, 0)?;
+                let rhs = self.get_arg(&decoded, 1)?;
+                self.put_arg(&decoded, 2, flag(lhs < rhs))?;
             }
             Instruction::EqualTo => {
-                let lhs = *self.get_arg(0, modes);
-                let rhs = *self.get_arg(1, modes);
-                let dst = self.get_arg(2, modes);
-                *dst = if lhs == rhs { 1 } else { 0 };
+                let lhs = self.get_arg(&decoded, 0)?;
+                let rhs = self.get_arg(&decoded, 1)?;
+                self.put_arg(&decoded, 2, flag(lhs == rhs))?;
             }
             Instruction::SetBase => {
-                let offset = *self.get_arg(0, modes);
-                self.relative_base += offset;
-                debug_assert!(self.relative_base >= 0);
+                let offset = self.get_arg(&decoded, 0)?;
+                let base = offset
+                    .to_int()
+                    .and_then(|o| self.relative_base.checked_add(o));
+                self.relative_base = base.ok_or_else(|| {
+                    self.fault(ErrorKind::Overflow {
+                        op: instr.mnemonic(),
+                        lhs: M::Word::from(self.relative_base),
+                        rhs: offset.clone(),
+                    })
+                })?;
             }
         }
         if update_pc {
             self.pc += 1 + instr.arity();
         }
+        self.steps += 1;
+        if let Some(journal) = &mut self.journal {
+            journal.commit();
+        }
+        if let Some(profiler) = &self.profiler {
+            profiler.record(pc, instr, accesses);
+        }
+        if let Some(loops) = &mut self.loops {
+            let mapped_io = match &mut self.devices {
+                Some(devices) => devices.take_accessed(),
+                None => false,
+            };
+            if instr == Instruction::Input || instr == Instruction::Output || mapped_io {
+                loops.record_io();
+            } else if loops.check(self.pc, self.relative_base, &self.inner) {
+                return Err(self.fault(ErrorKind::InfiniteLoop));
+            }
+        }
+        Ok(event)
+    }
+
+    /// Run until the machine produces output, halts, or blocks on input.
+    pub fn run_until_event(&mut self) -> Result<Event<M::Word>, M::Word> {
+        loop {
+            if let Some(event) = self.step()? {
+                return Ok(event);
+            }
+        }
+    }
+
+    pub fn device(&self) -> &IO {
+        &self.io_device
+    }
+
+    pub fn device_mut(&mut self) -> &mut IO {
+        &mut self.io_device
+    }
+
+    /// The memory as a dense vector, and the device. Flattening a sparse
+    /// memory with a write far away needs as much space as that address;
+    /// use `into_device` when the memory isn't wanted.
+    pub fn emit(self) -> (Vec<M::Word>, IO) {
+        (self.inner.into_inner(), self.io_device)
+    }
+
+    pub fn into_device(self) -> IO {
+        self.io_device
+    }
+}
+
+impl<IO: Input + Output> IntCode<IO> {
+    pub fn new_from_device(intcode: Vec<Int>, io_device: IO) -> IntCode<IO> {
+        IntCode::with_device(intcode, io_device)
+    }
+}
+
+impl<IO: Input<M::Word> + Output<M::Word>, M: Memory> IntCode<IO, M> {
+    pub fn run_one(&mut self) -> Result<(), M::Word> {
+        let pc = self.pc;
+        match self.step()? {
+            Some(Event::NeedsInput) => {
+                let input = self.io_device.input().map_err(|e| self.io_fault(pc, e))?;
+                self.provide_input(input);
+                self.step()?;
+            }
+            Some(Event::Output(x)) => {
+                let res = self.io_device.output(x);
+                res.map_err(|e| self.io_fault(pc, e))?;
+            }
+            Some(Event::Halted) | None => {}
+        }
         Ok(())
     }
 
-    pub fn run_till_end(&mut self) -> Result<()> {
+    fn io_fault(&self, pc: usize, e: anyhow::Error) -> IntCodeError<M::Word> {
+        if e.is::<InputExhausted>() {
+            self.fault_at(pc, ErrorKind::InputExhausted)
+        } else {
+            self.fault_at(pc, ErrorKind::Io(e))
+        }
+    }
+
+    pub fn run_till_end(&mut self) -> Result<(), M::Word> {
         while !self.halt {
             self.run_one()?
         }
+        log::debug!("halted after {} steps", self.steps);
         Ok(())
     }
 
-    pub fn emit(self) -> (Vec<Int>, IO) {
-        (self.inner.into_inner(), self.io_device)
+    /// Like `run_till_end`, but give up after `max_steps` more instructions.
+    ///
+    /// A loop found by `detect_loops` is reported as an outcome rather than
+    /// an error.
+    pub fn run_with_budget(&mut self, max_steps: u64) -> Result<RunOutcome, M::Word> {
+        let limit = self.steps.saturating_add(max_steps);
+        while !self.halt {
+            if self.steps >= limit {
+                return Ok(RunOutcome::BudgetExhausted);
+            }
+            match self.run_one() {
+                Err(IntCodeError {
+                    kind: ErrorKind::InfiniteLoop,
+                    ..
+                }) => return Ok(RunOutcome::LoopDetected),
+                res => res?,
+            }
+        }
+        Ok(RunOutcome::Halted)
+    }
+}
+
+/// The result of a comparison as a word.
+fn flag<W: Word>(b: bool) -> W {
+    if b {
+        W::one()
+    } else {
+        W::zero()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Int, IntCode};
+    use super::{
+        Arithmetic, DenseMemory, ErrorKind, Event, Int, IntCode, Memory, PagedMemory, RunOutcome,
+    };
+    use crate::intcode::asm::assemble;
     use crate::intcode::intcode_io::{NullIO, VecIO};
     use crate::intcode::run_intcode;
     use anyhow::Result;
+    use num::BigInt;
 
     fn single_input_single_output(code: Vec<Int>, input: Int) -> Result<Int> {
         let (_, output) = run_intcode(code, vec![input])?;
@@ -436,4 +806,340 @@ mod test {
         let (_, output) = run_intcode(code.clone(), vec![]).unwrap();
         assert_eq!(output[0], magic);
     }
+
+    #[test]
+    fn resumable_yields_on_io() {
+        let mut ic = IntCode::resumable(vec![3, 0, 4, 0, 3, 0, 4, 0, 99]);
+        assert_eq!(ic.run_until_event().unwrap(), Event::NeedsInput);
+        assert_eq!(ic.run_until_event().unwrap(), Event::NeedsInput);
+        ic.provide_input(5);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(5));
+        ic.provide_input(6);
+        ic.provide_input(7);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(6));
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+    }
+
+    #[test]
+    fn resumable_feedback_loop() {
+        let code = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let mut amps = [9, 8, 7, 6, 5]
+            .iter()
+            .map(|phase| {
+                let mut ic = IntCode::resumable(code.clone());
+                ic.provide_input(*phase);
+                ic
+            })
+            .collect::<Vec<_>>();
+        let mut signal = 0;
+        'feedback: loop {
+            for amp in amps.iter_mut() {
+                amp.provide_input(signal);
+                match amp.run_until_event().unwrap() {
+                    Event::Output(x) => signal = x,
+                    Event::Halted => break 'feedback,
+                    Event::NeedsInput => panic!("amplifier starved"),
+                }
+            }
+        }
+        assert_eq!(signal, 139629729);
+    }
+
+    fn fault_kind(code: Vec<Int>, input: Vec<Int>) -> (ErrorKind, usize, Int) {
+        let err = run_intcode(code, input).unwrap_err();
+        (err.kind, err.pc, err.word)
+    }
+
+    #[test]
+    fn structured_errors() {
+        match fault_kind(vec![1101, 1, 1, 5, 42, 0], vec![]) {
+            (ErrorKind::InvalidOpcode(42), 4, 42) => {}
+            e => panic!("unexpected: {:?}", e),
+        }
+        match fault_kind(vec![30001, 0, 0, 0, 99], vec![]) {
+            (ErrorKind::InvalidParameterMode(3), 0, 30001) => {}
+            e => panic!("unexpected: {:?}", e),
+        }
+        match fault_kind(vec![109, -5, 204, 1, 99], vec![]) {
+            (ErrorKind::NegativeAddress(-4), 2, 204) => {}
+            e => panic!("unexpected: {:?}", e),
+        }
+        match fault_kind(vec![1105, 1, -1], vec![]) {
+            (ErrorKind::NegativeAddress(-1), 0, 1105) => {}
+            e => panic!("unexpected: {:?}", e),
+        }
+        match fault_kind(vec![11101, 1, 1, 0, 99], vec![]) {
+            (ErrorKind::WriteToImmediate, 0, 11101) => {}
+            e => panic!("unexpected: {:?}", e),
+        }
+        match fault_kind(vec![3, 0, 3, 0, 99], vec![7]) {
+            (ErrorKind::InputExhausted, 2, 3) => {}
+            e => panic!("unexpected: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn device_errors() {
+        let mut ic = IntCode::new(vec![3, 0, 99], VecIO::default(), NullIO);
+        let err = ic.run_till_end().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InputExhausted));
+
+        struct Broken;
+        impl crate::intcode::intcode_io::Output for Broken {
+            fn output(&mut self, _: Int) -> Result<()> {
+                Err(anyhow::anyhow!("unplugged"))
+            }
+        }
+        let mut ic = IntCode::new(vec![109, 3, 104, 7, 99], NullIO, Broken);
+        let err = ic.run_till_end().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "io device failed: unplugged (pc=2 word=104 rb=3)"
+        );
+        assert_eq!(
+            format!("{:#}", anyhow::Error::from(err)),
+            "io device failed: unplugged (pc=2 word=104 rb=3)"
+        );
+    }
+
+    #[test]
+    fn paged_memory_survives_huge_addresses() {
+        // store the input far away, read it back relative to a huge base
+        let far: Int = 1 << 45;
+        let code = vec![3, far, 109, far, 204, 0, 21101, 2, 3, 1, 204, 1, 99];
+        let mut ic = IntCode::resumable(code).with_memory::<PagedMemory>();
+        ic.provide_input(42);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(42));
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(5));
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+        assert_eq!(ic.inner.resident_pages(), 2);
+        assert_eq!(ic.inner.len(), far as usize + 2);
+    }
+
+    #[test]
+    fn paged_memory_matches_dense() {
+        let code = crate::util::parse_intcode(include_str!("../../input/day9")).unwrap();
+        for input in 1..=2 {
+            let mut dense = IntCode::new(code.clone(), VecIO::input(vec![input]), VecIO::default());
+            let mut paged = IntCode::new(code.clone(), VecIO::input(vec![input]), VecIO::default())
+                .with_memory::<PagedMemory>();
+            dense.run_till_end().unwrap();
+            paged.run_till_end().unwrap();
+            let (dense_mem, dense_io) = dense.emit();
+            let (paged_mem, paged_io) = paged.emit();
+            assert_eq!(dense_mem, paged_mem);
+            assert_eq!(dense_io.output, paged_io.output);
+        }
+    }
+
+    #[test]
+    fn forks_share_pages_until_written() {
+        // counter: out [x]; add [x], #1, [x]; in [y]; jt #1, #0
+        let code = vec![4, 11, 1001, 11, 1, 11, 3, 12, 1105, 1, 0, 0, 0];
+        let mut ic = IntCode::resumable(code).with_memory::<PagedMemory>();
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(0));
+        assert_eq!(ic.run_until_event().unwrap(), Event::NeedsInput);
+
+        let mut fork = ic.fork();
+        assert_eq!(fork.inner.shared_pages(), 1);
+        fork.provide_input(7);
+        assert_eq!(fork.run_until_event().unwrap(), Event::Output(1));
+        assert_eq!(fork.inner.shared_pages(), 0);
+        assert_eq!(fork.peek(12), 7);
+        assert_eq!(ic.peek(12), 0);
+
+        ic.provide_input(9);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(1));
+        assert_eq!(ic.peek(12), 9);
+        assert_eq!(fork.steps(), ic.steps());
+    }
+
+    #[test]
+    fn arithmetic_overflow() {
+        let big = Int::MAX - 1;
+        let code = vec![1101, big, 2, 9, 1002, 9, 2, 9, 99, 0];
+        let mut ic = IntCode::resumable(code.clone());
+        let err = ic.run_until_event().unwrap_err();
+        assert!(matches!(
+            err.kind,
+            ErrorKind::Overflow {
+                op: "add",
+                lhs,
+                rhs: 2
+            } if lhs == big
+        ));
+        assert_eq!(err.pc, 0);
+        assert_eq!(
+            err.to_string(),
+            format!("arithmetic overflow: add {}, 2 (pc=0 word=1101 rb=0)", big)
+        );
+
+        let mut ic = IntCode::resumable(code.clone());
+        ic.set_arithmetic(Arithmetic::Wrapping);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+        assert_eq!(ic.peek(9), big.wrapping_add(2).wrapping_mul(2));
+
+        let mut ic = IntCode::resumable(code);
+        ic.set_arithmetic(Arithmetic::Saturating);
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+        assert_eq!(ic.peek(9), Int::MAX);
+
+        let mut ic = IntCode::resumable(vec![1102, big, -2, 5, 99, 0]);
+        ic.set_arithmetic(Arithmetic::Saturating);
+        ic.run_until_event().unwrap();
+        assert_eq!(ic.peek(5), Int::MIN);
+    }
+
+    #[test]
+    fn relative_base_overflow() {
+        // whatever the arithmetic policy, the base and addresses never wrap
+        let overflow = |code: Vec<Int>| {
+            let mut ic = IntCode::resumable(code);
+            ic.set_arithmetic(Arithmetic::Wrapping);
+            ic.run_until_event().unwrap_err().to_string()
+        };
+        assert_eq!(
+            overflow(vec![109, Int::MAX, 204, 1, 99]),
+            format!(
+                "arithmetic overflow: out {0}, 1 (pc=2 word=204 rb={0})",
+                Int::MAX
+            )
+        );
+        assert_eq!(
+            overflow(vec![109, Int::MAX, 109, 1, 99]),
+            format!(
+                "arithmetic overflow: arb {0}, 1 (pc=2 word=109 rb={0})",
+                Int::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn wider_words() {
+        let big = Int::MAX - 1;
+        let code = vec![1101, big, 2, 9, 1002, 9, 2, 9, 99, 0];
+        let wide = code.iter().map(|&x| i128::from(x)).collect();
+        let mut ic = IntCode::<(), DenseMemory<i128>>::with_device(wide, ());
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+        assert_eq!(ic.peek(9), (i128::from(big) + 2) * 2);
+
+        let huge = code.into_iter().map(BigInt::from).collect();
+        let mut ic = IntCode::<(), PagedMemory<BigInt>>::with_device(huge, ());
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+        assert_eq!(ic.peek(9), (BigInt::from(big) + 2) * 2);
+
+        let past = BigInt::from(1) << 70;
+        let code = vec![
+            BigInt::from(1001),
+            past.clone(),
+            BigInt::from(0),
+            BigInt::from(0),
+        ];
+        let mut ic = IntCode::<(), PagedMemory<BigInt>>::with_device(code, ());
+        let err = ic.run_until_event().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::AddressOutOfRange(addr) if addr == past));
+    }
+
+    #[test]
+    fn day9_at_every_width() {
+        fn boost<M: Memory>(input: &str) -> Vec<M::Word> {
+            let code = crate::util::parse_intcode(input).unwrap();
+            let code = code.into_iter().map(M::Word::from).collect();
+            let mut ic = IntCode::<(), M>::with_device(code, ());
+            ic.provide_input(M::Word::from(1));
+            let mut output = Vec::new();
+            while let Event::Output(x) = ic.run_until_event().unwrap() {
+                output.push(x);
+            }
+            output
+        }
+        let input = include_str!("../../input/day9");
+        let expected = boost::<DenseMemory>(input);
+        let wide = boost::<DenseMemory<i128>>(input);
+        let huge = boost::<PagedMemory<BigInt>>(input);
+        assert_eq!(
+            wide,
+            expected.iter().map(|&x| i128::from(x)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            huge,
+            expected.into_iter().map(BigInt::from).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn budget_and_step_count() {
+        let mut ic = IntCode::new(vec![1, 0, 0, 0, 99], NullIO, NullIO);
+        assert_eq!(ic.run_with_budget(10).unwrap(), RunOutcome::Halted);
+        assert_eq!(ic.steps(), 2);
+
+        let mut ic = IntCode::new(vec![1105, 1, 0], NullIO, NullIO);
+        assert_eq!(
+            ic.run_with_budget(100).unwrap(),
+            RunOutcome::BudgetExhausted
+        );
+        assert_eq!(ic.steps(), 100);
+        assert_eq!(ic.run_with_budget(50).unwrap(), RunOutcome::BudgetExhausted);
+        assert_eq!(ic.steps(), 150);
+    }
+
+    #[test]
+    fn loop_detection() {
+        let mut ic = IntCode::new(vec![1105, 1, 0], NullIO, NullIO);
+        ic.detect_loops(true);
+        assert_eq!(ic.run_with_budget(100).unwrap(), RunOutcome::LoopDetected);
+        assert_eq!(ic.steps(), 2);
+
+        let mut ic = IntCode::new(vec![1105, 1, 0], NullIO, NullIO);
+        ic.detect_loops(true);
+        let err = ic.run_till_end().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InfiniteLoop));
+
+        // memory keeps changing, so this never repeats
+        let counter = assemble("loop: add [n], #1, [n]\n jt #1, #loop\n n: .data 0").unwrap();
+        let mut ic = IntCode::new(counter, NullIO, NullIO);
+        ic.detect_loops(true);
+        assert_eq!(
+            ic.run_with_budget(1000).unwrap(),
+            RunOutcome::BudgetExhausted
+        );
+
+        // the same state between inputs is not a loop
+        let reader = assemble("loop: in [x]\n jt #1, #loop\n x: .data 0").unwrap();
+        let mut ic = IntCode::new(reader, VecIO::input(vec![0; 10]), NullIO);
+        ic.detect_loops(true);
+        let err = ic.run_with_budget(1000).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InputExhausted));
+        assert_eq!(ic.steps(), 20);
+    }
+
+    #[test]
+    fn self_modifying_code_invalidates_cache() {
+        // patch an operand, then the opcode, of an instruction already run
+        let code = assemble(
+            "
+            start:  out #5
+                    jt [done], #end
+                    add #7, #0, [start+1]
+                    add #1, #0, [done]
+                    jt #1, #start
+            end:    add #99, #0, [start]
+                    jt #1, #start
+            done:   .data 0
+            ",
+        )
+        .unwrap();
+        let (_, output) = run_intcode(code.clone(), vec![]).unwrap();
+        assert_eq!(output, vec![5, 7]);
+
+        let mut ic = IntCode::resumable(code);
+        ic.predecode();
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(5));
+        assert_eq!(ic.run_until_event().unwrap(), Event::Output(7));
+        assert_eq!(ic.run_until_event().unwrap(), Event::Halted);
+    }
 }
@@ -1,7 +1,7 @@
+use super::error::ErrorKind;
 use super::Int;
-use anyhow::{anyhow as ah, Result};
 
-const MAX_ARITY: usize = 3;
+pub(crate) const MAX_ARITY: usize = 3;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Instruction {
@@ -33,7 +33,7 @@ impl Instruction {
         }
     }
 
-    pub fn try_from_int(instr: Int) -> Result<Instruction> {
+    pub fn try_from_int(instr: Int) -> Result<Instruction, ErrorKind> {
         Ok(match instr {
             1 => Instruction::Add,
             2 => Instruction::Mul,
@@ -45,7 +45,53 @@ impl Instruction {
             8 => Instruction::EqualTo,
             9 => Instruction::SetBase,
             99 => Instruction::Halt,
-            n => return Err(ah!("unknown opcode {:02}", n)),
+            n => return Err(ErrorKind::InvalidOpcode(n)),
+        })
+    }
+
+    pub fn to_int(self) -> Int {
+        match self {
+            Instruction::Add => 1,
+            Instruction::Mul => 2,
+            Instruction::Input => 3,
+            Instruction::Output => 4,
+            Instruction::JumpTrue => 5,
+            Instruction::JumpFalse => 6,
+            Instruction::LessThan => 7,
+            Instruction::EqualTo => 8,
+            Instruction::SetBase => 9,
+            Instruction::Halt => 99,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Instruction::Add => "add",
+            Instruction::Mul => "mul",
+            Instruction::Input => "in",
+            Instruction::Output => "out",
+            Instruction::JumpTrue => "jt",
+            Instruction::JumpFalse => "jf",
+            Instruction::LessThan => "lt",
+            Instruction::EqualTo => "eq",
+            Instruction::SetBase => "arb",
+            Instruction::Halt => "hlt",
+        }
+    }
+
+    pub fn from_mnemonic(m: &str) -> Option<Instruction> {
+        Some(match m {
+            "add" => Instruction::Add,
+            "mul" => Instruction::Mul,
+            "in" => Instruction::Input,
+            "out" => Instruction::Output,
+            "jt" => Instruction::JumpTrue,
+            "jf" => Instruction::JumpFalse,
+            "lt" => Instruction::LessThan,
+            "eq" => Instruction::EqualTo,
+            "arb" => Instruction::SetBase,
+            "hlt" => Instruction::Halt,
+            _ => return None,
         })
     }
 }
@@ -63,14 +109,22 @@ pub enum ParameterMode {
 }
 
 impl ParameterMode {
-    pub fn try_from_int(m: i64) -> Result<ParameterMode> {
+    pub fn try_from_int(m: i64) -> Result<ParameterMode, ErrorKind> {
         Ok(match m {
             0 => ParameterMode::Position,
             1 => ParameterMode::Immediate,
             2 => ParameterMode::Relative,
-            n => return Err(ah!("unknown parameter mode: {}", n)),
+            n => return Err(ErrorKind::InvalidParameterMode(n)),
         })
     }
+
+    pub fn to_int(self) -> Int {
+        match self {
+            ParameterMode::Position => 0,
+            ParameterMode::Immediate => 1,
+            ParameterMode::Relative => 2,
+        }
+    }
 }
 
 impl Default for ParameterMode {
@@ -79,7 +133,7 @@ impl Default for ParameterMode {
     }
 }
 
-pub fn parse_instruction(instr: Int) -> Result<(Instruction, ParameterModes)> {
+pub fn parse_instruction(instr: Int) -> Result<(Instruction, ParameterModes), ErrorKind> {
     let opcode = Instruction::try_from_int(instr % 100)?;
 
     let mut modes = ParameterModes::default();
@@ -91,6 +145,16 @@ pub fn parse_instruction(instr: Int) -> Result<(Instruction, ParameterModes)> {
     Ok((opcode, modes))
 }
 
+pub fn encode_instruction(instr: Instruction, modes: ParameterModes) -> Int {
+    let mut word = instr.to_int();
+    let mut place = 100;
+    for mode in modes.inner.iter().take(instr.arity()) {
+        word += mode.to_int() * place;
+        place *= 10;
+    }
+    word
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -144,6 +208,13 @@ mod test {
         )
     }
     #[test]
+    fn encode_roundtrip() {
+        for word in &[1, 1002, 10001, 99, 21101, 204, 1105] {
+            let (instr, modes) = parse_instruction(*word).unwrap();
+            assert_eq!(encode_instruction(instr, modes), *word);
+        }
+    }
+    #[test]
     fn parse_halt() {
         assert_eq!(
             parse_instruction(99).unwrap(),
@@ -0,0 +1,195 @@
+use super::image::Image;
+use super::Int;
+use crate::util::parse_str;
+use anyhow::{anyhow as ah, Context, Result};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Where a patch writes: an address, or a label from the image's symbol
+/// table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    Addr(usize),
+    Symbol(String),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Addr(addr) => write!(f, "{}", addr),
+            Target::Symbol(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// One word to change in a program before it runs, such as the quarters
+/// inserted for day 13.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Patch {
+    pub name: Option<String>,
+    pub target: Target,
+    pub value: Int,
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "{}: ", name)?;
+        }
+        write!(f, "{}={}", self.target, self.value)
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl FromStr for Patch {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Patch> {
+        let mut sides = s.splitn(2, '=');
+        let lhs = sides.next().unwrap_or("");
+        let value = sides
+            .next()
+            .ok_or_else(|| ah!("expected '[name:] address=value', found '{}'", s))?;
+        let (name, target) = match lhs.find(':') {
+            Some(idx) => (Some(lhs[..idx].trim()), lhs[idx + 1..].trim()),
+            None => (None, lhs.trim()),
+        };
+        if let Some(name) = name {
+            if name.is_empty() {
+                return Err(ah!("empty patch name"));
+            }
+        }
+        let target = if is_identifier(target) {
+            Target::Symbol(target.to_string())
+        } else {
+            Target::Addr(parse_str(target).with_context(|| format!("bad address '{}'", target))?)
+        };
+        Ok(Patch {
+            name: name.map(|n| n.to_string()),
+            target,
+            value: parse_str(value.trim())?,
+        })
+    }
+}
+
+/// The patches from a patch file. The text form has one `address=value`
+/// line per patch, optionally named as `name: address=value`; the address
+/// can be a label from the image's symbol table. Blank lines and `#`
+/// comments are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Patches {
+    pub patches: Vec<Patch>,
+}
+
+impl Patches {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Patches> {
+        crate::util::read_to_string(&path)?
+            .parse()
+            .with_context(|| format!("invalid patch file {}", path.as_ref().display()))
+    }
+
+    /// Add the patches to `image`, to be applied when it is loaded.
+    pub fn apply(&self, image: &mut Image) -> Result<()> {
+        for patch in &self.patches {
+            let addr = match &patch.target {
+                Target::Addr(addr) => *addr,
+                Target::Symbol(name) => image
+                    .symbols
+                    .iter()
+                    .find(|(s, _)| s == name)
+                    .map(|(_, addr)| *addr)
+                    .ok_or_else(|| ah!("patch '{}': no symbol '{}' in the image", patch, name))?,
+            };
+            image.patches.push((addr, patch.value));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Patches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for patch in &self.patches {
+            writeln!(f, "{}", patch)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Patches {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Patches> {
+        let patches = s
+            .lines()
+            .enumerate()
+            .map(|(idx, l)| (idx, l.split('#').next().unwrap_or("").trim()))
+            .filter(|(_, l)| !l.is_empty())
+            .map(|(idx, l)| l.parse().with_context(|| format!("line {}", idx + 1)))
+            .collect::<Result<Vec<Patch>>>()?;
+        Ok(Patches { patches })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm::assemble_with_symbols;
+    use crate::intcode::run_intcode;
+
+    #[test]
+    fn day2_as_patches() {
+        let patches = "
+            # the 1202 program alarm
+            noun: 1=12
+            verb: 2 = 2
+        "
+        .parse::<Patches>()
+        .unwrap();
+        assert_eq!(patches.to_string(), "noun: 1=12\nverb: 2=2\n");
+        let mut image = Image::parse(include_str!("../../input/day2").as_bytes()).unwrap();
+        patches.apply(&mut image).unwrap();
+        let (mem, _) = run_intcode(image.program(), vec![]).unwrap();
+        assert_eq!(mem[0], 3_101_878);
+    }
+
+    #[test]
+    fn symbols_as_targets() {
+        let (words, symbols) = assemble_with_symbols("out [x]\nhlt\nx: .data 1").unwrap();
+        let mut image = Image {
+            words,
+            symbols,
+            patches: Vec::new(),
+        };
+        let patches = "x=7".parse::<Patches>().unwrap();
+        assert_eq!(patches.patches[0].target, Target::Symbol("x".to_string()));
+        patches.apply(&mut image).unwrap();
+        let (_, out) = run_intcode(image.program(), vec![]).unwrap();
+        assert_eq!(out, vec![7]);
+
+        let err = "y=1".parse::<Patches>().unwrap().apply(&mut image);
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "patch 'y=1': no symbol 'y' in the image"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_lines() {
+        let err = |s: &str| format!("{:#}", s.parse::<Patches>().unwrap_err());
+        assert_eq!(
+            err("0=1\n5"),
+            "line 2: expected '[name:] address=value', found '5'"
+        );
+        assert_eq!(err(": 3=1"), "line 1: empty patch name");
+        assert_eq!(
+            err("-3=1"),
+            "line 1: bad address '-3': invalid digit found in string"
+        );
+    }
+}
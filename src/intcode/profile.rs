@@ -0,0 +1,283 @@
+use super::disasm::{Line, Statement};
+use super::opcodes::Instruction;
+use super::{Accesses, Int, Word};
+use lazy_static::lazy_static;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+const HOT_ADDRESSES: usize = 10;
+/// Zeros between two stretches of memory which end the profiled program.
+const MAX_GAP: usize = 1 << 16;
+
+lazy_static! {
+    static ref GLOBAL: Mutex<Option<Profiler>> = Mutex::new(None);
+}
+
+/// Execution counts gathered from one or more machines running the same
+/// program.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    program: Vec<Int>,
+    steps: u64,
+    hits: HashMap<usize, u64>,
+    by_instruction: BTreeMap<&'static str, u64>,
+    reads: HashSet<usize>,
+    writes: HashSet<usize>,
+}
+
+impl Profile {
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// How many times the instruction at `pc` was executed.
+    pub fn hits(&self, pc: usize) -> u64 {
+        self.hits.get(&pc).cloned().unwrap_or(0)
+    }
+
+    /// The most executed addresses, busiest first.
+    pub fn hot_addresses(&self, count: usize) -> Vec<(usize, u64)> {
+        let mut hot = self
+            .hits
+            .iter()
+            .map(|(pc, n)| (*pc, *n))
+            .collect::<Vec<_>>();
+        hot.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        hot.truncate(count);
+        hot
+    }
+
+    /// Program words which were part of an executed instruction, including
+    /// its operands.
+    pub fn executed_words(&self) -> HashSet<usize> {
+        self.hits
+            .keys()
+            .flat_map(|pc| *pc..*pc + self.line_at(*pc).stmt.size())
+            .filter(|addr| *addr < self.program.len())
+            .collect()
+    }
+
+    /// Percentage of the loaded program executed as code.
+    pub fn coverage(&self) -> f64 {
+        if self.program.is_empty() {
+            return 0.0;
+        }
+        100.0 * self.executed_words().len() as f64 / self.program.len() as f64
+    }
+
+    /// The loaded program, disassembled along the paths actually executed.
+    ///
+    /// Words that were never executed are shown as data unless a linear
+    /// sweep decodes them into an instruction that doesn't overlap executed
+    /// code.
+    pub fn listing(&self) -> Vec<Line> {
+        let mut lines = Vec::new();
+        let mut addr = 0;
+        while addr < self.program.len() {
+            let mut line = self.line_at(addr);
+            let overlaps = (addr + 1..addr + line.stmt.size()).any(|a| self.hits.contains_key(&a));
+            if !self.hits.contains_key(&addr) && overlaps {
+                line = Line {
+                    addr,
+                    words: vec![self.program[addr]],
+                    stmt: Statement::Data(self.program[addr]),
+                };
+            }
+            addr += line.stmt.size();
+            lines.push(line);
+        }
+        lines
+    }
+
+    fn line_at(&self, pc: usize) -> Line {
+        if pc < self.program.len() {
+            Line::decode(&self.program, pc)
+        } else {
+            // code written past the end of the loaded program
+            Line::from_words(pc, &[])
+        }
+    }
+
+    fn annotation(&self, line: &Line) -> String {
+        let span = line.addr..line.addr + line.words.len();
+        let read = span.clone().any(|a| self.reads.contains(&a));
+        let written = span.clone().any(|a| self.writes.contains(&a));
+        let count = match self.hits.get(&line.addr) {
+            Some(n) => n.to_string(),
+            None => String::from("-"),
+        };
+        format!(
+            "{:>10} {}{}",
+            count,
+            if read { 'r' } else { ' ' },
+            if written { 'w' } else { ' ' }
+        )
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let executed = self.executed_words();
+        let data_only = self
+            .reads
+            .union(&self.writes)
+            .filter(|a| !executed.contains(a))
+            .count();
+        writeln!(f, "executed {} instructions", self.steps)?;
+        writeln!(
+            f,
+            "coverage: {}/{} words executed ({:.1}%), {} addresses used only as data",
+            executed.len(),
+            self.program.len(),
+            self.coverage(),
+            data_only
+        )?;
+        writeln!(f, "\nhot addresses:")?;
+        for (pc, n) in self.hot_addresses(HOT_ADDRESSES) {
+            writeln!(f, "{:>10}  {}", n, self.line_at(pc))?;
+        }
+        writeln!(f, "\ninstructions:")?;
+        for (mnemonic, n) in &self.by_instruction {
+            writeln!(f, "{:>10}  {}", n, mnemonic)?;
+        }
+        writeln!(f, "\nlisting:")?;
+        for line in self.listing() {
+            writeln!(f, "{}  {}", self.annotation(&line), line)?;
+        }
+        Ok(())
+    }
+}
+
+/// A handle machines record into; clones share the same `Profile`, so one
+/// profiler can follow every machine a challenge starts, across threads.
+#[derive(Debug, Clone, Default)]
+pub struct Profiler(Arc<Mutex<Profile>>);
+
+impl Profiler {
+    pub fn new() -> Profiler {
+        Profiler::default()
+    }
+
+    pub fn snapshot(&self) -> Profile {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Remember the program being profiled. Only the first one is kept, and
+    /// only if all of its words fit in an `Int`.
+    pub(crate) fn load<W: Word>(&self, program: &[W]) {
+        let mut profile = self.0.lock().unwrap();
+        if profile.program.is_empty() {
+            if let Some(program) = program.iter().map(Word::to_int).collect() {
+                profile.program = program;
+            }
+        }
+    }
+
+    /// Like `load`, for memory given as `Memory::regions`. The program runs
+    /// from address 0 to the first gap of more than `MAX_GAP` zeros, so a
+    /// write far away doesn't make it huge.
+    pub(crate) fn load_regions<W: Word>(&self, regions: Vec<(usize, &[W])>) {
+        let mut program = Vec::new();
+        for (start, words) in regions {
+            if start > program.len() + MAX_GAP {
+                break;
+            }
+            program.resize(start, W::zero());
+            program.extend_from_slice(words);
+        }
+        self.load(&program);
+    }
+
+    pub(crate) fn record(&self, pc: usize, instr: Instruction, accesses: Option<Accesses>) {
+        let mut profile = self.0.lock().unwrap();
+        profile.steps += 1;
+        *profile.hits.entry(pc).or_insert(0) += 1;
+        *profile.by_instruction.entry(instr.mnemonic()).or_insert(0) += 1;
+        if let Some(acc) = accesses {
+            profile.reads.extend(acc.reads);
+            profile.writes.extend(acc.write);
+        }
+    }
+}
+
+/// Attach a shared profiler to every machine created from now on.
+pub fn profile_all() -> Profiler {
+    let profiler = Profiler::new();
+    *GLOBAL.lock().unwrap() = Some(profiler.clone());
+    profiler
+}
+
+pub(crate) fn global() -> Option<Profiler> {
+    GLOBAL.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::intcode::asm::assemble;
+    use crate::intcode::intcode_io::{NullIO, VecIO};
+    use crate::intcode::intcode_mem::PagedMemory;
+    use crate::intcode::IntCode;
+
+    const COUNTDOWN: &str = "
+                in [counter]
+        loop:   out [counter]
+                add [counter], #-1, [counter]
+                jt [counter], #loop
+                hlt
+        unused: add #1, #1, [counter]
+        counter: .data 0
+    ";
+
+    #[test]
+    fn counts_and_coverage() {
+        let code = assemble(COUNTDOWN).unwrap();
+        let mut ic = IntCode::new(code, VecIO::input(vec![3]), VecIO::default());
+        let profiler = ic.attach_profiler();
+        ic.run_till_end().unwrap();
+        let profile = profiler.snapshot();
+
+        assert_eq!(profile.steps(), 1 + 3 * 3 + 1);
+        assert_eq!(profile.hits(2), 3);
+        assert_eq!(profile.hits(12), 0);
+        assert_eq!(profile.hot_addresses(1), vec![(2, 3)]);
+        // 12 of 17 words: everything but `unused` and `counter`
+        assert!((profile.coverage() - 100.0 * 12.0 / 17.0).abs() < 1e-9);
+
+        let report = profile.to_string();
+        assert!(report.contains("coverage: 12/17 words executed"));
+        assert!(report.contains("1 addresses used only as data"));
+        assert!(report.contains("         3  out"));
+        let listing = report.split("listing:\n").nth(1).unwrap();
+        assert_eq!(
+            listing.lines().last().unwrap(),
+            "         - rw     16: 0                    .data 0"
+        );
+    }
+
+    #[test]
+    fn shared_between_machines() {
+        let code = assemble("add #1, #2, [0]\nhlt").unwrap();
+        let mut a = IntCode::new(code.clone(), NullIO, NullIO);
+        let profiler = a.attach_profiler();
+        let mut b = IntCode::new(code, NullIO, NullIO);
+        b.set_profiler(profiler.clone());
+        a.run_till_end().unwrap();
+        b.run_till_end().unwrap();
+        assert_eq!(profiler.snapshot().hits(0), 2);
+    }
+
+    #[test]
+    fn sparse_memory_is_not_flattened() {
+        let code = assemble(COUNTDOWN).unwrap();
+        let mut ic = IntCode::new(code, VecIO::input(vec![1]), VecIO::default())
+            .with_memory::<PagedMemory>();
+        ic.inner[1 << 40] = 1;
+        let profiler = ic.attach_profiler();
+        ic.run_till_end().unwrap();
+        let profile = profiler.snapshot();
+        // the program's page, without the one far away
+        assert_eq!(profile.program.len(), 1024);
+        assert_eq!(profile.steps(), 5);
+    }
+}
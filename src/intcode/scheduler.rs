@@ -0,0 +1,302 @@
+use super::{DenseMemory, Event, Int, IntCode, Memory};
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+
+/// Steps each machine gets before the next one runs.
+const DEFAULT_SLICE: u64 = 1000;
+
+/// Carries values between the machines of a `Scheduler`.
+pub trait Router {
+    /// The next value waiting for machine `id`, if there is one.
+    fn recv(&mut self, id: usize) -> Option<Int>;
+    /// Machine `id` output `value`.
+    fn send(&mut self, id: usize, value: Int) -> Result<()>;
+}
+
+/// One queue per machine, with every output delivered to the next machine
+/// along, wrapping around at the end: a ring of amplifiers.
+#[derive(Debug, Clone, Default)]
+pub struct Ring {
+    queues: Vec<VecDeque<Int>>,
+    last: Vec<Int>,
+}
+
+impl Ring {
+    pub fn new(size: usize) -> Ring {
+        Ring {
+            queues: vec![VecDeque::new(); size],
+            last: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, id: usize, value: Int) {
+        self.queues[id].push_back(value);
+    }
+
+    /// Everything the last machine in the ring has output.
+    pub fn last_outputs(&self) -> &[Int] {
+        &self.last
+    }
+}
+
+impl Router for Ring {
+    fn recv(&mut self, id: usize) -> Option<Int> {
+        self.queues[id].pop_front()
+    }
+
+    fn send(&mut self, id: usize, value: Int) -> Result<()> {
+        if id + 1 == self.queues.len() {
+            self.last.push(value);
+        }
+        let next = (id + 1) % self.queues.len();
+        self.queues[next].push_back(value);
+        Ok(())
+    }
+}
+
+/// Where a scheduler stands after a round.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Running,
+    /// Every machine still running is waiting on input nobody has sent.
+    Idle,
+    Halted,
+}
+
+/// Runs many machines on one thread, a slice of steps each in turn, with
+/// all of their IO going through a `Router`.
+///
+/// A machine counts as waiting once it asks for input and the router has
+/// none, and stops waiting when it gets input or outputs something. The
+/// scheduler is idle when every running machine has waited since the last
+/// output anywhere, so no input can be in flight.
+///
+/// By default a waiting machine is left blocked until the router has input
+/// for it. With `poll_with`, it is given that value instead and carries on,
+/// for programs which poll for input; its turn ends when it polls a second
+/// time without having output anything, rather than spinning through the
+/// rest of its slice.
+#[derive(Debug)]
+pub struct Scheduler<R, M: Memory = DenseMemory> {
+    machines: Vec<IntCode<(), M>>,
+    waiting: Vec<Option<u64>>,
+    outputs: u64,
+    router: R,
+    poll: Option<Int>,
+    slice: u64,
+}
+
+impl<R: Router, M: Memory<Word = Int>> Scheduler<R, M> {
+    pub fn new(router: R) -> Scheduler<R, M> {
+        Scheduler {
+            machines: Vec::new(),
+            waiting: Vec::new(),
+            outputs: 0,
+            router,
+            poll: None,
+            slice: DEFAULT_SLICE,
+        }
+    }
+
+    /// Give `value` to machines which ask for input the router doesn't have.
+    pub fn poll_with(mut self, value: Int) -> Scheduler<R, M> {
+        self.poll = Some(value);
+        self
+    }
+
+    /// Let each machine run up to `steps` instructions per turn.
+    pub fn with_slice(mut self, steps: u64) -> Scheduler<R, M> {
+        self.slice = steps.max(1);
+        self
+    }
+
+    /// Add a machine, returning the id the router will see for it.
+    pub fn add(&mut self, machine: IntCode<(), M>) -> usize {
+        self.machines.push(machine);
+        self.waiting.push(None);
+        self.machines.len() - 1
+    }
+
+    pub fn machine(&self, id: usize) -> &IntCode<(), M> {
+        &self.machines[id]
+    }
+
+    pub fn router(&self) -> &R {
+        &self.router
+    }
+
+    pub fn router_mut(&mut self) -> &mut R {
+        &mut self.router
+    }
+
+    /// Give every machine still running one turn.
+    pub fn run_round(&mut self) -> Result<Status> {
+        for id in 0..self.machines.len() {
+            self.run_slice(id)
+                .with_context(|| format!("machine {}", id))?;
+        }
+        Ok(self.status())
+    }
+
+    /// Run rounds until every machine has halted or the scheduler is idle.
+    pub fn run_until_idle(&mut self) -> Result<Status> {
+        loop {
+            match self.run_round()? {
+                Status::Running => {}
+                status => return Ok(status),
+            }
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        let mut running = self
+            .machines
+            .iter()
+            .zip(&self.waiting)
+            .filter(|(m, _)| !m.halt);
+        match running.next() {
+            None => Status::Halted,
+            Some(first) => {
+                let idle = |(_, w): (&IntCode<(), M>, &Option<u64>)| *w == Some(self.outputs);
+                if idle(first) && running.all(idle) {
+                    Status::Idle
+                } else {
+                    Status::Running
+                }
+            }
+        }
+    }
+
+    fn run_slice(&mut self, id: usize) -> Result<()> {
+        let machine = &mut self.machines[id];
+        for _ in 0..self.slice {
+            match machine.step()? {
+                Some(Event::NeedsInput) => match self.router.recv(id) {
+                    Some(x) => {
+                        self.waiting[id] = None;
+                        machine.provide_input(x);
+                    }
+                    None => {
+                        let polled = self.waiting[id] == Some(self.outputs);
+                        self.waiting[id] = Some(self.outputs);
+                        match self.poll {
+                            Some(x) => machine.provide_input(x),
+                            None => break,
+                        }
+                        // it has already polled once for nothing, so it
+                        // would only spin until someone else sends
+                        if polled {
+                            break;
+                        }
+                    }
+                },
+                Some(Event::Output(x)) => {
+                    self.outputs += 1;
+                    self.waiting[id] = None;
+                    self.router.send(id, x)?;
+                }
+                Some(Event::Halted) => break,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    /// Reads a value and outputs it plus one, forever.
+    const INCREMENT: &str = "
+        loop:   in [x]
+                add [x], #1, [x]
+                out [x]
+                jt #1, #loop
+        x:      .data 0
+    ";
+
+    /// Polls for input, passing on anything other than -1.
+    const FORWARD: &str = "
+        loop:   in [x]
+                eq [x], #-1, [t]
+                jt [t], #loop
+                out [x]
+                jt #1, #loop
+        x:      .data 0
+        t:      .data 0
+    ";
+
+    #[derive(Default)]
+    struct Outbox {
+        inbox: Vec<VecDeque<Int>>,
+        sent: Vec<(usize, Int)>,
+    }
+
+    impl Router for Outbox {
+        fn recv(&mut self, id: usize) -> Option<Int> {
+            self.inbox.get_mut(id).and_then(|q| q.pop_front())
+        }
+        fn send(&mut self, id: usize, value: Int) -> Result<()> {
+            self.sent.push((id, value));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn ring_runs_until_halted() {
+        let once = assemble("in [x]\nmul [x], #2, [x]\nout [x]\nhlt\nx: .data 0").unwrap();
+        let mut ring = Ring::new(3);
+        ring.push(0, 1);
+        let mut sched = Scheduler::new(ring);
+        for _ in 0..3 {
+            sched.add(IntCode::resumable(once.clone()));
+        }
+        assert_eq!(sched.run_until_idle().unwrap(), Status::Halted);
+        assert_eq!(sched.router().last_outputs(), &[8]);
+    }
+
+    #[test]
+    fn blocked_ring_is_idle() {
+        let code = assemble(INCREMENT).unwrap();
+        let mut sched = Scheduler::new(Ring::new(2));
+        sched.add(IntCode::resumable(code.clone()));
+        sched.add(IntCode::resumable(code));
+        assert_eq!(sched.run_until_idle().unwrap(), Status::Idle);
+
+        sched.router_mut().push(0, 1);
+        assert_eq!(sched.run_round().unwrap(), Status::Running);
+        assert_eq!(sched.router().last_outputs(), &[3]);
+        sched.run_round().unwrap();
+        assert_eq!(sched.router().last_outputs(), &[3, 5]);
+    }
+
+    #[test]
+    fn polling_machines_go_idle() {
+        let code = assemble(FORWARD).unwrap();
+        let outbox = Outbox {
+            inbox: vec![VecDeque::new(), vec![7].into()],
+            ..Outbox::default()
+        };
+        let mut sched = Scheduler::new(outbox).poll_with(-1).with_slice(3);
+        sched.add(IntCode::resumable(code.clone()));
+        sched.add(IntCode::resumable(code));
+        assert_eq!(sched.run_until_idle().unwrap(), Status::Idle);
+        assert_eq!(sched.router().sent, vec![(1, 7)]);
+        assert!(sched.machine(0).steps() > 3);
+        assert!(!sched.machine(1).halt);
+    }
+
+    #[test]
+    fn polling_ends_the_turn() {
+        let code = assemble(FORWARD).unwrap();
+        let mut sched = Scheduler::new(Outbox::default()).poll_with(-1);
+        sched.add(IntCode::resumable(code));
+        assert_eq!(sched.run_round().unwrap(), Status::Idle);
+        // in, eq and jt, then it stops at the next in
+        assert_eq!(sched.machine(0).steps(), 3);
+        sched.run_round().unwrap();
+        assert_eq!(sched.machine(0).steps(), 6);
+    }
+}
@@ -0,0 +1,363 @@
+use super::icache::InstructionCache;
+use super::{Arithmetic, Int, IntCode, Memory};
+use anyhow::{anyhow as ah, Context, Result};
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+const HEADER: &str = "intcode-snapshot";
+const VERSION: u32 = 2;
+
+/// Runs of zeros at least this long split a memory line.
+const ZERO_GAP: usize = 16;
+const LINE_WORDS: usize = 64;
+
+/// Everything needed to resume a machine later, except its IO device.
+///
+/// The text form is line based: a version header, one `key value` line per
+/// register, then `mem <addr> <words>` lines for the nonzero parts of memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pc: usize,
+    relative_base: Int,
+    halt: bool,
+    steps: u64,
+    arithmetic: Arithmetic,
+    pending_input: Vec<Int>,
+    len: usize,
+    memory: Vec<(usize, Vec<Int>)>,
+}
+
+impl Snapshot {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(&path, self.to_string())
+            .with_context(|| format!("unable to write snapshot {}", path.as_ref().display()))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Snapshot> {
+        crate::util::read_to_string(&path)?
+            .parse()
+            .with_context(|| format!("invalid snapshot {}", path.as_ref().display()))
+    }
+}
+
+/// Split `words` (starting at `start`) into lines of nonzero runs.
+fn memory_lines(start: usize, words: &[Int], lines: &mut Vec<(usize, Vec<Int>)>) {
+    let mut idx = 0;
+    while idx < words.len() {
+        if words[idx] == 0 {
+            idx += 1;
+            continue;
+        }
+        let run_start = idx;
+        let mut zeros = 0;
+        while idx < words.len() && zeros < ZERO_GAP && idx - run_start < LINE_WORDS {
+            zeros = if words[idx] == 0 { zeros + 1 } else { 0 };
+            idx += 1;
+        }
+        let run_end = idx - zeros;
+        lines.push((start + run_start, words[run_start..run_end].to_vec()));
+    }
+}
+
+fn csv(words: &[Int]) -> String {
+    words
+        .iter()
+        .map(|w| w.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", HEADER, VERSION)?;
+        writeln!(f, "pc {}", self.pc)?;
+        writeln!(f, "relative_base {}", self.relative_base)?;
+        writeln!(f, "halt {}", self.halt)?;
+        writeln!(f, "steps {}", self.steps)?;
+        writeln!(f, "arithmetic {}", self.arithmetic)?;
+        writeln!(f, "input {}", csv(&self.pending_input))?;
+        writeln!(f, "len {}", self.len)?;
+        for (addr, words) in &self.memory {
+            writeln!(f, "mem {} {}", addr, csv(words))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct SnapshotBuilder {
+    pc: Option<usize>,
+    relative_base: Option<Int>,
+    halt: Option<bool>,
+    steps: Option<u64>,
+    arithmetic: Option<Arithmetic>,
+    pending_input: Vec<Int>,
+    len: Option<usize>,
+    memory: Vec<(usize, Vec<Int>)>,
+}
+
+impl SnapshotBuilder {
+    fn field(&mut self, key: &str, value: &str) -> Result<()> {
+        use crate::util::{parse_intcode, parse_str};
+        match key {
+            "pc" => self.pc = Some(parse_str(value)?),
+            "relative_base" => self.relative_base = Some(parse_str(value)?),
+            "halt" => self.halt = Some(parse_str(value)?),
+            "steps" => self.steps = Some(parse_str(value)?),
+            "arithmetic" => self.arithmetic = Some(value.parse()?),
+            "input" => self.pending_input = parse_intcode(value)?,
+            "len" => self.len = Some(parse_str(value)?),
+            "mem" => {
+                let mut parts = value.splitn(2, ' ');
+                let addr = parse_str::<usize>(parts.next().unwrap_or(""))?;
+                let words = parse_intcode(parts.next().unwrap_or(""))?;
+                self.memory.push((addr, words));
+            }
+            _ => return Err(ah!("unknown field '{}'", key)),
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Snapshot {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Snapshot> {
+        let mut lines = s.lines().enumerate();
+        let header = lines.next().map(|(_, l)| l.trim()).unwrap_or("");
+        let version = match header.strip_prefix(HEADER) {
+            Some(v) => crate::util::parse_str::<u32>(v.trim())
+                .with_context(|| format!("bad version in header '{}'", header))?,
+            None => return Err(ah!("not an intcode snapshot")),
+        };
+        if version != VERSION {
+            return Err(ah!(
+                "unsupported snapshot version {} (expected {})",
+                version,
+                VERSION
+            ));
+        }
+
+        let mut builder = SnapshotBuilder::default();
+        for (idx, line) in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.splitn(2, ' ');
+            let key = parts.next().unwrap_or("");
+            let value = parts.next().unwrap_or("").trim();
+            builder
+                .field(key, value)
+                .with_context(|| format!("line {}", idx + 1))?;
+        }
+
+        let missing = |name: &str| ah!("missing field '{}'", name);
+        let snapshot = Snapshot {
+            pc: builder.pc.ok_or_else(|| missing("pc"))?,
+            relative_base: builder
+                .relative_base
+                .ok_or_else(|| missing("relative_base"))?,
+            halt: builder.halt.ok_or_else(|| missing("halt"))?,
+            steps: builder.steps.ok_or_else(|| missing("steps"))?,
+            arithmetic: builder.arithmetic.ok_or_else(|| missing("arithmetic"))?,
+            pending_input: builder.pending_input,
+            len: builder.len.ok_or_else(|| missing("len"))?,
+            memory: builder.memory,
+        };
+        for (addr, words) in &snapshot.memory {
+            match addr.checked_add(words.len()) {
+                Some(end) if end <= snapshot.len => {}
+                _ => {
+                    return Err(ah!(
+                        "{} words of memory at {} are past len {}",
+                        words.len(),
+                        addr,
+                        snapshot.len
+                    ))
+                }
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+impl<IO, M: Memory<Word = Int>> IntCode<IO, M> {
+    /// Capture the machine's state. Profiling, loop detection and the undo
+    /// journal are not part of it, and neither are the IO device or mapped
+    /// devices.
+    pub fn snapshot(&self) -> Snapshot {
+        let mut memory = Vec::new();
+        for (start, words) in self.inner.regions() {
+            memory_lines(start, words, &mut memory);
+        }
+        Snapshot {
+            pc: self.pc,
+            relative_base: self.relative_base,
+            halt: self.halt,
+            steps: self.steps,
+            arithmetic: self.arithmetic,
+            pending_input: self.pending_input.iter().cloned().collect(),
+            len: self.inner.len(),
+            memory,
+        }
+    }
+
+    /// Rebuild a machine from `snapshot`, attached to a new IO device. Fails
+    /// if the memory is too big for `M` to hold.
+    pub fn restore(snapshot: &Snapshot, io_device: IO) -> Result<IntCode<IO, M>> {
+        if snapshot.len > M::MAX_LEN {
+            return Err(ah!(
+                "snapshot memory of {} words is too large (at most {})",
+                snapshot.len,
+                M::MAX_LEN
+            ));
+        }
+        let mut inner = M::from(Vec::new());
+        if snapshot.len > 0 {
+            inner[snapshot.len - 1] = 0;
+        }
+        for (addr, words) in &snapshot.memory {
+            for (offset, word) in words.iter().enumerate() {
+                inner[addr + offset] = *word;
+            }
+        }
+        Ok(IntCode {
+            icache: InstructionCache::new(snapshot.len),
+            inner,
+            pc: snapshot.pc,
+            relative_base: snapshot.relative_base,
+            halt: snapshot.halt,
+            pending_input: snapshot
+                .pending_input
+                .iter()
+                .cloned()
+                .collect::<VecDeque<_>>(),
+            io_device,
+            devices: None,
+            steps: snapshot.steps,
+            arithmetic: snapshot.arithmetic,
+            loops: None,
+            journal: None,
+            profiler: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::{DenseMemory, Event, PagedMemory};
+
+    fn run_to_end(ic: &mut IntCode<()>, mut input: Vec<Int>) -> Vec<Int> {
+        input.reverse();
+        let mut output = Vec::new();
+        loop {
+            match ic.run_until_event().unwrap() {
+                Event::Output(x) => output.push(x),
+                Event::NeedsInput => ic.provide_input(input.pop().unwrap()),
+                _ => break,
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn resume_from_text() {
+        let code = crate::util::parse_intcode(include_str!("../../input/day9")).unwrap();
+        let mut expected = IntCode::resumable(code.clone());
+        let expected_out = run_to_end(&mut expected, vec![2]);
+
+        let mut ic = IntCode::resumable(code);
+        ic.provide_input(2);
+        for _ in 0..100_000 {
+            ic.step().unwrap();
+        }
+        let text = ic.snapshot().to_string();
+        assert!(text.starts_with("intcode-snapshot 2\n"));
+        let snapshot = text.parse::<Snapshot>().unwrap();
+        assert_eq!(snapshot, ic.snapshot());
+
+        let mut resumed: IntCode<()> = IntCode::restore(&snapshot, ()).unwrap();
+        assert_eq!(run_to_end(&mut resumed, vec![]), expected_out);
+        assert_eq!(resumed.steps(), expected.steps());
+        assert_eq!(resumed.emit().0, expected.emit().0);
+    }
+
+    #[test]
+    fn pending_input_and_sparse_memory() {
+        let mut ic = IntCode::resumable(vec![3, 0, 99]).with_memory::<PagedMemory>();
+        ic.provide_input(7);
+        ic.provide_input(8);
+        ic.inner[1 << 40] = 5;
+        ic.set_arithmetic(Arithmetic::Saturating);
+        let snapshot = ic.snapshot();
+        let text = snapshot.to_string();
+        assert!(text.contains("input 7,8\n"));
+        assert!(text.contains(&format!("mem {} 5\n", 1u64 << 40)));
+
+        let restored: IntCode<(), PagedMemory> =
+            IntCode::restore(&text.parse().unwrap(), ()).unwrap();
+        assert_eq!(restored.peek(1 << 40), 5);
+        assert_eq!(restored.peek(2), 99);
+        assert_eq!(restored.snapshot(), snapshot);
+    }
+
+    #[test]
+    fn zero_runs_split_lines() {
+        let mut code = vec![1; 3];
+        code.extend(vec![0; 4]);
+        code.extend(vec![2; 2]);
+        code.extend(vec![0; ZERO_GAP]);
+        code.push(3);
+        code.extend(vec![0; 5]);
+        let ic = IntCode::resumable(code.clone());
+        let text = ic.snapshot().to_string();
+        let mem = text
+            .lines()
+            .filter(|l| l.starts_with("mem"))
+            .collect::<Vec<_>>();
+        assert_eq!(mem, vec!["mem 0 1,1,1,0,0,0,0,2,2", "mem 25 3"]);
+        let restored: IntCode<()> = IntCode::restore(&text.parse().unwrap(), ()).unwrap();
+        assert_eq!(restored.emit().0, code);
+    }
+
+    #[test]
+    fn rejects_bad_snapshots() {
+        let err = |s: &str| format!("{:#}", s.parse::<Snapshot>().unwrap_err());
+        assert_eq!(err("hello"), "not an intcode snapshot");
+        assert_eq!(
+            err("intcode-snapshot 1\n"),
+            "unsupported snapshot version 1 (expected 2)"
+        );
+        assert_eq!(
+            err("intcode-snapshot 2\npc 0\nflux 3\n"),
+            "line 3: unknown field 'flux'"
+        );
+        assert_eq!(
+            err("intcode-snapshot 2\npc 0\n"),
+            "missing field 'relative_base'"
+        );
+        assert_eq!(
+            err("intcode-snapshot 2\narithmetic fast\n"),
+            "line 2: unknown arithmetic 'fast'"
+        );
+
+        let fields = "intcode-snapshot 2\npc 0\nrelative_base 0\nhalt false\nsteps 0\n\
+                      arithmetic wrapping\ninput\n";
+        assert_eq!(
+            err(&format!("{}len 10\nmem 18446744073709551615 1\n", fields)),
+            "1 words of memory at 18446744073709551615 are past len 10"
+        );
+        let huge = format!("{}len 99999999999999\n", fields).parse().unwrap();
+        let err = IntCode::<(), DenseMemory>::restore(&huge, ()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "snapshot memory of 99999999999999 words is too large (at most 134217728)"
+        );
+        let restored = IntCode::<(), PagedMemory>::restore(&huge, ()).unwrap();
+        assert_eq!(restored.snapshot().arithmetic, Arithmetic::Wrapping);
+    }
+}
@@ -0,0 +1,514 @@
+use super::opcodes::{parse_instruction, Instruction, ParameterMode};
+use super::Int;
+use anyhow::{anyhow as ah, Context, Result};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// An unknown integer standing in for a memory cell or an input.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Symbol {
+    id: usize,
+    name: String,
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// A constant plus a sum of symbols, each times a coefficient.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Linear {
+    constant: Int,
+    terms: BTreeMap<Symbol, Int>,
+}
+
+impl Linear {
+    pub fn constant(&self) -> Int {
+        self.constant
+    }
+
+    /// The coefficient of `symbol`, 0 if it doesn't appear.
+    pub fn coefficient(&self, symbol: &Symbol) -> Int {
+        self.terms.get(symbol).cloned().unwrap_or(0)
+    }
+
+    fn as_constant(&self) -> Option<Int> {
+        if self.terms.is_empty() {
+            Some(self.constant)
+        } else {
+            None
+        }
+    }
+
+    fn add(&self, rhs: &Linear) -> Option<Linear> {
+        let mut sum = self.clone();
+        sum.constant = sum.constant.checked_add(rhs.constant)?;
+        for (symbol, k) in &rhs.terms {
+            let coeff = sum.terms.entry(symbol.clone()).or_insert(0);
+            *coeff = coeff.checked_add(*k)?;
+            if *coeff == 0 {
+                sum.terms.remove(symbol);
+            }
+        }
+        Some(sum)
+    }
+
+    fn scale(&self, by: Int) -> Option<Linear> {
+        if by == 0 {
+            return Some(Linear::default());
+        }
+        let mut terms = BTreeMap::new();
+        for (symbol, k) in &self.terms {
+            terms.insert(symbol.clone(), k.checked_mul(by)?);
+        }
+        Some(Linear {
+            constant: self.constant.checked_mul(by)?,
+            terms,
+        })
+    }
+
+    /// Values for the symbols in `bounds`, in that order, which make this
+    /// equal to `target`. The first solution in lexicographic order is
+    /// returned, or `None` if there isn't one or a symbol has no bounds.
+    ///
+    /// Every symbol but the last with a nonzero coefficient is enumerated,
+    /// and the last is solved for directly.
+    pub fn solve(&self, target: Int, bounds: &[(Symbol, RangeInclusive<Int>)]) -> Option<Vec<Int>> {
+        if self
+            .terms
+            .keys()
+            .any(|s| !bounds.iter().any(|(b, _)| b == s))
+        {
+            return None;
+        }
+        let mut values = bounds.iter().map(|(_, r)| *r.start()).collect::<Vec<_>>();
+        let free = (0..bounds.len())
+            .filter(|idx| self.coefficient(&bounds[*idx].0) != 0)
+            .collect::<Vec<_>>();
+        let rest = target.checked_sub(self.constant)?;
+        match free.split_last() {
+            None if rest == 0 => Some(values),
+            None => None,
+            Some((last, enumerated)) => {
+                if self.search(bounds, enumerated, *last, rest, &mut values) {
+                    Some(values)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn search(
+        &self,
+        bounds: &[(Symbol, RangeInclusive<Int>)],
+        enumerated: &[usize],
+        last: usize,
+        rest: Int,
+        values: &mut Vec<Int>,
+    ) -> bool {
+        match enumerated.split_first() {
+            None => {
+                let (symbol, range) = &bounds[last];
+                let k = self.coefficient(symbol);
+                if rest % k == 0 && range.contains(&(rest / k)) {
+                    values[last] = rest / k;
+                    true
+                } else {
+                    false
+                }
+            }
+            Some((idx, others)) => {
+                let (symbol, range) = &bounds[*idx];
+                let k = self.coefficient(symbol);
+                for value in range.clone() {
+                    let rest = match k.checked_mul(value).and_then(|x| rest.checked_sub(x)) {
+                        Some(rest) => rest,
+                        None => continue,
+                    };
+                    values[*idx] = value;
+                    if self.search(bounds, others, last, rest, values) {
+                        return true;
+                    }
+                }
+                false
+            }
+        }
+    }
+}
+
+impl fmt::Display for Linear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (symbol, k) in &self.terms {
+            let sep = match (first, *k < 0) {
+                (true, false) => "",
+                (true, true) => "-",
+                (false, false) => " + ",
+                (false, true) => " - ",
+            };
+            match k.abs() {
+                1 => write!(f, "{}{}", sep, symbol)?,
+                n => write!(f, "{}{}*{}", sep, n, symbol)?,
+            }
+            first = false;
+        }
+        match (first, self.constant) {
+            (true, c) => write!(f, "{}", c),
+            (false, 0) => Ok(()),
+            (false, c) if c < 0 => write!(f, " - {}", -c),
+            (false, c) => write!(f, " + {}", c),
+        }
+    }
+}
+
+/// What a symbolic machine knows about a word.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Linear(Linear),
+    /// Depends on the symbols in a way that isn't linear, such as the
+    /// product of two of them or a read from a symbolic address.
+    Opaque,
+}
+
+impl Value {
+    pub fn linear(&self) -> Option<&Linear> {
+        match self {
+            Value::Linear(l) => Some(l),
+            Value::Opaque => None,
+        }
+    }
+
+    fn as_constant(&self) -> Option<Int> {
+        self.linear().and_then(Linear::as_constant)
+    }
+}
+
+impl From<Int> for Value {
+    fn from(x: Int) -> Value {
+        Value::Linear(Linear {
+            constant: x,
+            terms: BTreeMap::new(),
+        })
+    }
+}
+
+impl From<Symbol> for Value {
+    fn from(symbol: Symbol) -> Value {
+        let mut terms = BTreeMap::new();
+        terms.insert(symbol, 1);
+        Value::Linear(Linear { constant: 0, terms })
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Linear(l) => write!(f, "{}", l),
+            Value::Opaque => write!(f, "?"),
+        }
+    }
+}
+
+/// Runs an Intcode program with some memory cells or inputs left unknown,
+/// tracking each word as a linear expression in the unknowns.
+///
+/// `add` and `mul` keep expressions linear where they can and give up with
+/// `Value::Opaque` where they can't. Everything which decides where the
+/// program goes next (opcodes, jump conditions and targets, the relative
+/// base and write addresses) must work out to a constant, otherwise the run
+/// fails, since the machine doesn't fork on branches.
+#[derive(Debug, Clone)]
+pub struct Symbolic {
+    memory: Vec<Value>,
+    /// Words written past the loaded program, which may be anywhere.
+    beyond: BTreeMap<usize, Value>,
+    pc: usize,
+    relative_base: Int,
+    halt: bool,
+    symbols: usize,
+    inputs: VecDeque<Value>,
+    outputs: Vec<Value>,
+}
+
+impl Symbolic {
+    pub fn new(intcode: &[Int]) -> Symbolic {
+        Symbolic {
+            memory: intcode.iter().map(|x| Value::from(*x)).collect(),
+            beyond: BTreeMap::new(),
+            pc: 0,
+            relative_base: 0,
+            halt: false,
+            symbols: 0,
+            inputs: VecDeque::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// A new unknown, named for display.
+    pub fn symbol(&mut self, name: &str) -> Symbol {
+        self.symbols += 1;
+        Symbol {
+            id: self.symbols,
+            name: name.to_string(),
+        }
+    }
+
+    pub fn set(&mut self, addr: usize, value: Value) {
+        match self.memory.get_mut(addr) {
+            Some(word) => *word = value,
+            None => {
+                self.beyond.insert(addr, value);
+            }
+        }
+    }
+
+    pub fn peek(&self, addr: usize) -> Value {
+        self.memory
+            .get(addr)
+            .or_else(|| self.beyond.get(&addr))
+            .cloned()
+            .unwrap_or_else(|| Value::from(0))
+    }
+
+    pub fn provide_input(&mut self, value: Value) {
+        self.inputs.push_back(value);
+    }
+
+    pub fn outputs(&self) -> &[Value] {
+        &self.outputs
+    }
+
+    /// Run until the program halts, failing if it takes more than
+    /// `max_steps` instructions.
+    pub fn run(&mut self, max_steps: u64) -> Result<()> {
+        for _ in 0..max_steps {
+            if self.halt {
+                return Ok(());
+            }
+            let pc = self.pc;
+            self.step()
+                .with_context(|| format!("at pc={} ({})", pc, self.peek(pc)))?;
+        }
+        if self.halt {
+            Ok(())
+        } else {
+            Err(ah!("still running after {} steps", max_steps))
+        }
+    }
+
+    fn constant(&self, value: &Value, what: &str) -> Result<Int> {
+        value
+            .as_constant()
+            .ok_or_else(|| ah!("{} depends on the unknowns: {}", what, value))
+    }
+
+    fn address(&self, mode: ParameterMode, nth: usize) -> Result<Option<usize>> {
+        let arg = self.peek(self.pc + 1 + nth);
+        let addr = match mode {
+            ParameterMode::Immediate => return Ok(Some(self.pc + 1 + nth)),
+            ParameterMode::Position => arg.as_constant(),
+            ParameterMode::Relative => match arg.as_constant() {
+                Some(x) => Some(x.checked_add(self.relative_base).ok_or_else(|| {
+                    ah!("relative address {}{:+} overflows", self.relative_base, x)
+                })?),
+                None => None,
+            },
+        };
+        match addr {
+            Some(addr) if addr >= 0 => Ok(Some(addr as usize)),
+            Some(addr) => Err(ah!("negative address {}", addr)),
+            None => Ok(None),
+        }
+    }
+
+    fn get(&self, mode: ParameterMode, nth: usize) -> Result<Value> {
+        Ok(match self.address(mode, nth)? {
+            Some(addr) => self.peek(addr),
+            None => Value::Opaque,
+        })
+    }
+
+    fn put(&mut self, mode: ParameterMode, nth: usize, value: Value) -> Result<()> {
+        if mode == ParameterMode::Immediate {
+            return Err(ah!("write to immediate operand"));
+        }
+        match self.address(mode, nth)? {
+            Some(addr) => {
+                self.set(addr, value);
+                Ok(())
+            }
+            None => Err(ah!(
+                "write address depends on the unknowns: {}",
+                self.peek(self.pc + 1 + nth)
+            )),
+        }
+    }
+
+    fn step(&mut self) -> Result<()> {
+        let word = self.constant(&self.peek(self.pc), "opcode")?;
+        let (instr, modes) = parse_instruction(word).map_err(|e| ah!("{}", e))?;
+        let modes = modes.inner;
+        let mut next = self.pc + 1 + instr.arity();
+        match instr {
+            Instruction::Add | Instruction::Mul => {
+                let lhs = self.get(modes[0], 0)?;
+                let rhs = self.get(modes[1], 1)?;
+                let result = arithmetic(instr, &lhs, &rhs)
+                    .ok_or_else(|| ah!("overflow: {} {}, {}", instr.mnemonic(), lhs, rhs))?;
+                self.put(modes[2], 2, result)?;
+            }
+            Instruction::LessThan | Instruction::EqualTo => {
+                let lhs = self.get(modes[0], 0)?;
+                let rhs = self.get(modes[1], 1)?;
+                let result = match (lhs.as_constant(), rhs.as_constant()) {
+                    (Some(l), Some(r)) if instr == Instruction::LessThan => {
+                        Value::from((l < r) as Int)
+                    }
+                    (Some(l), Some(r)) => Value::from((l == r) as Int),
+                    _ if instr == Instruction::EqualTo && lhs == rhs && lhs != Value::Opaque => {
+                        Value::from(1)
+                    }
+                    _ => Value::Opaque,
+                };
+                self.put(modes[2], 2, result)?;
+            }
+            Instruction::Input => {
+                let input = self
+                    .inputs
+                    .pop_front()
+                    .ok_or_else(|| ah!("no more input"))?;
+                self.put(modes[0], 0, input)?;
+            }
+            Instruction::Output => {
+                let value = self.get(modes[0], 0)?;
+                self.outputs.push(value);
+            }
+            Instruction::JumpTrue | Instruction::JumpFalse => {
+                let cond = self.get(modes[0], 0)?;
+                let cond = self.constant(&cond, "jump condition")?;
+                if (cond != 0) == (instr == Instruction::JumpTrue) {
+                    let target = self.get(modes[1], 1)?;
+                    let target = self.constant(&target, "jump target")?;
+                    if target < 0 {
+                        return Err(ah!("jump to negative address {}", target));
+                    }
+                    next = target as usize;
+                }
+            }
+            Instruction::SetBase => {
+                let offset = self.get(modes[0], 0)?;
+                let offset = self.constant(&offset, "relative base")?;
+                self.relative_base = self.relative_base.checked_add(offset).ok_or_else(|| {
+                    ah!("relative base {}{:+} overflows", self.relative_base, offset)
+                })?;
+            }
+            Instruction::Halt => {
+                self.halt = true;
+                next = self.pc;
+            }
+        }
+        self.pc = next;
+        Ok(())
+    }
+}
+
+/// `lhs op rhs`, or `None` on overflow.
+fn arithmetic(instr: Instruction, lhs: &Value, rhs: &Value) -> Option<Value> {
+    let (l, r) = match (lhs, rhs) {
+        (Value::Linear(l), Value::Linear(r)) => (l, r),
+        _ => return Some(Value::Opaque),
+    };
+    let result = match instr {
+        Instruction::Add => l.add(r)?,
+        _ => match (l.as_constant(), r.as_constant()) {
+            (Some(k), _) => r.scale(k)?,
+            (_, Some(k)) => l.scale(k)?,
+            _ => return Some(Value::Opaque),
+        },
+    };
+    Some(Value::Linear(result))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    #[test]
+    fn tracks_linear_expressions() {
+        let code = assemble(
+            "
+                in [x]
+                in [y]
+                mul [x], #3, [t]
+                add [t], [y], [t]
+                add [t], #-7, [t]
+                out [t]
+                mul [x], [y], [t]
+                out [t]
+                hlt
+            x:  .data 0
+            y:  .data 0
+            t:  .data 0
+            ",
+        )
+        .unwrap();
+        let mut m = Symbolic::new(&code);
+        let x = m.symbol("x");
+        let y = m.symbol("y");
+        m.provide_input(x.clone().into());
+        m.provide_input(y.clone().into());
+        m.run(100).unwrap();
+        let out = m.outputs();
+        assert_eq!(out[0].to_string(), "3*x + y - 7");
+        assert_eq!(out[1], Value::Opaque);
+
+        let expr = out[0].linear().unwrap();
+        assert_eq!(expr.coefficient(&x), 3);
+        assert_eq!(expr.constant(), -7);
+        let bounds = [(x, 0..=9), (y, 0..=9)];
+        assert_eq!(expr.solve(20, &bounds), Some(vec![6, 9]));
+        assert_eq!(expr.solve(100, &bounds), None);
+    }
+
+    #[test]
+    fn branches_must_be_concrete() {
+        let code = assemble("in [x]\njt [x], #0\nhlt\nx: .data 0").unwrap();
+        let mut m = Symbolic::new(&code);
+        let x = m.symbol("x");
+        m.provide_input(x.into());
+        let err = m.run(100).unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "at pc=2 (1005): jump condition depends on the unknowns: x"
+        );
+
+        let mut m = Symbolic::new(&code);
+        m.provide_input(Value::from(0));
+        m.run(100).unwrap();
+    }
+
+    #[test]
+    fn far_writes_stay_sparse() {
+        let far = 1 << 40;
+        let code = assemble(&format!(
+            "in [{0}]
+out [{0}]
+hlt",
+            far
+        ))
+        .unwrap();
+        let mut m = Symbolic::new(&code);
+        let x = m.symbol("x");
+        m.provide_input(x.into());
+        m.run(100).unwrap();
+        assert_eq!(m.outputs()[0].to_string(), "x");
+        assert_eq!(m.peek(far), m.outputs()[0]);
+        assert_eq!(m.peek(far + 1), Value::from(0));
+        assert_eq!(m.memory.len(), code.len());
+    }
+}
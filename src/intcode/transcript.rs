@@ -0,0 +1,320 @@
+use super::intcode_io::{Input, InputExhausted, Output};
+use super::Int;
+use anyhow::{anyhow as ah, Context, Result};
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// One value crossing between a machine and its IO device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Entry {
+    Input(Int),
+    Output(Int),
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Input(x) => write!(f, "in {}", x),
+            Entry::Output(x) => write!(f, "out {}", x),
+        }
+    }
+}
+
+impl FromStr for Entry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Entry> {
+        let mut parts = s.split_whitespace();
+        let entry = match (parts.next(), parts.next(), parts.next()) {
+            (Some("in"), Some(x), None) => Entry::Input(crate::util::parse_str(x)?),
+            (Some("out"), Some(x), None) => Entry::Output(crate::util::parse_str(x)?),
+            _ => return Err(ah!("expected 'in <value>' or 'out <value>', found '{}'", s)),
+        };
+        Ok(entry)
+    }
+}
+
+/// Every input and output of a session, in order. The text form has one
+/// entry per line; blank lines and `#` comments are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcript {
+    pub entries: Vec<Entry>,
+}
+
+impl Transcript {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(&path, self.to_string())
+            .with_context(|| format!("unable to write transcript {}", path.as_ref().display()))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Transcript> {
+        crate::util::read_to_string(&path)?
+            .parse()
+            .with_context(|| format!("invalid transcript {}", path.as_ref().display()))
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Transcript {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Transcript> {
+        let entries = s
+            .lines()
+            .enumerate()
+            .map(|(idx, l)| (idx, l.trim()))
+            .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'))
+            .map(|(idx, l)| l.parse().with_context(|| format!("line {}", idx + 1)))
+            .collect::<Result<Vec<Entry>>>()?;
+        Ok(Transcript { entries })
+    }
+}
+
+/// Passes IO through to `inner`, keeping a transcript of every value.
+///
+/// With `to_file`, each entry is also written out as it happens, so the
+/// transcript survives a session that ends badly.
+pub struct RecordingIO<D> {
+    inner: D,
+    transcript: Transcript,
+    log: Option<File>,
+}
+
+impl<D> RecordingIO<D> {
+    pub fn new(inner: D) -> RecordingIO<D> {
+        RecordingIO {
+            inner,
+            transcript: Transcript::default(),
+            log: None,
+        }
+    }
+
+    pub fn to_file<P: AsRef<Path>>(inner: D, path: P) -> Result<RecordingIO<D>> {
+        let file = File::create(&path)
+            .with_context(|| format!("unable to create {}", path.as_ref().display()))?;
+        Ok(RecordingIO {
+            log: Some(file),
+            ..RecordingIO::new(inner)
+        })
+    }
+
+    pub fn transcript(&self) -> &Transcript {
+        &self.transcript
+    }
+
+    pub fn into_parts(self) -> (D, Transcript) {
+        (self.inner, self.transcript)
+    }
+
+    /// Write `text` to the file as a comment, for context such as how the
+    /// program was set up. It isn't part of the transcript.
+    pub(crate) fn note(&mut self, text: &str) -> Result<()> {
+        if let Some(log) = &mut self.log {
+            writeln!(log, "# {}", text).context("unable to write transcript")?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn record(&mut self, entry: Entry) -> Result<()> {
+        if let Some(log) = &mut self.log {
+            writeln!(log, "{}", entry).context("unable to write transcript")?;
+        }
+        self.transcript.entries.push(entry);
+        Ok(())
+    }
+}
+
+impl<D: Input> Input for RecordingIO<D> {
+    fn input(&mut self) -> Result<Int> {
+        let x = self.inner.input()?;
+        self.record(Entry::Input(x))?;
+        Ok(x)
+    }
+}
+
+impl<D: Output> Output for RecordingIO<D> {
+    fn output(&mut self, out: Int) -> Result<()> {
+        self.inner.output(out)?;
+        self.record(Entry::Output(out))
+    }
+}
+
+/// Plays a transcript back to a machine, failing on the first input or
+/// output that doesn't line up with it.
+///
+/// Asking for input past the end is how an interactive session stops, so it
+/// is reported as `InputExhausted` rather than a divergence.
+#[derive(Debug, Clone)]
+pub struct ReplayIO {
+    entries: Vec<Entry>,
+    pos: usize,
+}
+
+impl ReplayIO {
+    pub fn new(transcript: Transcript) -> ReplayIO {
+        ReplayIO {
+            entries: transcript.entries,
+            pos: 0,
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<ReplayIO> {
+        Ok(ReplayIO::new(Transcript::load(path)?))
+    }
+
+    /// Check the whole transcript was used up.
+    pub fn finish(&self) -> Result<()> {
+        match self.entries.get(self.pos) {
+            Some(entry) => Err(ah!(
+                "session ended early: entry {} of {} ({}) was never replayed",
+                self.pos + 1,
+                self.entries.len(),
+                entry
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Input for ReplayIO {
+    fn input(&mut self) -> Result<Int> {
+        let entry = match self.entries.get(self.pos) {
+            Some(entry) => *entry,
+            None => return Err(InputExhausted.into()),
+        };
+        self.pos += 1;
+        match entry {
+            Entry::Input(x) => Ok(x),
+            entry => Err(ah!(
+                "diverged at entry {}: program wants input, transcript has '{}'",
+                self.pos,
+                entry
+            )),
+        }
+    }
+}
+
+impl Output for ReplayIO {
+    fn output(&mut self, out: Int) -> Result<()> {
+        let entry = self.entries.get(self.pos).cloned().ok_or_else(|| {
+            ah!(
+                "diverged after {} entries: program output {} past the end of the transcript",
+                self.pos,
+                out
+            )
+        })?;
+        self.pos += 1;
+        match entry {
+            Entry::Output(x) if x == out => Ok(()),
+            entry => Err(ah!(
+                "diverged at entry {}: program output {}, transcript has '{}'",
+                self.pos,
+                out,
+                entry
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::intcode_io::{FusedIO, VecIO};
+    use crate::intcode::{ErrorKind, IntCode};
+
+    const DAY25: &str = include_str!("../../input/day25");
+
+    fn ascii(lines: &[&str]) -> Vec<Int> {
+        lines
+            .iter()
+            .flat_map(|l| l.bytes().chain(Some(b'\n')))
+            .map(|b| b as Int)
+            .collect()
+    }
+
+    fn record_session(commands: &[&str]) -> Transcript {
+        let code = crate::util::parse_intcode(DAY25).unwrap();
+        let io = FusedIO {
+            input: VecIO::input(ascii(commands)),
+            output: VecIO::default(),
+        };
+        let mut ic = IntCode::new_from_device(code, RecordingIO::new(io));
+        let err = ic.run_till_end().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InputExhausted));
+        ic.into_device().into_parts().1
+    }
+
+    fn replay(transcript: Transcript) -> Result<()> {
+        let code = crate::util::parse_intcode(DAY25).unwrap();
+        let mut ic = IntCode::new_from_device(code, ReplayIO::new(transcript));
+        match ic.run_till_end() {
+            Err(e) if matches!(e.kind, ErrorKind::Io(_)) => Err(e.into()),
+            _ => ic.into_device().finish(),
+        }
+    }
+
+    #[test]
+    fn text_roundtrip() {
+        let transcript = record_session(&["north", "inv"]);
+        assert!(transcript.entries.contains(&Entry::Input('n' as Int)));
+        let text = format!("# day 25\n\n{}", transcript);
+        assert_eq!(text.parse::<Transcript>().unwrap(), transcript);
+        assert_eq!(
+            format!("{:#}", "in 1\nout\n".parse::<Transcript>().unwrap_err()),
+            "line 2: expected 'in <value>' or 'out <value>', found 'out'"
+        );
+    }
+
+    #[test]
+    fn replay_matches_recording() {
+        let transcript = record_session(&["north", "inv"]);
+        replay(transcript).unwrap();
+    }
+
+    #[test]
+    fn replay_reports_divergence() {
+        let mut transcript = record_session(&["north"]);
+        let first_input = transcript
+            .entries
+            .iter()
+            .position(|e| matches!(e, Entry::Input(_)))
+            .unwrap();
+        transcript.entries[first_input] = Entry::Input('s' as Int);
+        let err = format!("{:#}", replay(transcript).unwrap_err());
+        assert!(
+            err.starts_with("io device failed: diverged at entry "),
+            "{}",
+            err
+        );
+        assert!(err.contains(": program output "), "{}", err);
+    }
+
+    #[test]
+    fn replay_reports_unused_entries() {
+        let echo = vec![3, 0, 4, 0, 99];
+        let mut ic =
+            IntCode::new_from_device(echo.clone(), RecordingIO::new(VecIO::input(vec![5])));
+        ic.run_till_end().unwrap();
+        let mut transcript = ic.into_device().into_parts().1;
+        assert_eq!(transcript.to_string(), "in 5\nout 5\n");
+
+        transcript.entries.push(Entry::Output(9));
+        let mut ic = IntCode::new_from_device(echo, ReplayIO::new(transcript));
+        ic.run_till_end().unwrap();
+        assert_eq!(
+            format!("{:#}", ic.into_device().finish().unwrap_err()),
+            "session ended early: entry 3 of 3 (out 9) was never replayed"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use super::{Int, Memory, Word};
+
+fn mix(addr: usize, value: u64) -> u64 {
+    // splitmix64 finalizer over the (address, value) pair
+    let mut z = (addr as u64)
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add(value);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Spots a machine revisiting an exact earlier state without any IO in
+/// between, which means it will never halt.
+///
+/// This is Brent's cycle detection: the state is saved after 1, 2, 4, 8...
+/// steps since the last IO and every step is compared with the latest save,
+/// so a loop is caught within about twice its length once it is entered,
+/// and only one earlier state is ever kept.
+///
+/// Memory is tracked with an incremental hash of every write, so comparing
+/// a step costs the same however large memory is. A matching hash is
+/// confirmed by comparing the memories themselves, so a collision can't
+/// report a loop which isn't there.
+#[derive(Debug, Clone)]
+pub(crate) struct LoopDetector<M> {
+    mem_hash: u64,
+    saved: Option<Saved<M>>,
+    /// Steps from one save to the next, and since the last one.
+    interval: u64,
+    since_save: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Saved<M> {
+    pc: usize,
+    relative_base: Int,
+    mem_hash: u64,
+    memory: M,
+}
+
+impl<M> Default for LoopDetector<M> {
+    fn default() -> Self {
+        LoopDetector {
+            mem_hash: 0,
+            saved: None,
+            interval: 1,
+            since_save: 0,
+        }
+    }
+}
+
+impl<M: Memory> LoopDetector<M> {
+    pub(crate) fn record_write<W: Word>(&mut self, addr: usize, old: &W, new: &W) {
+        if old != new {
+            self.mem_hash ^= mix(addr, old.fingerprint()) ^ mix(addr, new.fingerprint());
+        }
+    }
+
+    /// IO may change what the program does next, so earlier states no
+    /// longer prove anything.
+    pub(crate) fn record_io(&mut self) {
+        self.saved = None;
+        self.interval = 1;
+        self.since_save = 0;
+    }
+
+    /// Returns true if this state was saved earlier since the last IO.
+    pub(crate) fn check(&mut self, pc: usize, relative_base: Int, memory: &M) -> bool {
+        if let Some(saved) = &self.saved {
+            if saved.pc == pc
+                && saved.relative_base == relative_base
+                && saved.mem_hash == self.mem_hash
+                && same_memory(&saved.memory, memory)
+            {
+                return true;
+            }
+        }
+        self.since_save += 1;
+        if self.since_save == self.interval {
+            self.saved = Some(Saved {
+                pc,
+                relative_base,
+                mem_hash: self.mem_hash,
+                memory: memory.clone(),
+            });
+            self.interval *= 2;
+            self.since_save = 0;
+        }
+        false
+    }
+
+    /// The same detector for a machine moved onto another memory backend.
+    /// The saved state is dropped, which only delays finding a loop.
+    pub(crate) fn with_memory<N>(self) -> LoopDetector<N> {
+        LoopDetector {
+            mem_hash: self.mem_hash,
+            ..LoopDetector::default()
+        }
+    }
+}
+
+/// Whether every address reads the same in `a` and `b`.
+fn same_memory<M: Memory>(a: &M, b: &M) -> bool {
+    let covers = |x: &M, y: &M| {
+        x.regions().into_iter().all(|(start, words)| {
+            words
+                .iter()
+                .enumerate()
+                .all(|(offset, word)| *word == y[start + offset])
+        })
+    };
+    covers(a, b) && covers(b, a)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::DenseMemory;
+
+    #[test]
+    fn memory_hash_returns_to_start() {
+        let mem: DenseMemory = DenseMemory::from(vec![0; 8]);
+        let mut d = LoopDetector::default();
+        assert!(!d.check(0, 0, &mem));
+        let (zero, seven): (Int, Int) = (0, 7);
+        d.record_write(5, &zero, &seven);
+        assert!(!d.check(0, 0, &mem));
+        d.record_write(5, &seven, &zero);
+        assert!(d.check(0, 0, &mem));
+        d.record_io();
+        assert!(!d.check(0, 0, &mem));
+    }
+
+    #[test]
+    fn hash_collisions_are_not_loops() {
+        let before: DenseMemory = DenseMemory::from(vec![0; 8]);
+        let mut after = before.clone();
+        after[3] = 1;
+        let mut d = LoopDetector::default();
+        assert!(!d.check(0, 0, &before));
+        // a write the hash doesn't see, as if two memories collided
+        assert!(!d.check(0, 0, &after));
+        assert!(d.check(0, 0, &before));
+    }
+
+    #[test]
+    fn long_loops_are_found_in_bounded_memory() {
+        let mem: DenseMemory = DenseMemory::from(vec![0; 8]);
+        let mut d = LoopDetector::default();
+        let period = 1000;
+        let found = (0..10 * period).position(|step| d.check(step % period, 0, &mem));
+        let found = found.unwrap();
+        assert!(found >= period && found < 3 * period, "{}", found);
+        assert!(d.saved.is_some());
+    }
+}
@@ -0,0 +1,142 @@
+use super::Int;
+use num::{BigInt, One, ToPrimitive, Zero};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// An integer type a machine can compute with.
+///
+/// The puzzles only need `Int`, but the same machine runs with `i128`, or
+/// with `num::BigInt` for programs whose numbers don't fit in any fixed
+/// width.
+pub trait Word:
+    Clone
+    + Default
+    + PartialEq
+    + PartialOrd
+    + Hash
+    + Zero
+    + One
+    + From<Int>
+    + FromStr
+    + fmt::Debug
+    + fmt::Display
+    + Send
+    + Sync
+    + 'static
+{
+    /// The value as an `Int`, if it fits in one.
+    fn to_int(&self) -> Option<Int>;
+
+    /// The value as a memory address, if it can be one.
+    fn to_addr(&self) -> Option<usize>;
+
+    /// The wrapped result, and whether it overflowed.
+    fn add_overflowing(&self, rhs: &Self) -> (Self, bool);
+    fn mul_overflowing(&self, rhs: &Self) -> (Self, bool);
+
+    fn add_saturating(&self, rhs: &Self) -> Self;
+    fn mul_saturating(&self, rhs: &Self) -> Self;
+
+    /// A hash of the value which is cheap for the fixed width types.
+    fn fingerprint(&self) -> u64;
+}
+
+impl Word for i64 {
+    #[inline]
+    fn to_int(&self) -> Option<Int> {
+        Some(*self)
+    }
+
+    #[inline]
+    fn to_addr(&self) -> Option<usize> {
+        self.to_usize()
+    }
+
+    #[inline]
+    fn add_overflowing(&self, rhs: &i64) -> (i64, bool) {
+        self.overflowing_add(*rhs)
+    }
+
+    #[inline]
+    fn mul_overflowing(&self, rhs: &i64) -> (i64, bool) {
+        self.overflowing_mul(*rhs)
+    }
+
+    fn add_saturating(&self, rhs: &i64) -> i64 {
+        self.saturating_add(*rhs)
+    }
+
+    fn mul_saturating(&self, rhs: &i64) -> i64 {
+        self.saturating_mul(*rhs)
+    }
+
+    #[inline]
+    fn fingerprint(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl Word for i128 {
+    fn to_int(&self) -> Option<Int> {
+        self.to_i64()
+    }
+
+    fn to_addr(&self) -> Option<usize> {
+        self.to_usize()
+    }
+
+    fn add_overflowing(&self, rhs: &i128) -> (i128, bool) {
+        self.overflowing_add(*rhs)
+    }
+
+    fn mul_overflowing(&self, rhs: &i128) -> (i128, bool) {
+        self.overflowing_mul(*rhs)
+    }
+
+    fn add_saturating(&self, rhs: &i128) -> i128 {
+        self.saturating_add(*rhs)
+    }
+
+    fn mul_saturating(&self, rhs: &i128) -> i128 {
+        self.saturating_mul(*rhs)
+    }
+
+    fn fingerprint(&self) -> u64 {
+        (*self as u64) ^ ((*self >> 64) as u64)
+    }
+}
+
+/// Never overflows, so every arithmetic policy behaves the same.
+impl Word for BigInt {
+    fn to_int(&self) -> Option<Int> {
+        self.to_i64()
+    }
+
+    fn to_addr(&self) -> Option<usize> {
+        self.to_usize()
+    }
+
+    fn add_overflowing(&self, rhs: &BigInt) -> (BigInt, bool) {
+        (self + rhs, false)
+    }
+
+    fn mul_overflowing(&self, rhs: &BigInt) -> (BigInt, bool) {
+        (self * rhs, false)
+    }
+
+    fn add_saturating(&self, rhs: &BigInt) -> BigInt {
+        self + rhs
+    }
+
+    fn mul_saturating(&self, rhs: &BigInt) -> BigInt {
+        self * rhs
+    }
+
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
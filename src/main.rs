@@ -10,6 +10,8 @@ extern crate log;
 pub mod graph;
 pub mod util;
 
+mod commands;
+
 pub mod display;
 pub mod intcode;
 pub mod orbital_data;
@@ -49,7 +51,13 @@ pub mod challenges {
     pub(crate) fn do_challenge(args: &ArgMatches) -> Result<()> {
         let day = args.value_of("day").unwrap().parse::<u32>()?;
         let part = args.value_of("part").unwrap().parse::<u32>()?;
-        let input = crate::util::read_to_string(args.value_of("input").unwrap())?;
+        let input = crate::intcode::image::read_source(args.value_of("input").unwrap())?;
+
+        let profiler = if args.is_present("profile") {
+            Some(crate::intcode::profile::profile_all())
+        } else {
+            None
+        };
 
         log::debug!("running day {}:{}", day, part);
         let result = match (day, part) {
@@ -114,6 +122,14 @@ pub mod challenges {
             }
         };
         println!("{}", result);
+        if let Some(profiler) = profiler {
+            let profile = profiler.snapshot();
+            if profile.steps() == 0 {
+                println!("no intcode was executed");
+            } else {
+                println!("\n{}", profile);
+            }
+        }
         Ok(())
     }
 
@@ -174,6 +190,14 @@ fn run(args: &ArgMatches) -> Result<()> {
 
     match args.subcommand() {
         ("challenge", Some(sub_m)) => crate::challenges::do_challenge(sub_m)?,
+        ("disasm", Some(sub_m)) => crate::commands::disasm(sub_m)?,
+        ("asm", Some(sub_m)) => crate::commands::asm(sub_m)?,
+        ("cfg", Some(sub_m)) => crate::commands::cfg(sub_m)?,
+        ("convert", Some(sub_m)) => crate::commands::convert(sub_m)?,
+        ("transpile", Some(sub_m)) => crate::commands::transpile(sub_m)?,
+        ("debug", Some(sub_m)) => crate::commands::debug(sub_m)?,
+        ("fuzz", Some(sub_m)) => crate::commands::fuzz(sub_m)?,
+        ("run", Some(sub_m)) => crate::commands::run(sub_m)?,
         ("", _) => return Err(ah!("Please provide a command:\n{}", args.usage())),
         subc => return Err(ah!("Unknown command: {:?}\n{}", subc, args.usage())),
     }
@@ -238,7 +262,134 @@ fn get_args() -> clap::ArgMatches<'static> {
                 .about("run one of the daily challenges")
                 .arg(Arg::with_name("day").required(true))
                 .arg(Arg::with_name("part").required(true))
-                .arg(Arg::with_name("input").required(true)),
+                .arg(Arg::with_name("input").required(true))
+                .arg(
+                    Arg::with_name("profile")
+                        .long("profile")
+                        .help("print an execution profile of the intcode program"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("disasm")
+                .about("disassemble an intcode program")
+                .arg(Arg::with_name("file").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("asm")
+                .about("assemble intcode source into a comma separated program")
+                .arg(Arg::with_name("file").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("cfg")
+                .about("print the control-flow graph of an intcode program as DOT")
+                .arg(Arg::with_name("file").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("convert")
+                .about("convert an intcode program between text and binary images")
+                .arg(Arg::with_name("input").required(true))
+                .arg(Arg::with_name("output").required(true))
+                .arg(
+                    Arg::with_name("to")
+                        .long("to")
+                        .takes_value(true)
+                        .possible_values(&["text", "binary"])
+                        .help("format to write, the other one from the input by default"),
+                )
+                .arg(
+                    Arg::with_name("asm")
+                        .long("asm")
+                        .help("read the input as assembly source, keeping its labels"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("transpile")
+                .about("compile an intcode program to a rust module under src/intcode")
+                .arg(Arg::with_name("file").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("debug")
+                .about("step through an intcode program interactively")
+                .arg(Arg::with_name("file").required(true))
+                .arg(
+                    Arg::with_name("input")
+                        .long("input")
+                        .takes_value(true)
+                        .help("comma separated values to feed the program"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("fuzz")
+                .about("compare the intcode machine against a reference interpreter")
+                .arg(
+                    Arg::with_name("seed")
+                        .long("seed")
+                        .takes_value(true)
+                        .help("seed for the random programs, from the clock by default"),
+                )
+                .arg(
+                    Arg::with_name("cases")
+                        .long("cases")
+                        .takes_value(true)
+                        .default_value("10000")
+                        .help("number of programs to try"),
+                )
+                .arg(
+                    Arg::with_name("budget")
+                        .long("budget")
+                        .takes_value(true)
+                        .default_value("1000")
+                        .help("steps each program may run for"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("run")
+                .about("run an intcode program, reading input from stdin")
+                .arg(Arg::with_name("file").required_unless("resume"))
+                .arg(
+                    Arg::with_name("input")
+                        .long("input")
+                        .takes_value(true)
+                        .help("comma separated values to feed the program first"),
+                )
+                .arg(
+                    Arg::with_name("ascii")
+                        .long("ascii")
+                        .help("print output as text and send input lines as ascii"),
+                )
+                .arg(
+                    Arg::with_name("resume")
+                        .long("resume")
+                        .takes_value(true)
+                        .help("continue from a snapshot written by '!save <file>'"),
+                )
+                .arg(
+                    Arg::with_name("patch")
+                        .long("patch")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .conflicts_with("resume")
+                        .help("apply the 'address=value' lines of a patch file before running"),
+                )
+                .arg(
+                    Arg::with_name("record")
+                        .long("record")
+                        .takes_value(true)
+                        .help("write every input and output to a transcript file"),
+                )
+                .arg(
+                    Arg::with_name("framebuffer")
+                        .long("framebuffer")
+                        .takes_value(true)
+                        .help("map a framebuffer at 'addr:WxH' and draw it once the program halts"),
+                )
+                .arg(
+                    Arg::with_name("random")
+                        .long("random")
+                        .takes_value(true)
+                        .help("map 'addr:bound', which reads as a random number below bound"),
+                ),
         )
         .subcommand(SubCommand::with_name("test"))
         .get_matches()